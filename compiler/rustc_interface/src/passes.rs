@@ -914,6 +914,10 @@ fn analysis(tcx: TyCtxt<'_>, cnum: CrateNum) -> Result<()> {
         );
     });
 
+    if sess.opts.debugging_opts.semver_check.is_some() {
+        sess.time("semver_checking", || rustc_metadata::semver_check::check_crate(tcx));
+    }
+
     Ok(())
 }
 
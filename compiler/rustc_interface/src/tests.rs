@@ -467,6 +467,7 @@ macro_rules! untracked {
     untracked!(ast_json, true);
     untracked!(ast_json_noexpand, true);
     untracked!(borrowck, String::from("other"));
+    untracked!(borrowck_explain, true);
     untracked!(borrowck_stats, true);
     untracked!(deduplicate_diagnostics, true);
     untracked!(dep_tasks, true);
@@ -499,6 +500,7 @@ macro_rules! untracked {
     untracked!(parse_only, true);
     untracked!(perf_stats, true);
     untracked!(polonius, true);
+    untracked!(polonius_algorithm, Some(String::from("Naive")));
     // `pre_link_arg` is omitted because it just forwards to `pre_link_args`.
     untracked!(pre_link_args, vec![String::from("abc"), String::from("def")]);
     untracked!(print_link_args, true);
@@ -511,6 +513,7 @@ macro_rules! untracked {
     untracked!(save_analysis, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
     untracked!(self_profile_events, Some(vec![String::new()]));
+    untracked!(semver_check, Some(String::from("foo_baseline")));
     untracked!(span_debug, true);
     untracked!(span_free_formats, true);
     untracked!(strip, Strip::None);
@@ -594,6 +597,7 @@ macro_rules! tracked {
     tracked!(tls_model, Some(TlsModel::GeneralDynamic));
     tracked!(treat_err_as_bug, Some(1));
     tracked!(unleash_the_miri_inside_of_you, true);
+    tracked!(unused_pub_items, true);
     tracked!(use_ctors_section, Some(true));
     tracked!(verify_llvm_ir, true);
 }
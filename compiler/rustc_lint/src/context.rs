@@ -30,6 +30,7 @@
 use rustc_middle::lint::LintDiagnosticBuilder;
 use rustc_middle::middle::privacy::AccessLevels;
 use rustc_middle::middle::stability;
+use rustc_middle::mir;
 use rustc_middle::ty::layout::{LayoutError, TyAndLayout};
 use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, print::Printer, subst::GenericArg, Ty, TyCtxt};
@@ -713,6 +714,28 @@ pub fn typeck_results(&self) -> &'tcx ty::TypeckResults<'tcx> {
         self.maybe_typeck_results().expect("`LateContext::typeck_results` called outside of body")
     }
 
+    /// Gets the optimized MIR for the current body, or `None` if outside a body or if the body
+    /// is not that of a function or closure (e.g. a `const` or `static` initializer).
+    ///
+    /// Lint passes in crates that depend on `rustc_mir`, such as clippy, can run the analyses in
+    /// `rustc_mir::dataflow` (e.g. `ConstantLocals` or `MaybeTaintedLocals`) over the returned
+    /// MIR. `rustc_lint` itself does not depend on `rustc_mir`, so the built-in lints get the
+    /// results of those two analyses through the `constant_locals` and `tainted_sink_calls`
+    /// queries instead.
+    ///
+    /// Note that this forces MIR to be built and optimized for the body, which would otherwise
+    /// not happen in `check` builds, so only call this from lints that actually need it.
+    pub fn enclosing_optimized_mir(&self) -> Option<&'tcx mir::Body<'tcx>> {
+        let body = self.enclosing_body?;
+        let hir = self.tcx.hir();
+        let def_id = hir.body_owner_def_id(body);
+        if hir.body_owner_kind(hir.local_def_id_to_hir_id(def_id)).is_fn_or_closure() {
+            Some(self.tcx.optimized_mir(def_id.to_def_id()))
+        } else {
+            None
+        }
+    }
+
     /// Returns the final resolution of a `QPath`, or `Res::Err` if unavailable.
     /// Unlike `.typeck_results().qpath_res(qpath, id)`, this can be used even outside
     /// bodies (e.g. for paths in `hir::Ty`), without any risk of ICE-ing.
@@ -510,6 +510,56 @@
     "detect unused, unexported items"
 }
 
+declare_lint! {
+    /// The `unused_pub_items` lint detects public items of a crate that are
+    /// not used by any other crate in the build of an executable.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs-dependency)
+    /// // In a library `helpers`, built with `-Z unused-pub-items`:
+    /// pub fn never_called() {}
+    ///
+    /// // In a binary depending on `helpers`, also built with
+    /// // `-Z unused-pub-items`:
+    /// fn main() {}
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// warning: public function `never_called` is never used outside of crate `helpers`
+    ///  --> helpers/src/lib.rs:2:1
+    ///   |
+    /// 2 | pub fn never_called() {}
+    ///   | ^^^^^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = note: `#[warn(unused_pub_items)]` on by default
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// The `dead_code` lint considers every exported item to be used, since
+    /// it only sees a single crate. With `-Z unused-pub-items`, every crate
+    /// records in its metadata which items of other crates it uses, and
+    /// the build of an executable reports the public items of its
+    /// dependencies that were also built with `-Z unused-pub-items` but
+    /// that no crate in the build uses. Such items can be removed, or made
+    /// private if they are only used in their own crate.
+    ///
+    /// Crates built without `-Z unused-pub-items`, such as the standard
+    /// library or dependencies built by another tool, do not record which
+    /// items they use. The items of every crate such a crate depends on are
+    /// therefore never reported, since any of them may be used there.
+    ///
+    /// The lint is only emitted with `-Z unused-pub-items`. It can be
+    /// silenced for individual items by allowing it on them in the crate
+    /// that defines them.
+    pub UNUSED_PUB_ITEMS,
+    Warn,
+    "detect public items that no crate in the build uses"
+}
+
 declare_lint! {
     /// The `unused_attributes` lint detects attributes that were not used by
     /// the compiler.
@@ -2742,6 +2792,45 @@
     "detects deprecation attributes with no effect",
 }
 
+declare_lint! {
+    /// The `semver_breaking_changes` lint detects changes to the public API
+    /// of a crate that need a new major version, when the crate is compiled
+    /// with `-Z semver-check`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore (needs an older version of the crate)
+    /// // Version 1.0.0 had `pub fn parse(s: &str) -> u32`.
+    /// pub fn parse(s: &str) -> u64 {
+    ///     s.parse().unwrap()
+    /// }
+    /// ```
+    ///
+    /// This will produce:
+    ///
+    /// ```text
+    /// error: breaking change: the return type of `parse` changed from `u32` to `u64`
+    ///  --> src/lib.rs:2:1
+    ///   |
+    /// 2 | pub fn parse(s: &str) -> u64 {
+    ///   | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    ///   |
+    ///   = note: `#[deny(semver_breaking_changes)]` on by default
+    /// ```
+    ///
+    /// ### Explanation
+    ///
+    /// `-Z semver-check=<name>` compares the public API of the crate against
+    /// an older version of it, passed as `--extern <name>=<path>`. Removed
+    /// items, changed signatures, new bounds, removed trait impls and new
+    /// required trait items can all break crates that depend on the older
+    /// version. This lint can be allowed when a new major version is
+    /// intended, or for individual changes that are known not to matter.
+    pub SEMVER_BREAKING_CHANGES,
+    Deny,
+    "detects changes to the public API that need a new major version",
+}
+
 declare_tool_lint! {
     pub rustc::INEFFECTIVE_UNSTABLE_TRAIT_IMPL,
     Deny,
@@ -2763,6 +2852,7 @@
         UNUSED_VARIABLES,
         UNUSED_ASSIGNMENTS,
         DEAD_CODE,
+        UNUSED_PUB_ITEMS,
         UNREACHABLE_CODE,
         UNREACHABLE_PATTERNS,
         OVERLAPPING_PATTERNS,
@@ -2832,6 +2922,7 @@
         UNINHABITED_STATIC,
         FUNCTION_ITEM_REFERENCES,
         USELESS_DEPRECATED,
+        SEMVER_BREAKING_CHANGES,
     ]
 }
 
@@ -40,6 +40,8 @@ pub struct CStore {
     allocator_kind: Option<AllocatorKind>,
     /// This crate has a `#[global_allocator]` item.
     has_global_allocator: bool,
+    /// The older version of this crate loaded for `-Z semver-check`.
+    semver_baseline: Option<CrateNum>,
 }
 
 pub struct CrateLoader<'a> {
@@ -184,6 +186,10 @@ fn crate_dependencies_in_reverse_postorder(&self, cnum: CrateNum) -> Vec<CrateNu
     crate fn has_global_allocator(&self) -> bool {
         self.has_global_allocator
     }
+
+    crate fn semver_baseline(&self) -> Option<CrateNum> {
+        self.semver_baseline
+    }
 }
 
 impl<'a> CrateLoader<'a> {
@@ -205,6 +211,7 @@ pub fn new(
                 injected_panic_runtime: None,
                 allocator_kind: None,
                 has_global_allocator: false,
+                semver_baseline: None,
             },
             used_extern_options: Default::default(),
         }
@@ -878,10 +885,64 @@ fn report_unused_deps(&mut self, krate: &ast::Crate) {
         }
     }
 
+    fn inject_semver_baseline(&mut self) {
+        let name = match &self.sess.opts.debugging_opts.semver_check {
+            Some(name) => Symbol::intern(name),
+            None => return,
+        };
+        info!("loading semver baseline `{}`", name);
+
+        if self.sess.opts.externs.get(&name.as_str()).and_then(|entry| entry.files()).is_none() {
+            self.sess.err(&format!(
+                "`-Z semver-check={}` needs the baseline to be passed as `--extern {}=<path>`",
+                name, name
+            ));
+            return;
+        }
+        self.used_extern_options.insert(name);
+
+        // The baseline is only found through its `--extern` paths, so it can have any name
+        // there. It is registered like any other dependency: an older version of the local
+        // crate built with the same `-C metadata` would have the same `DefPathHash`es as the
+        // local crate, which `verify_no_symbol_conflicts` rejects.
+        let mut locator = CrateLocator::new(
+            self.sess,
+            self.metadata_loader,
+            name,
+            None,
+            None,
+            None,
+            false, // is_host
+            PathKind::ExternFlag,
+            None,
+            Some(false), // is_proc_macro
+        );
+        let result = match locator.maybe_load_library_crate() {
+            Ok(Some(library)) => {
+                let baseline_name = library.metadata.get_root().name();
+                if baseline_name != self.local_crate_name {
+                    self.sess.err(&format!(
+                        "the `-Z semver-check` baseline `{}` is the crate `{}`, not an older \
+                         version of `{}`",
+                        name, baseline_name, self.local_crate_name
+                    ));
+                    return;
+                }
+                self.register_crate(None, None, library, CrateDepKind::MacrosOnly, name)
+            }
+            Ok(None) => Err(locator.into_error()),
+            Err(err) => Err(err),
+        };
+
+        let cnum = result.unwrap_or_else(|err| err.report(self.sess, DUMMY_SP));
+        self.cstore.semver_baseline = Some(cnum);
+    }
+
     pub fn postprocess(&mut self, krate: &ast::Crate) {
         self.inject_profiler_runtime();
         self.inject_allocator_crate(krate);
         self.inject_panic_runtime(krate);
+        self.inject_semver_baseline();
 
         info!("{:?}", CrateDump(&self.cstore));
 
@@ -34,3 +34,4 @@
 pub mod creader;
 pub mod dynamic_lib;
 pub mod locator;
+pub mod semver_check;
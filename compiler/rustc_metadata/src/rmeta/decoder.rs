@@ -1451,6 +1451,19 @@ fn get_missing_lang_items(&self, tcx: TyCtxt<'tcx>) -> &'tcx [lang_items::LangIt
         }
     }
 
+    fn get_used_extern_items(&self, tcx: TyCtxt<'tcx>) -> Option<&'tcx [DefId]> {
+        if !self.root.records_used_extern_items {
+            return None;
+        }
+        Some(tcx.arena.alloc_from_iter(self.root.used_extern_items.decode(self)))
+    }
+
+    fn get_unused_pub_item_candidates(&self, tcx: TyCtxt<'tcx>) -> &'tcx [DefId] {
+        tcx.arena.alloc_from_iter(
+            self.root.unused_pub_item_candidates.decode(self).map(|index| self.local_def_id(index)),
+        )
+    }
+
     fn get_fn_param_names(&self, tcx: TyCtxt<'tcx>, id: DefIndex) -> &'tcx [Ident] {
         let param_names = match self.kind(id) {
             EntryKind::Fn(data) | EntryKind::ForeignFn(data) => data.decode(self).param_names,
@@ -220,6 +220,11 @@ fn into_args(self) -> (DefId, DefId) {
     defined_lang_items => { cdata.get_lang_items(tcx) }
     diagnostic_items => { cdata.get_diagnostic_items() }
     missing_lang_items => { cdata.get_missing_lang_items(tcx) }
+    used_extern_items => { cdata.get_used_extern_items(tcx) }
+    unused_pub_item_candidates => { cdata.get_unused_pub_item_candidates(tcx) }
+    crate_dependencies => {
+        tcx.arena.alloc_slice(&CStore::from_tcx(tcx).crate_dependencies_in_postorder(def_id.krate))
+    }
 
     missing_extern_crate_item => {
         let r = matches!(*cdata.extern_crate.borrow(), Some(extern_crate) if !extern_crate.is_direct());
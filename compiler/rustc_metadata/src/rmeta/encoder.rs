@@ -552,6 +552,10 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
         let lang_items_missing = self.encode_lang_items_missing();
         let lang_item_bytes = self.position() - i;
 
+        // Encode the items used across crates, for `-Z unused-pub-items`.
+        let used_extern_items = self.encode_used_extern_items();
+        let unused_pub_item_candidates = self.encode_unused_pub_item_candidates();
+
         // Encode the diagnostic items.
         i = self.position();
         let diagnostic_items = self.encode_diagnostic_items();
@@ -670,6 +674,9 @@ fn encode_crate_root(&mut self) -> Lazy<CrateRoot<'tcx>> {
             lang_items,
             diagnostic_items,
             lang_items_missing,
+            records_used_extern_items: tcx.sess.opts.debugging_opts.unused_pub_items,
+            used_extern_items,
+            unused_pub_item_candidates,
             native_libraries,
             foreign_modules,
             source_map,
@@ -1624,6 +1631,25 @@ fn encode_lang_items_missing(&mut self) -> Lazy<[lang_items::LangItem]> {
         self.lazy(&tcx.lang_items().missing)
     }
 
+    fn encode_used_extern_items(&mut self) -> Lazy<[DefId]> {
+        empty_proc_macro!(self);
+        let tcx = self.tcx;
+        if !tcx.sess.opts.debugging_opts.unused_pub_items {
+            return Lazy::empty();
+        }
+        self.lazy(tcx.used_extern_items(LOCAL_CRATE).unwrap_or_default())
+    }
+
+    fn encode_unused_pub_item_candidates(&mut self) -> Lazy<[DefIndex]> {
+        empty_proc_macro!(self);
+        let tcx = self.tcx;
+        if !tcx.sess.opts.debugging_opts.unused_pub_items {
+            return Lazy::empty();
+        }
+        let candidates = tcx.unused_pub_item_candidates(LOCAL_CRATE);
+        self.lazy(candidates.iter().map(|def_id| def_id.index))
+    }
+
     /// Encodes an index, mapping each trait to its (local) implementations.
     fn encode_impls(&mut self) -> Lazy<[TraitImpls]> {
         empty_proc_macro!(self);
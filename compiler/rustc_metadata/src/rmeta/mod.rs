@@ -214,6 +214,13 @@ macro_rules! Lazy {
     lib_features: Lazy<[(Symbol, Option<Symbol>)]>,
     lang_items: Lazy<[(DefIndex, usize)]>,
     lang_items_missing: Lazy<[lang_items::LangItem]>,
+    /// Whether this crate was built with `-Z unused-pub-items` and so records the items of
+    /// other crates it uses in `used_extern_items`.
+    records_used_extern_items: bool,
+    /// Items of other crates used by this crate, recorded under `-Z unused-pub-items`.
+    used_extern_items: Lazy<[DefId]>,
+    /// Public items of this crate checked by `-Z unused-pub-items`.
+    unused_pub_item_candidates: Lazy<[DefIndex]>,
     diagnostic_items: Lazy<[(Symbol, DefIndex)]>,
     native_libraries: Lazy<[NativeLib]>,
     foreign_modules: Lazy<[ForeignModule]>,
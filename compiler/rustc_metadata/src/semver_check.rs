@@ -0,0 +1,764 @@
+//! Implements `-Z semver-check=<name>`, which compares the public API of the local crate against
+//! an older version of it and reports the changes that need a new major or minor version.
+//!
+//! The older version, the *baseline*, is passed as `--extern <name>=<path>` and loaded from its
+//! metadata by the `CrateLoader` as a crate that is never linked. Breaking changes are reported
+//! through the `semver_breaking_changes` lint and additions are reported as notes.
+//!
+//! Items of both versions are matched by their path from the crate root through public modules
+//! and re-exports, so moving an item to another public path is reported as a removal and an
+//! addition. Types and bounds are compared structurally, treating items of both versions as the
+//! same when their `DefPath`s match.
+
+use crate::creader::CStore;
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Namespace, Res};
+use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc_middle::ty::subst::{GenericArg, GenericArgKind, SubstsRef};
+use rustc_middle::ty::{self, GenericParamDefKind, PredicateAtom, Region, Ty, TyCtxt};
+use rustc_session::lint::builtin::SEMVER_BREAKING_CHANGES;
+use rustc_span::symbol::Symbol;
+use rustc_span::Span;
+
+use std::collections::BTreeMap;
+use std::mem;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Severity {
+    /// Additions to the API, which need a new minor version.
+    Minor,
+    /// Changes that can break downstream crates, which need a new major version.
+    Major,
+}
+
+/// The public items of a crate, keyed by their path and namespace.
+type PublicApi = BTreeMap<(String, Namespace), DefId>;
+
+pub fn check_crate(tcx: TyCtxt<'_>) {
+    let baseline = match CStore::from_tcx(tcx).semver_baseline() {
+        Some(baseline) => baseline,
+        None => return,
+    };
+
+    let old_api = public_api(tcx, baseline);
+    let new_api = public_api(tcx, LOCAL_CRATE);
+    let mut checker = SemverChecker {
+        tcx,
+        baseline,
+        old_public: old_api.values().copied().collect(),
+        new_public: new_api.values().copied().collect(),
+        severity: None,
+    };
+
+    for ((path, ns), &old) in &old_api {
+        match new_api.get(&(path.clone(), *ns)) {
+            Some(&new) => checker.compare_items(path, old, new),
+            None => checker.report(
+                Severity::Major,
+                tcx.def_span(old),
+                format!("{} `{}` was removed", checker.descr(old), path),
+            ),
+        }
+    }
+    for ((path, ns), &new) in &new_api {
+        if !old_api.contains_key(&(path.clone(), *ns)) {
+            checker.report(
+                Severity::Minor,
+                tcx.def_span(new),
+                format!("{} `{}` was added", checker.descr(new), path),
+            );
+        }
+    }
+    checker.compare_trait_impls();
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    tcx.sess.note_without_error(&match checker.severity {
+        Some(Severity::Major) => {
+            format!("the changes to `{}` since the baseline need a new major version", crate_name)
+        }
+        Some(Severity::Minor) => {
+            format!("the changes to `{}` since the baseline need a new minor version", crate_name)
+        }
+        None => format!("the public API of `{}` is unchanged since the baseline", crate_name),
+    });
+}
+
+/// Collects the items reachable from the root of `krate` through public modules.
+fn public_api(tcx: TyCtxt<'_>, krate: CrateNum) -> PublicApi {
+    let mut api = PublicApi::new();
+    let mut visited = FxHashSet::default();
+    let root = DefId { krate, index: CRATE_DEF_INDEX };
+    collect_public_items(tcx, root, &mut Vec::new(), &mut api, &mut visited);
+    api
+}
+
+fn collect_public_items(
+    tcx: TyCtxt<'_>,
+    module: DefId,
+    path: &mut Vec<Symbol>,
+    api: &mut PublicApi,
+    visited: &mut FxHashSet<DefId>,
+) {
+    if !visited.insert(module) {
+        return;
+    }
+
+    for (name, kind, def_id) in public_children(tcx, module) {
+        let ns = match kind.ns() {
+            Some(ns) => ns,
+            None => continue,
+        };
+        path.push(name);
+        let path_str = path.iter().map(|name| name.to_string()).collect::<Vec<_>>().join("::");
+        api.entry((path_str, ns)).or_insert(def_id);
+        // Re-exported modules of other crates are not part of this crate's API.
+        if kind == DefKind::Mod && def_id.krate == module.krate {
+            collect_public_items(tcx, def_id, path, api, visited);
+        }
+        path.pop();
+    }
+}
+
+/// Returns the name, kind and `DefId` of the public items and re-exports of `module`.
+fn public_children(tcx: TyCtxt<'_>, module: DefId) -> Vec<(Symbol, DefKind, DefId)> {
+    let local_module = match module.as_local() {
+        Some(local_module) => local_module,
+        None => {
+            return tcx
+                .item_children(module)
+                .iter()
+                .filter(|child| child.vis == ty::Visibility::Public)
+                .filter_map(|child| match child.res {
+                    Res::Def(kind, def_id) => Some((child.ident.name, kind, def_id)),
+                    _ => None,
+                })
+                .collect();
+        }
+    };
+
+    let hir = tcx.hir();
+    let mut children = Vec::new();
+    let mut push_child = |name, hir_id| {
+        let def_id = hir.local_def_id(hir_id).to_def_id();
+        children.push((name, tcx.def_kind(def_id), def_id));
+    };
+    let (module_items, ..) = hir.get_module(local_module);
+    for item_id in module_items.item_ids {
+        let item = hir.item(item_id.id);
+        match item.kind {
+            hir::ItemKind::ForeignMod(ref foreign_mod) => {
+                for foreign_item in foreign_mod.items {
+                    if foreign_item.vis.node.is_pub() {
+                        push_child(foreign_item.ident.name, foreign_item.hir_id);
+                    }
+                }
+            }
+            // Re-exports are handled below, and impls are compared separately.
+            hir::ItemKind::Use(..)
+            | hir::ItemKind::ExternCrate(..)
+            | hir::ItemKind::Impl { .. }
+            | hir::ItemKind::GlobalAsm(..) => {}
+            _ if item.vis.node.is_pub() => push_child(item.ident.name, item.hir_id),
+            _ => {}
+        }
+    }
+    for export in tcx.module_exports(local_module).unwrap_or(&[]) {
+        if let (Res::Def(kind, def_id), ty::Visibility::Public) = (export.res, export.vis) {
+            children.push((export.ident.name, kind, def_id));
+        }
+    }
+    children
+}
+
+struct SemverChecker<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    baseline: CrateNum,
+    /// Items that are part of the public API of the baseline.
+    old_public: FxHashSet<DefId>,
+    /// Items that are part of the public API of the local crate.
+    new_public: FxHashSet<DefId>,
+    /// The most severe change reported so far.
+    severity: Option<Severity>,
+}
+
+impl SemverChecker<'tcx> {
+    fn report(&mut self, severity: Severity, span: Span, message: String) {
+        match severity {
+            Severity::Major => self.tcx.struct_span_lint_hir(
+                SEMVER_BREAKING_CHANGES,
+                hir::CRATE_HIR_ID,
+                span,
+                |lint| lint.build(&format!("breaking change: {}", message)).emit(),
+            ),
+            Severity::Minor => {
+                self.tcx.sess.span_note_without_error(span, &format!("addition: {}", message))
+            }
+        }
+        self.severity = self.severity.max(Some(severity));
+    }
+
+    fn descr(&self, def_id: DefId) -> &'static str {
+        self.tcx.def_kind(def_id).descr(def_id)
+    }
+
+    fn compare_items(&mut self, path: &str, old: DefId, new: DefId) {
+        let tcx = self.tcx;
+        let (old_kind, new_kind) = (tcx.def_kind(old), tcx.def_kind(new));
+        if old_kind != new_kind {
+            let (old_article, old_descr) = tcx.article_and_description(old);
+            let (new_article, new_descr) = tcx.article_and_description(new);
+            let message = format!(
+                "`{}` changed from {} {} to {} {}",
+                path, old_article, old_descr, new_article, new_descr,
+            );
+            self.report(Severity::Major, tcx.def_span(new), message);
+            return;
+        }
+        if old == new {
+            // Both versions re-export the same item of another crate.
+            return;
+        }
+
+        match old_kind {
+            DefKind::Fn | DefKind::AssocFn => {
+                self.compare_generics(path, old, new);
+                self.compare_fn_sigs(path, old, new);
+                self.compare_predicates(path, old, new);
+            }
+            DefKind::Const | DefKind::AssocConst | DefKind::Static => {
+                self.compare_types(path, old, new);
+            }
+            DefKind::TyAlias => {
+                self.compare_generics(path, old, new);
+                self.compare_types(path, old, new);
+            }
+            DefKind::Struct | DefKind::Union | DefKind::Enum => {
+                self.compare_generics(path, old, new);
+                self.compare_predicates(path, old, new);
+                self.compare_adts(path, old, new);
+                self.compare_inherent_items(path, old, new);
+            }
+            DefKind::Trait => {
+                self.compare_generics(path, old, new);
+                self.compare_predicates(path, old, new);
+                self.compare_trait_items(path, old, new);
+            }
+            _ => {}
+        }
+    }
+
+    fn compare_generics(&mut self, path: &str, old: DefId, new: DefId) {
+        let tcx = self.tcx;
+        let (old_generics, new_generics) = (tcx.generics_of(old), tcx.generics_of(new));
+        let is_lifetime =
+            |param: &&ty::GenericParamDef| matches!(param.kind, GenericParamDefKind::Lifetime);
+        let old_lifetimes = old_generics.params.iter().filter(is_lifetime).count();
+        let new_lifetimes = new_generics.params.iter().filter(is_lifetime).count();
+        if old_lifetimes != new_lifetimes {
+            let message = format!(
+                "the number of lifetime parameters of `{}` changed from {} to {}",
+                path, old_lifetimes, new_lifetimes,
+            );
+            self.report(Severity::Major, tcx.def_span(new), message);
+        }
+
+        let old_params = old_generics.params.iter().filter(|param| !is_lifetime(param));
+        let mut new_params = new_generics.params.iter().filter(|param| !is_lifetime(param));
+        for old_param in old_params {
+            let new_param = match new_params.next() {
+                Some(new_param) => new_param,
+                None => {
+                    let message =
+                        format!("generic parameter `{}` of `{}` was removed", old_param.name, path);
+                    self.report(Severity::Major, tcx.def_span(new), message);
+                    continue;
+                }
+            };
+            if mem::discriminant(&old_param.kind) != mem::discriminant(&new_param.kind) {
+                let message = format!(
+                    "generic parameter `{}` of `{}` changed from a {} parameter to a {} parameter",
+                    new_param.name,
+                    path,
+                    old_param.kind.descr(),
+                    new_param.kind.descr(),
+                );
+                self.report(Severity::Major, tcx.def_span(new), message);
+            }
+        }
+        for new_param in new_params {
+            // Defaulted type parameters can only be declared on types, where they don't break
+            // any existing uses.
+            let severity = match new_param.kind {
+                GenericParamDefKind::Type { has_default: true, .. } => Severity::Minor,
+                _ => Severity::Major,
+            };
+            let message = format!("generic parameter `{}` was added to `{}`", new_param.name, path);
+            self.report(severity, tcx.def_span(new), message);
+        }
+    }
+
+    fn compare_fn_sigs(&mut self, path: &str, old: DefId, new: DefId) {
+        let tcx = self.tcx;
+        let span = tcx.def_span(new);
+        let old_sig = tcx.fn_sig(old).skip_binder();
+        let new_sig = tcx.fn_sig(new).skip_binder();
+
+        if old_sig.unsafety == hir::Unsafety::Normal && new_sig.unsafety == hir::Unsafety::Unsafe {
+            self.report(Severity::Major, span, format!("`{}` became unsafe", path));
+        }
+        if old_sig.abi != new_sig.abi {
+            let message =
+                format!("the ABI of `{}` changed from {} to {}", path, old_sig.abi, new_sig.abi);
+            self.report(Severity::Major, span, message);
+        }
+        if old_sig.c_variadic != new_sig.c_variadic {
+            self.report(Severity::Major, span, format!("`{}` changed its variadicity", path));
+        }
+        if tcx.is_const_fn_raw(old) && !tcx.is_const_fn_raw(new) {
+            self.report(Severity::Major, span, format!("`{}` is no longer a `const fn`", path));
+        }
+
+        let (old_inputs, new_inputs) = (old_sig.inputs(), new_sig.inputs());
+        if old_inputs.len() != new_inputs.len() {
+            let message = format!(
+                "the number of parameters of `{}` changed from {} to {}",
+                path,
+                old_inputs.len(),
+                new_inputs.len(),
+            );
+            self.report(Severity::Major, span, message);
+        } else {
+            for (i, (&old_input, &new_input)) in old_inputs.iter().zip(new_inputs).enumerate() {
+                if !self.same_ty(old_input, new_input) {
+                    let message = format!(
+                        "the type of parameter #{} of `{}` changed from `{}` to `{}`",
+                        i + 1,
+                        path,
+                        old_input,
+                        new_input,
+                    );
+                    self.report(Severity::Major, span, message);
+                }
+            }
+        }
+        if !self.same_ty(old_sig.output(), new_sig.output()) {
+            let message = format!(
+                "the return type of `{}` changed from `{}` to `{}`",
+                path,
+                old_sig.output(),
+                new_sig.output(),
+            );
+            self.report(Severity::Major, span, message);
+        }
+    }
+
+    fn compare_types(&mut self, path: &str, old: DefId, new: DefId) {
+        let (old_ty, new_ty) = (self.tcx.type_of(old), self.tcx.type_of(new));
+        if !self.same_ty(old_ty, new_ty) {
+            let message =
+                format!("the type of `{}` changed from `{}` to `{}`", path, old_ty, new_ty);
+            self.report(Severity::Major, self.tcx.def_span(new), message);
+        }
+    }
+
+    /// Reports the bounds that `new` has and `old` doesn't. Removing a bound only breaks
+    /// downstream crates for traits, whose users may rely on it.
+    fn compare_predicates(&mut self, path: &str, old: DefId, new: DefId) {
+        let tcx = self.tcx;
+        let old_predicates = tcx.predicates_of(old).predicates;
+        let new_predicates = tcx.predicates_of(new).predicates;
+        for &(new_predicate, span) in new_predicates {
+            if !old_predicates.iter().any(|&(old, _)| self.same_predicate(old, new_predicate)) {
+                let message = format!("`{}` gained the bound `{}`", path, new_predicate);
+                self.report(Severity::Major, span, message);
+            }
+        }
+        if tcx.def_kind(old) == DefKind::Trait {
+            for &(old_predicate, _) in old_predicates {
+                if !new_predicates.iter().any(|&(new, _)| self.same_predicate(old_predicate, new)) {
+                    let message = format!("`{}` lost the bound `{}`", path, old_predicate);
+                    self.report(Severity::Major, tcx.def_span(new), message);
+                }
+            }
+        }
+    }
+
+    fn compare_adts(&mut self, path: &str, old: DefId, new: DefId) {
+        let tcx = self.tcx;
+        let (old_adt, new_adt) = (tcx.adt_def(old), tcx.adt_def(new));
+        if !old_adt.is_enum() {
+            self.compare_variants(path, old_adt.non_enum_variant(), new_adt.non_enum_variant());
+            return;
+        }
+
+        for old_variant in &old_adt.variants {
+            let variant_path = format!("{}::{}", path, old_variant.ident);
+            match new_adt.variants.iter().find(|v| v.ident.name == old_variant.ident.name) {
+                Some(new_variant) => self.compare_variants(&variant_path, old_variant, new_variant),
+                None => {
+                    let message = format!("variant `{}` was removed", variant_path);
+                    self.report(Severity::Major, tcx.def_span(new), message);
+                }
+            }
+        }
+        if !old_adt.is_variant_list_non_exhaustive() && new_adt.is_variant_list_non_exhaustive() {
+            let message = format!("`{}` became `#[non_exhaustive]`", path);
+            self.report(Severity::Major, tcx.def_span(new), message);
+        }
+        for new_variant in &new_adt.variants {
+            if !old_adt.variants.iter().any(|v| v.ident.name == new_variant.ident.name) {
+                // Adding a variant breaks exhaustive matches outside of the crate.
+                let severity = if old_adt.is_variant_list_non_exhaustive() {
+                    Severity::Minor
+                } else {
+                    Severity::Major
+                };
+                let message = format!("variant `{}::{}` was added", path, new_variant.ident);
+                self.report(severity, tcx.def_span(new_variant.def_id), message);
+            }
+        }
+    }
+
+    fn compare_variants(&mut self, path: &str, old: &ty::VariantDef, new: &ty::VariantDef) {
+        let tcx = self.tcx;
+        let span = tcx.def_span(new.def_id);
+        if old.ctor_kind != new.ctor_kind {
+            let message = format!(
+                "the kind of `{}` changed from {:?} to {:?}",
+                path, old.ctor_kind, new.ctor_kind,
+            );
+            self.report(Severity::Major, span, message);
+            return;
+        }
+
+        let is_public = |field: &&ty::FieldDef| field.vis == ty::Visibility::Public;
+        for old_field in old.fields.iter().filter(is_public) {
+            let new_field =
+                new.fields.iter().filter(is_public).find(|f| f.ident.name == old_field.ident.name);
+            match new_field {
+                Some(new_field) => {
+                    let old_ty = tcx.type_of(old_field.did);
+                    let new_ty = tcx.type_of(new_field.did);
+                    if !self.same_ty(old_ty, new_ty) {
+                        let message = format!(
+                            "the type of field `{}` of `{}` changed from `{}` to `{}`",
+                            old_field.ident, path, old_ty, new_ty,
+                        );
+                        self.report(Severity::Major, tcx.def_span(new_field.did), message);
+                    }
+                }
+                None => {
+                    let message =
+                        format!("public field `{}` of `{}` was removed", old_field.ident, path);
+                    self.report(Severity::Major, span, message);
+                }
+            }
+        }
+
+        // Downstream crates can construct and exhaustively match variants whose fields are all
+        // public, so any new field breaks them.
+        let old_exhaustive =
+            !old.is_field_list_non_exhaustive() && old.fields.iter().all(|f| is_public(&f));
+        if old_exhaustive && new.is_field_list_non_exhaustive() {
+            self.report(Severity::Major, span, format!("`{}` became `#[non_exhaustive]`", path));
+        }
+        for new_field in &new.fields {
+            if old.fields.iter().any(|f| f.ident.name == new_field.ident.name) {
+                continue;
+            }
+            let severity = if old_exhaustive { Severity::Major } else { Severity::Minor };
+            if severity == Severity::Minor && !is_public(&new_field) {
+                continue;
+            }
+            let message = format!("field `{}` was added to `{}`", new_field.ident, path);
+            self.report(severity, tcx.def_span(new_field.did), message);
+        }
+    }
+
+    fn compare_inherent_items(&mut self, path: &str, old: DefId, new: DefId) {
+        let old_items = self.public_inherent_items(old);
+        let new_items = self.public_inherent_items(new);
+        for ((name, ns), &old_item) in &old_items {
+            let item_path = format!("{}::{}", path, name);
+            match new_items.get(&(name.clone(), *ns)) {
+                Some(&new_item) => self.compare_items(&item_path, old_item, new_item),
+                None => {
+                    let message = format!("{} `{}` was removed", self.descr(old_item), item_path);
+                    self.report(Severity::Major, self.tcx.def_span(new), message);
+                }
+            }
+        }
+        for ((name, ns), &new_item) in &new_items {
+            if !old_items.contains_key(&(name.clone(), *ns)) {
+                let message = format!("{} `{}::{}` was added", self.descr(new_item), path, name);
+                self.report(Severity::Minor, self.tcx.def_span(new_item), message);
+            }
+        }
+    }
+
+    fn public_inherent_items(&self, adt: DefId) -> BTreeMap<(String, Namespace), DefId> {
+        let tcx = self.tcx;
+        let mut items = BTreeMap::new();
+        for &impl_def_id in tcx.inherent_impls(adt) {
+            for item in tcx.associated_items(impl_def_id).in_definition_order() {
+                if item.vis == ty::Visibility::Public {
+                    items.insert((item.ident.to_string(), item.kind.namespace()), item.def_id);
+                }
+            }
+        }
+        items
+    }
+
+    fn compare_trait_items(&mut self, path: &str, old: DefId, new: DefId) {
+        let tcx = self.tcx;
+        let old_items = tcx.associated_items(old);
+        let new_items = tcx.associated_items(new);
+        let find = |items: &ty::AssociatedItems<'_>, item: &ty::AssocItem| {
+            items
+                .in_definition_order()
+                .find(|other| other.ident.name == item.ident.name && other.kind == item.kind)
+                .copied()
+        };
+
+        for old_item in old_items.in_definition_order() {
+            let item_path = format!("{}::{}", path, old_item.ident);
+            match find(new_items, old_item) {
+                Some(new_item) => {
+                    self.compare_items(&item_path, old_item.def_id, new_item.def_id);
+                    if old_item.defaultness.has_value() && !new_item.defaultness.has_value() {
+                        let message = format!("`{}` no longer has a default", item_path);
+                        self.report(Severity::Major, tcx.def_span(new_item.def_id), message);
+                    }
+                }
+                None => {
+                    let descr = self.descr(old_item.def_id);
+                    let message = format!("{} `{}` was removed", descr, item_path);
+                    self.report(Severity::Major, tcx.def_span(new), message);
+                }
+            }
+        }
+        for new_item in new_items.in_definition_order() {
+            if find(old_items, new_item).is_some() {
+                continue;
+            }
+            // Implementations outside of the crate don't provide the new item.
+            let (severity, defaulted) = if new_item.defaultness.has_value() {
+                (Severity::Minor, "defaulted")
+            } else {
+                (Severity::Major, "non-defaulted")
+            };
+            let message = format!(
+                "{} {} `{}::{}` was added",
+                defaulted,
+                self.descr(new_item.def_id),
+                path,
+                new_item.ident,
+            );
+            self.report(severity, tcx.def_span(new_item.def_id), message);
+        }
+    }
+
+    /// Compares the trait impls for public traits and types of both versions.
+    fn compare_trait_impls(&mut self) {
+        let tcx = self.tcx;
+        let old_impls: Vec<_> = tcx
+            .all_trait_implementations(self.baseline)
+            .iter()
+            .filter_map(|&(impl_def_id, _)| Some((impl_def_id, tcx.impl_trait_ref(impl_def_id)?)))
+            .filter(|&(_, trait_ref)| self.is_public_trait_ref(trait_ref, &self.old_public))
+            .collect();
+        let new_impls: Vec<_> = tcx
+            .all_local_trait_impls(LOCAL_CRATE)
+            .values()
+            .flatten()
+            .map(|&hir_id| tcx.hir().local_def_id(hir_id).to_def_id())
+            .filter_map(|impl_def_id| Some((impl_def_id, tcx.impl_trait_ref(impl_def_id)?)))
+            .filter(|&(_, trait_ref)| self.is_public_trait_ref(trait_ref, &self.new_public))
+            .collect();
+
+        let same_trait_ref = |this: &Self, old: ty::TraitRef<'tcx>, new: ty::TraitRef<'tcx>| {
+            this.same_def(old.def_id, new.def_id) && this.same_substs(old.substs, new.substs)
+        };
+        for &(old_impl, old_trait_ref) in &old_impls {
+            if !new_impls.iter().any(|&(_, new)| same_trait_ref(self, old_trait_ref, new)) {
+                let message = format!(
+                    "the implementation of `{}` for `{}` was removed",
+                    old_trait_ref.print_only_trait_path(),
+                    old_trait_ref.self_ty(),
+                );
+                self.report(Severity::Major, tcx.def_span(old_impl), message);
+            }
+        }
+        for &(new_impl, new_trait_ref) in &new_impls {
+            if !old_impls.iter().any(|&(_, old)| same_trait_ref(self, old, new_trait_ref)) {
+                let message = format!(
+                    "an implementation of `{}` for `{}` was added",
+                    new_trait_ref.print_only_trait_path(),
+                    new_trait_ref.self_ty(),
+                );
+                self.report(Severity::Minor, tcx.def_span(new_impl), message);
+            }
+        }
+    }
+
+    /// Whether downstream crates can name the trait and the types of `trait_ref`. Items of
+    /// other crates are assumed to be public.
+    fn is_public_trait_ref(
+        &self,
+        trait_ref: ty::TraitRef<'tcx>,
+        public: &FxHashSet<DefId>,
+    ) -> bool {
+        let is_public = |def_id: DefId| {
+            !(def_id.is_local() || def_id.krate == self.baseline) || public.contains(&def_id)
+        };
+        is_public(trait_ref.def_id)
+            && trait_ref.substs.iter().flat_map(|arg| arg.walk()).all(|arg| match arg.unpack() {
+                GenericArgKind::Type(ty) => match *ty.kind() {
+                    ty::Adt(adt, _) => is_public(adt.did),
+                    ty::Foreign(def_id) => is_public(def_id),
+                    _ => true,
+                },
+                _ => true,
+            })
+    }
+
+    /// Whether `old` in the baseline and `new` in the local crate are the same item. This holds
+    /// for the same item of another crate and for items with the same `DefPath` in crates of
+    /// the same name, so that items of the baseline match the local crate, and items of
+    /// different versions of a dependency match each other.
+    fn same_def(&self, old: DefId, new: DefId) -> bool {
+        let tcx = self.tcx;
+        old == new
+            || tcx.crate_name(old.krate) == tcx.crate_name(new.krate)
+                && tcx.def_path(old).data == tcx.def_path(new).data
+    }
+
+    fn same_ty(&self, old: Ty<'tcx>, new: Ty<'tcx>) -> bool {
+        self.same_generic_arg(old.into(), new.into())
+    }
+
+    fn same_substs(&self, old: SubstsRef<'tcx>, new: SubstsRef<'tcx>) -> bool {
+        old.len() == new.len()
+            && old.iter().zip(new).all(|(old, new)| self.same_generic_arg(old, new))
+    }
+
+    /// Compares the two arguments structurally, by walking both in lockstep and comparing the
+    /// types, regions and constants that they are made of.
+    fn same_generic_arg(&self, old: GenericArg<'tcx>, new: GenericArg<'tcx>) -> bool {
+        let mut old_walker = old.walk();
+        let mut new_walker = new.walk();
+        loop {
+            match (old_walker.next(), new_walker.next()) {
+                (None, None) => return true,
+                (Some(old), Some(new)) => {
+                    let same = match (old.unpack(), new.unpack()) {
+                        (GenericArgKind::Type(old), GenericArgKind::Type(new)) => {
+                            self.same_ty_kind(old, new)
+                        }
+                        (GenericArgKind::Lifetime(old), GenericArgKind::Lifetime(new)) => {
+                            self.same_region(old, new)
+                        }
+                        (GenericArgKind::Const(old), GenericArgKind::Const(new)) => {
+                            old.to_string() == new.to_string()
+                        }
+                        _ => false,
+                    };
+                    if !same {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    /// Compares the outermost type constructors of `old` and `new`, ignoring their
+    /// arguments, which `same_generic_arg` walks separately.
+    fn same_ty_kind(&self, old: Ty<'tcx>, new: Ty<'tcx>) -> bool {
+        match (old.kind(), new.kind()) {
+            (ty::Bool, ty::Bool)
+            | (ty::Char, ty::Char)
+            | (ty::Str, ty::Str)
+            | (ty::Never, ty::Never)
+            | (ty::Array(..), ty::Array(..))
+            | (ty::Slice(..), ty::Slice(..)) => true,
+            (ty::Int(old), ty::Int(new)) => old == new,
+            (ty::Uint(old), ty::Uint(new)) => old == new,
+            (ty::Float(old), ty::Float(new)) => old == new,
+            (ty::Tuple(old), ty::Tuple(new)) => old.len() == new.len(),
+            (ty::Ref(_, _, old), ty::Ref(_, _, new)) => old == new,
+            (ty::RawPtr(old), ty::RawPtr(new)) => old.mutbl == new.mutbl,
+            (ty::Param(old), ty::Param(new)) => old.index == new.index,
+            (ty::Adt(old, _), ty::Adt(new, _)) => self.same_def(old.did, new.did),
+            (ty::Foreign(old), ty::Foreign(new))
+            | (ty::FnDef(old, _), ty::FnDef(new, _))
+            | (ty::Closure(old, _), ty::Closure(new, _))
+            | (ty::Generator(old, ..), ty::Generator(new, ..))
+            | (ty::Opaque(old, _), ty::Opaque(new, _)) => self.same_def(*old, *new),
+            (ty::Projection(old), ty::Projection(new)) => {
+                self.same_def(old.item_def_id, new.item_def_id)
+            }
+            (ty::FnPtr(old), ty::FnPtr(new)) => {
+                old.abi() == new.abi()
+                    && old.unsafety() == new.unsafety()
+                    && old.c_variadic() == new.c_variadic()
+                    && old.inputs().skip_binder().len() == new.inputs().skip_binder().len()
+            }
+            (ty::Dynamic(old, _), ty::Dynamic(new, _)) => {
+                let same_principal = match (old.principal_def_id(), new.principal_def_id()) {
+                    (Some(old), Some(new)) => self.same_def(old, new),
+                    (None, None) => true,
+                    _ => false,
+                };
+                let old_auto_traits: Vec<_> = old.auto_traits().collect();
+                let new_auto_traits: Vec<_> = new.auto_traits().collect();
+                let old_projections: Vec<_> =
+                    old.projection_bounds().map(|p| p.skip_binder().item_def_id).collect();
+                let new_projections: Vec<_> =
+                    new.projection_bounds().map(|p| p.skip_binder().item_def_id).collect();
+                let same_defs = |old: &[DefId], new: &[DefId]| {
+                    old.len() == new.len()
+                        && old.iter().zip(new).all(|(&old, &new)| self.same_def(old, new))
+                };
+                same_principal
+                    && same_defs(&old_auto_traits, &new_auto_traits)
+                    && same_defs(&old_projections, &new_projections)
+            }
+            _ => false,
+        }
+    }
+
+    fn same_region(&self, old: Region<'tcx>, new: Region<'tcx>) -> bool {
+        match (old, new) {
+            (ty::ReEarlyBound(old), ty::ReEarlyBound(new)) => old.index == new.index,
+            (ty::ReLateBound(old, _), ty::ReLateBound(new, _)) => old == new,
+            _ => old == new,
+        }
+    }
+
+    fn same_predicate(&self, old: ty::Predicate<'tcx>, new: ty::Predicate<'tcx>) -> bool {
+        match (old.skip_binders(), new.skip_binders()) {
+            (PredicateAtom::Trait(old, old_const), PredicateAtom::Trait(new, new_const)) => {
+                old_const == new_const
+                    && self.same_def(old.def_id(), new.def_id())
+                    && self.same_substs(old.trait_ref.substs, new.trait_ref.substs)
+            }
+            (PredicateAtom::Projection(old), PredicateAtom::Projection(new)) => {
+                self.same_def(old.projection_ty.item_def_id, new.projection_ty.item_def_id)
+                    && self.same_substs(old.projection_ty.substs, new.projection_ty.substs)
+                    && self.same_ty(old.ty, new.ty)
+            }
+            (
+                PredicateAtom::TypeOutlives(ty::OutlivesPredicate(old_ty, old_region)),
+                PredicateAtom::TypeOutlives(ty::OutlivesPredicate(new_ty, new_region)),
+            ) => self.same_ty(old_ty, new_ty) && self.same_region(old_region, new_region),
+            (
+                PredicateAtom::RegionOutlives(ty::OutlivesPredicate(old_a, old_b)),
+                PredicateAtom::RegionOutlives(ty::OutlivesPredicate(new_a, new_b)),
+            ) => self.same_region(old_a, new_a) && self.same_region(old_b, new_b),
+            _ => old.to_string() == new.to_string(),
+        }
+    }
+}
@@ -15,7 +15,7 @@
 use std::cell::Cell;
 use std::fmt::{self, Debug};
 
-use super::{Field, SourceInfo};
+use super::{BasicBlock, Field, Local, Location, SourceInfo};
 
 #[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable)]
 pub enum UnsafetyViolationKind {
@@ -409,6 +409,45 @@ pub struct CoverageInfo {
     pub num_expressions: u32,
 }
 
+/// The locals of a function that hold a known constant at each location of its optimized MIR, as
+/// computed by the `ConstantLocals` dataflow analysis. Retrieved via the `constant_locals` query.
+#[derive(Debug, HashStable)]
+pub struct ConstantLocalsResult<'tcx> {
+    /// For each statement and terminator, the locals known to hold a constant right before its
+    /// primary effect, sorted by local, or `None` if it is unreachable.
+    pub states: IndexVec<BasicBlock, Vec<Option<Vec<(Local, &'tcx ty::Const<'tcx>)>>>>,
+}
+
+impl<'tcx> ConstantLocalsResult<'tcx> {
+    /// Returns the constant that `local` holds right before the statement or terminator at
+    /// `location`, if it was last assigned that same constant on every path leading there.
+    pub fn constant_at(&self, location: Location, local: Local) -> Option<&'tcx ty::Const<'tcx>> {
+        let locals = self.states.get(location.block)?.get(location.statement_index)?.as_ref()?;
+        let index = locals.binary_search_by_key(&local, |&(local, _)| local).ok()?;
+        Some(locals[index].1)
+    }
+}
+
+/// The functions that define the taint analysis run by the `tainted_sink_calls` query.
+///
+/// The results of calls to `sources` are tainted, and so are the results of calls with a tainted
+/// argument, unless the callee is one of the `sanitizers`. Only direct calls are recognized.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, HashStable)]
+pub struct TaintFns<'tcx> {
+    pub sources: &'tcx [DefId],
+    pub sinks: &'tcx [DefId],
+    pub sanitizers: &'tcx [DefId],
+}
+
+/// A call to a sink that may receive tainted data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, HashStable)]
+pub struct TaintedSinkCall {
+    /// The location of the `Call` terminator.
+    pub location: Location,
+    /// The index of the (first) tainted argument.
+    pub arg: usize,
+}
+
 /// Shims which make dealing with `WithOptConstParam` easier.
 ///
 /// For more information on why this is needed, consider looking
@@ -346,6 +346,25 @@ fn describe_as_module(def_id: LocalDefId, tcx: TyCtxt<'_>) -> String {
             cache_on_disk_if { key.is_local() }
         }
 
+        /// Runs the `ConstantLocals` dataflow analysis over the optimized MIR of a function or
+        /// closure, so that lints can look up which locals hold a known constant at a location.
+        query constant_locals(key: LocalDefId) -> mir::ConstantLocalsResult<'tcx> {
+            desc { |tcx| "finding constant locals in `{}`", tcx.def_path_str(key.to_def_id()) }
+            storage(ArenaCacheSelector<'tcx>)
+        }
+
+        /// Runs the `MaybeTaintedLocals` dataflow analysis over the optimized MIR of a function or
+        /// closure, with the given sources, sinks and sanitizers, and returns every call to a sink
+        /// that may receive tainted data.
+        query tainted_sink_calls(
+            key: (LocalDefId, mir::TaintFns<'tcx>)
+        ) -> &'tcx [mir::TaintedSinkCall] {
+            desc {
+                |tcx| "finding tainted sink calls in `{}`",
+                tcx.def_path_str(key.0.to_def_id())
+            }
+        }
+
         /// The `DefId` is the `DefId` of the containing MIR body. Promoteds do not have their own
         /// `DefId`. This function returns all promoteds in the specified body. The body references
         /// promoteds by the `DefId` and the `mir::Promoted` index. This is necessary, because
@@ -1327,6 +1346,21 @@ fn describe_as_module(def_id: LocalDefId, tcx: TyCtxt<'_>) -> String {
         query missing_lang_items(_: CrateNum) -> &'tcx [LangItem] {
             desc { "calculating the missing lang items in a crate" }
         }
+        /// Returns the items of other crates that a crate uses, or `None` for upstream
+        /// crates that were not built with `-Z unused-pub-items` and so did not record them.
+        query used_extern_items(_: CrateNum) -> Option<&'tcx [DefId]> {
+            desc { "calculating the items of other crates used by a crate" }
+        }
+        /// Returns the public items of a crate that `-Z unused-pub-items` reports if no
+        /// crate uses them.
+        query unused_pub_item_candidates(_: CrateNum) -> &'tcx [DefId] {
+            desc { "calculating the public items of a crate checked for use by other crates" }
+        }
+        /// Returns the upstream crates that an upstream crate depends on, directly or
+        /// transitively, in postorder and including the crate itself.
+        query crate_dependencies(_: CrateNum) -> &'tcx [CrateNum] {
+            desc { "calculating the dependencies of a crate" }
+        }
         query visible_parent_map(_: CrateNum)
             -> DefIdMap<DefId> {
             storage(ArenaCacheSelector<'tcx>)
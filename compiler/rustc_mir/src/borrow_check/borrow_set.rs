@@ -156,6 +156,10 @@ pub fn build(
         self.location_map.len()
     }
 
+    crate fn is_empty(&self) -> bool {
+        self.location_map.is_empty()
+    }
+
     crate fn indices(&self) -> impl Iterator<Item = BorrowIndex> {
         BorrowIndex::from_usize(0)..BorrowIndex::from_usize(self.len())
     }
@@ -37,6 +37,9 @@ pub(in crate::borrow_check) enum BorrowExplanation {
         span: Span,
         region_name: RegionName,
         opt_place_desc: Option<String>,
+        /// Notes describing every step of the constraint path, only
+        /// populated under `-Z borrowck-explain`.
+        constraint_path: Vec<(Span, String)>,
     },
     Unexplained,
 }
@@ -192,6 +195,7 @@ pub(in crate::borrow_check) fn add_explanation_to_diagnostic<'tcx>(
                 ref region_name,
                 ref opt_place_desc,
                 from_closure: _,
+                ref constraint_path,
             } => {
                 region_name.highlight_region_name(err);
 
@@ -218,6 +222,14 @@ pub(in crate::borrow_check) fn add_explanation_to_diagnostic<'tcx>(
                 };
 
                 self.add_lifetime_bound_suggestion_to_diagnostic(err, &category, span, region_name);
+
+                for (span, note) in constraint_path {
+                    if span.is_dummy() {
+                        err.note(note);
+                    } else {
+                        err.span_note(*span, note);
+                    }
+                }
             }
             _ => {}
         }
@@ -347,12 +359,14 @@ pub(in crate::borrow_check) fn explain_why_borrow_contains_point(
                         self.free_region_constraint_info(borrow_region_vid, region);
                     if let Some(region_name) = region_name {
                         let opt_place_desc = self.describe_place(borrow.borrowed_place.as_ref());
+                        let constraint_path = self.constraint_path_notes(borrow_region_vid, region);
                         BorrowExplanation::MustBeValidFor {
                             category,
                             from_closure,
                             span,
                             region_name,
                             opt_place_desc,
+                            constraint_path,
                         }
                     } else {
                         debug!(
@@ -1,6 +1,8 @@
 //! Error reporting machinery for lifetime errors.
 
-use rustc_errors::{Applicability, DiagnosticBuilder};
+use std::io;
+
+use rustc_errors::{pluralize, Applicability, DiagnosticBuilder};
 use rustc_infer::infer::{
     error_reporting::nice_region_error::NiceRegionError,
     error_reporting::unexpected_hidden_region_diagnostic, NLLRegionVariableOrigin,
@@ -9,9 +11,10 @@
 use rustc_middle::ty::subst::Subst;
 use rustc_middle::ty::{self, RegionVid, Ty};
 use rustc_span::symbol::{kw, sym};
-use rustc_span::Span;
+use rustc_span::{Span, DUMMY_SP};
 
 use crate::util::borrowck_errors;
+use crate::util::pretty;
 
 use crate::borrow_check::{
     nll::ConstraintDescription,
@@ -284,7 +287,8 @@ pub(in crate::borrow_check) fn report_region_error(
         // Check if we can use one of the "nice region errors".
         if let (Some(f), Some(o)) = (self.to_error_region(fr), self.to_error_region(outlived_fr)) {
             let nice = NiceRegionError::new_from_span(self.infcx, span, o, f);
-            if let Some(diag) = nice.try_report_from_nll() {
+            if let Some(mut diag) = nice.try_report_from_nll() {
+                self.add_constraint_path_notes(fr, outlived_fr, &mut diag);
                 diag.buffer(&mut self.errors_buffer);
                 return;
             }
@@ -309,7 +313,7 @@ pub(in crate::borrow_check) fn report_region_error(
             span,
         };
 
-        let diag = match (category, fr_is_local, outlived_fr_is_local) {
+        let mut diag = match (category, fr_is_local, outlived_fr_is_local) {
             (ConstraintCategory::Return(kind), true, false) if self.is_closure_fn_mut(fr) => {
                 self.report_fnmut_error(&errci, kind)
             }
@@ -332,9 +336,101 @@ pub(in crate::borrow_check) fn report_region_error(
             }
         };
 
+        self.add_constraint_path_notes(fr, outlived_fr, &mut diag);
         diag.buffer(&mut self.errors_buffer);
     }
 
+    /// Under `-Z borrowck-explain`, adds a note for every constraint on
+    /// the path that forces `fr` to outlive `outlived_fr`, rather than
+    /// only the single "best blame" constraint.
+    pub(in crate::borrow_check) fn add_constraint_path_notes(
+        &self,
+        fr: RegionVid,
+        outlived_fr: RegionVid,
+        diag: &mut DiagnosticBuilder<'_>,
+    ) {
+        for (span, note) in self.constraint_path_notes(fr, outlived_fr) {
+            if span.is_dummy() {
+                diag.note(&note);
+            } else {
+                diag.span_note(span, &note);
+            }
+        }
+    }
+
+    /// Describes each step of the constraint path that forces `fr` to
+    /// outlive `outlived_fr`. Returns nothing unless `-Z borrowck-explain`
+    /// is enabled; with `-Z dump-mir-graphviz` the path is also written
+    /// out as a `.dot` file.
+    pub(in crate::borrow_check) fn constraint_path_notes(
+        &self,
+        fr: RegionVid,
+        outlived_fr: RegionVid,
+    ) -> Vec<(Span, String)> {
+        let tcx = self.infcx.tcx;
+        if !tcx.sess.opts.debugging_opts.borrowck_explain {
+            return vec![];
+        }
+
+        let path = self.regioncx.blame_constraint_path(&self.body, fr, |r| {
+            self.regioncx.provides_universal_region(r, fr, outlived_fr)
+        });
+        debug!("constraint_path_notes: path={:#?}", path);
+
+        if tcx.sess.opts.debugging_opts.dump_mir_graphviz {
+            let disambiguator = format!("{}_{}", fr.index(), outlived_fr.index());
+            let _: io::Result<()> = try {
+                let mut file = pretty::create_dump_file(
+                    tcx,
+                    "borrowck-explain.dot",
+                    None,
+                    "nll",
+                    &disambiguator,
+                    self.body.source,
+                )?;
+                self.regioncx.dump_graphviz_constraint_path(&path, &mut file)?;
+            };
+        }
+
+        let describe_region = |r: RegionVid| {
+            if self.regioncx.universal_regions().is_universal_region(r) {
+                if let Some(name) = self.give_region_a_name(r) {
+                    return format!("`{}`", name);
+                }
+            }
+            format!("`{:?}`", r)
+        };
+
+        let mut notes = Vec::with_capacity(path.len() + 1);
+        notes.push((
+            DUMMY_SP,
+            format!(
+                "{} must outlive {} because of the following {} constraint{}",
+                describe_region(fr),
+                describe_region(outlived_fr),
+                path.len(),
+                pluralize!(path.len()),
+            ),
+        ));
+        for (i, (constraint, category, span)) in path.iter().enumerate() {
+            let reason = match category.description().trim_end() {
+                "" => "an internal constraint",
+                description => description,
+            };
+            notes.push((
+                *span,
+                format!(
+                    "#{}: {} must outlive {} due to {}",
+                    i + 1,
+                    describe_region(constraint.sup),
+                    describe_region(constraint.sub),
+                    reason,
+                ),
+            ));
+        }
+        notes
+    }
+
     /// Report a specialized error when `FnMut` closures return a reference to a captured variable.
     /// This function expects `fr` to be local and `outlived_fr` to not be local.
     ///
@@ -9,7 +9,7 @@
     BasicBlock, Body, ClosureOutlivesSubject, ClosureRegionRequirements, LocalKind, Location,
     Promoted,
 };
-use rustc_middle::ty::{self, RegionKind, RegionVid};
+use rustc_middle::ty::{self, RegionKind, RegionVid, TyCtxt};
 use rustc_span::symbol::sym;
 use std::env;
 use std::fmt::Debug;
@@ -152,6 +152,48 @@ fn populate_polonius_move_facts(
         .extend(move_data.moves.iter().map(|mo| (mo.path, location_table.mid_index(mo.source))));
 }
 
+/// Returns whether Polonius facts need to be gathered for this body.
+///
+/// A body without any loans can never have a loan error, and if its only universal regions are
+/// `'static` and the function body region, Polonius cannot find a subset error that the regular
+/// NLL universal region check would not also find. Building `AllFacts` for those bodies is pure
+/// overhead, so under `-Z polonius` they are checked by NLL alone -- unless the facts were
+/// explicitly requested with `-Z nll-facts`.
+fn polonius_facts_needed(
+    tcx: TyCtxt<'_>,
+    universal_regions: &UniversalRegions<'_>,
+    borrow_set: &BorrowSet<'_>,
+) -> bool {
+    if tcx.sess.opts.debugging_opts.nll_facts {
+        return true;
+    }
+
+    let trivial = borrow_set.is_empty()
+        && universal_regions
+            .universal_regions()
+            .all(|r| r == universal_regions.fr_static || r == universal_regions.fr_fn_body);
+    debug!("polonius_facts_needed: trivial={:?}", trivial);
+    !trivial
+}
+
+/// Selects the Polonius algorithm used by `-Z polonius`: `-Z polonius-algorithm` if given, then
+/// the `POLONIUS_ALGORITHM` environment variable, and `Hybrid` otherwise. The hybrid algorithm
+/// runs the cheap location-insensitive analysis first, and only falls back to the full
+/// location-sensitive analysis for bodies where that pre-pass found potential errors.
+fn polonius_algorithm(tcx: TyCtxt<'_>) -> Algorithm {
+    let name = tcx
+        .sess
+        .opts
+        .debugging_opts
+        .polonius_algorithm
+        .clone()
+        .or_else(|| env::var("POLONIUS_ALGORITHM").ok())
+        .unwrap_or_else(|| String::from("Hybrid"));
+    Algorithm::from_str(&name).unwrap_or_else(|valid| {
+        tcx.sess.fatal(&format!("unknown polonius algorithm `{}`: {}", name, valid))
+    })
+}
+
 /// Computes the (non-lexical) regions from the input MIR.
 ///
 /// This may result in errors being reported.
@@ -167,7 +209,9 @@ pub(in crate::borrow_check) fn compute_regions<'cx, 'tcx>(
     borrow_set: &BorrowSet<'tcx>,
     upvars: &[Upvar],
 ) -> NllOutput<'tcx> {
-    let mut all_facts = AllFacts::enabled(infcx.tcx).then_some(AllFacts::default());
+    let mut all_facts = (AllFacts::enabled(infcx.tcx)
+        && polonius_facts_needed(infcx.tcx, &universal_regions, borrow_set))
+    .then_some(AllFacts::default());
 
     let universal_regions = Rc::new(universal_regions);
 
@@ -281,9 +325,7 @@ pub(in crate::borrow_check) fn compute_regions<'cx, 'tcx>(
         }
 
         if infcx.tcx.sess.opts.debugging_opts.polonius {
-            let algorithm =
-                env::var("POLONIUS_ALGORITHM").unwrap_or_else(|_| String::from("Naive"));
-            let algorithm = Algorithm::from_str(&algorithm).unwrap();
+            let algorithm = polonius_algorithm(infcx.tcx);
             debug!("compute_regions: using polonius algorithm {:?}", algorithm);
             let _prof_timer = infcx.tcx.prof.generic_activity("polonius_analysis");
             Some(Rc::new(Output::compute(&all_facts, algorithm, false)))
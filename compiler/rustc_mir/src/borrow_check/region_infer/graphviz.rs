@@ -27,6 +27,15 @@ impl<'tcx> RegionInferenceContext<'tcx> {
 
         dot::render(&SccConstraints { regioncx: self, nodes_per_scc }, &mut w)
     }
+
+    /// Write out a single constraint path, as found by `blame_constraint_path`.
+    crate fn dump_graphviz_constraint_path(
+        &self,
+        path: &[(OutlivesConstraint, ConstraintCategory, Span)],
+        mut w: &mut dyn Write,
+    ) -> io::Result<()> {
+        dot::render(&ConstraintPath { path }, &mut w)
+    }
 }
 
 struct RawConstraints<'a, 'tcx> {
@@ -138,3 +147,55 @@ fn target(&'this self, edge: &(ConstraintSccIndex, ConstraintSccIndex)) -> Const
         edge.1
     }
 }
+
+struct ConstraintPath<'a> {
+    path: &'a [(OutlivesConstraint, ConstraintCategory, Span)],
+}
+
+impl<'a, 'this> dot::Labeller<'this> for ConstraintPath<'a> {
+    type Node = RegionVid;
+    type Edge = usize;
+
+    fn graph_id(&'this self) -> dot::Id<'this> {
+        dot::Id::new("ConstraintPath").unwrap()
+    }
+    fn node_id(&'this self, n: &RegionVid) -> dot::Id<'this> {
+        dot::Id::new(format!("r{}", n.index())).unwrap()
+    }
+    fn node_shape(&'this self, _node: &RegionVid) -> Option<dot::LabelText<'this>> {
+        Some(dot::LabelText::LabelStr(Cow::Borrowed("box")))
+    }
+    fn node_label(&'this self, n: &RegionVid) -> dot::LabelText<'this> {
+        dot::LabelText::LabelStr(format!("{:?}", n).into())
+    }
+    fn edge_label(&'this self, e: &usize) -> dot::LabelText<'this> {
+        let (_, category, span) = &self.path[*e];
+        dot::LabelText::LabelStr(format!("#{}: {:?} at {:?}", e + 1, category, span).into())
+    }
+}
+
+impl<'a, 'this> dot::GraphWalk<'this> for ConstraintPath<'a> {
+    type Node = RegionVid;
+    type Edge = usize;
+
+    fn nodes(&'this self) -> dot::Nodes<'this, RegionVid> {
+        let mut vids: Vec<RegionVid> = self.path.iter().map(|(c, ..)| c.sup).collect();
+        vids.extend(self.path.last().map(|(c, ..)| c.sub));
+        vids.dedup();
+        vids.into()
+    }
+    fn edges(&'this self) -> dot::Edges<'this, usize> {
+        let edges: Vec<usize> = (0..self.path.len()).collect();
+        edges.into()
+    }
+
+    // Render `a: b` as `a -> b`, as for the raw constraint graph.
+
+    fn source(&'this self, edge: &usize) -> RegionVid {
+        self.path[*edge].0.sup
+    }
+
+    fn target(&'this self, edge: &usize) -> RegionVid {
+        self.path[*edge].0.sub
+    }
+}
@@ -567,12 +567,15 @@ pub(super) fn solve(
         // In Polonius mode, the errors about missing universal region relations are in the output
         // and need to be emitted or propagated. Otherwise, we need to check whether the
         // constraints were too strong, and if so, emit or propagate those errors.
-        if infcx.tcx.sess.opts.debugging_opts.polonius {
+        //
+        // Polonius may also have been skipped for bodies where it cannot give a different
+        // answer (see `nll::polonius_facts_needed`): those take the regular NLL path.
+        if let Some(polonius_output) = polonius_output {
             self.check_polonius_subset_errors(
                 body,
                 outlives_requirements.as_mut(),
                 &mut errors_buffer,
-                polonius_output.expect("Polonius output is unavailable despite `-Z polonius`"),
+                polonius_output,
             );
         } else {
             self.check_universal_regions(body, outlives_requirements.as_mut(), &mut errors_buffer);
@@ -1939,6 +1942,41 @@ fn check_member_constraints(
         self.universal_regions.as_ref()
     }
 
+    /// Returns the category and span to report for a single constraint,
+    /// along with whether it was propagated out of a closure.
+    fn categorize_constraint(
+        &self,
+        body: &Body<'tcx>,
+        constraint: &OutlivesConstraint,
+    ) -> (ConstraintCategory, bool, Span) {
+        if constraint.category == ConstraintCategory::ClosureBounds {
+            self.retrieve_closure_constraint_info(body, constraint)
+        } else {
+            (constraint.category, false, constraint.locations.span(body))
+        }
+    }
+
+    /// Finds the full constraint path that forces some region `R`
+    /// meeting `target_test` to be outlived by `from_region`. Unlike
+    /// `best_blame_constraint`, which picks a single constraint out of
+    /// that path, this returns every step, each paired with its
+    /// category and span. Used by `-Z borrowck-explain`.
+    crate fn blame_constraint_path(
+        &self,
+        body: &Body<'tcx>,
+        from_region: RegionVid,
+        target_test: impl Fn(RegionVid) -> bool,
+    ) -> Vec<(OutlivesConstraint, ConstraintCategory, Span)> {
+        let (path, _) =
+            self.find_constraint_paths_between_regions(from_region, target_test).unwrap();
+        path.into_iter()
+            .map(|constraint| {
+                let (category, _, span) = self.categorize_constraint(body, &constraint);
+                (constraint, category, span)
+            })
+            .collect()
+    }
+
     /// Tries to find the best constraint to blame for the fact that
     /// `R: from_region`, where `R` is some region that meets
     /// `target_test`. This works by following the constraint graph,
@@ -1973,16 +2011,8 @@ fn check_member_constraints(
         );
 
         // Classify each of the constraints along the path.
-        let mut categorized_path: Vec<(ConstraintCategory, bool, Span)> = path
-            .iter()
-            .map(|constraint| {
-                if constraint.category == ConstraintCategory::ClosureBounds {
-                    self.retrieve_closure_constraint_info(body, &constraint)
-                } else {
-                    (constraint.category, false, constraint.locations.span(body))
-                }
-            })
-            .collect();
+        let mut categorized_path: Vec<(ConstraintCategory, bool, Span)> =
+            path.iter().map(|constraint| self.categorize_constraint(body, constraint)).collect();
         debug!("best_blame_constraint: categorized_path={:#?}", categorized_path);
 
         // To find the best span to cite, we first try to look for the
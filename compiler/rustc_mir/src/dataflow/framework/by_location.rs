@@ -0,0 +1,70 @@
+//! Dataflow results stored for random access by `Location`.
+
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::{self, BasicBlock, Location};
+
+use super::{Analysis, Results, ResultsVisitor};
+
+/// The dataflow state immediately before the primary effect of every reachable statement and
+/// terminator in a `mir::Body`.
+///
+/// A `ResultsCursor` is cheaper when locations are visited in order, but consumers outside of the
+/// borrow checker and MIR transformations (e.g. lints) often want to look up the state at
+/// arbitrary locations, in whatever order they encounter them. This stores a copy of the state for
+/// each location, so it should only be used for small domains or small bodies.
+pub struct StatesByLocation<T> {
+    states: IndexVec<BasicBlock, Vec<Option<T>>>,
+}
+
+impl<T> StatesByLocation<T> {
+    crate fn new<'mir, 'tcx, A>(body: &'mir mir::Body<'tcx>, results: &Results<'tcx, A>) -> Self
+    where
+        A: Analysis<'tcx, Domain = T>,
+        T: Clone,
+    {
+        let states = body
+            .basic_blocks()
+            .iter()
+            .map(|block_data| (0..=block_data.statements.len()).map(|_| None).collect())
+            .collect();
+
+        let mut recorder = StatesByLocation { states };
+        results.visit_reachable_with(body, &mut recorder);
+        recorder
+    }
+
+    /// Returns the state before the primary effect of the statement or terminator at `location`,
+    /// or `None` if `location` is unreachable.
+    pub fn get(&self, location: Location) -> Option<&T> {
+        self.states.get(location.block)?.get(location.statement_index)?.as_ref()
+    }
+
+    fn record(&mut self, state: &T, location: Location)
+    where
+        T: Clone,
+    {
+        self.states[location.block][location.statement_index] = Some(state.clone());
+    }
+}
+
+impl<'mir, 'tcx, T: Clone> ResultsVisitor<'mir, 'tcx> for StatesByLocation<T> {
+    type FlowState = T;
+
+    fn visit_statement_before_primary_effect(
+        &mut self,
+        state: &Self::FlowState,
+        _statement: &'mir mir::Statement<'tcx>,
+        location: Location,
+    ) {
+        self.record(state, location);
+    }
+
+    fn visit_terminator_before_primary_effect(
+        &mut self,
+        state: &Self::FlowState,
+        _terminator: &'mir mir::Terminator<'tcx>,
+        location: Location,
+    ) {
+        self.record(state, location);
+    }
+}
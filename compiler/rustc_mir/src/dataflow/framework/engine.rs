@@ -18,7 +18,7 @@
 use super::graphviz;
 use super::{
     visit_results, Analysis, Direction, GenKill, GenKillAnalysis, GenKillSet, JoinSemiLattice,
-    ResultsCursor, ResultsVisitor,
+    ResultsCursor, ResultsVisitor, StatesByLocation,
 };
 use crate::util::pretty::{create_dump_file, dump_enabled};
 
@@ -54,6 +54,12 @@ pub fn visit_with(
         visit_results(body, blocks, self, vis)
     }
 
+    /// Records the state before the primary effect of every reachable location in `body`, for
+    /// random access by `Location`. See `StatesByLocation` for when to prefer this over a cursor.
+    pub fn states_by_location(&self, body: &'mir mir::Body<'tcx>) -> StatesByLocation<A::Domain> {
+        StatesByLocation::new(body, self)
+    }
+
     pub fn visit_reachable_with(
         &self,
         body: &'mir mir::Body<'tcx>,
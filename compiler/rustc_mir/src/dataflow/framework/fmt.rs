@@ -2,7 +2,7 @@
 //! analysis.
 
 use rustc_index::bit_set::{BitSet, HybridBitSet};
-use rustc_index::vec::Idx;
+use rustc_index::vec::{Idx, IndexVec};
 use std::fmt;
 
 /// An extension to `fmt::Debug` for data that can be better printed with some auxiliary data `C`.
@@ -133,6 +133,41 @@ fn fmt_diff_with(&self, old: &Self, ctxt: &C, f: &mut fmt::Formatter<'_>) -> fmt
     }
 }
 
+impl<I, T, C> DebugWithContext<C> for IndexVec<I, T>
+where
+    I: Idx,
+    T: DebugWithContext<C>,
+{
+    fn fmt_with(&self, ctxt: &C, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter_enumerated().map(|(i, v)| (i, DebugWithAdapter { this: v, ctxt })))
+            .finish()
+    }
+
+    fn fmt_diff_with(&self, old: &Self, ctxt: &C, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        assert_eq!(self.len(), old.len());
+
+        let mut first = true;
+        for ((i, new_elem), old_elem) in self.iter_enumerated().zip(old.iter()) {
+            if new_elem == old_elem {
+                continue;
+            }
+
+            if !first {
+                write!(f, "{}", if f.alternate() { "\n" } else { "\t" })?;
+            }
+            first = false;
+
+            write!(f, "{:?}: \u{001f}+", i)?;
+            new_elem.fmt_with(ctxt, f)?;
+            write!(f, " \u{001f}-")?;
+            old_elem.fmt_with(ctxt, f)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<T, C> DebugWithContext<C> for &'_ T
 where
     T: DebugWithContext<C>,
@@ -158,6 +193,8 @@ fn fmt_with(&self, ctxt: &C, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+impl<T, C> DebugWithContext<C> for crate::dataflow::lattice::FlatSet<T> where T: Eq + fmt::Debug {}
+
 impl<T, C> DebugWithContext<C> for crate::dataflow::lattice::Dual<T>
 where
     T: DebugWithContext<C>,
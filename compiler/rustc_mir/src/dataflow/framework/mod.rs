@@ -38,6 +38,7 @@
 use rustc_middle::mir::{self, BasicBlock, Location};
 use rustc_middle::ty::TyCtxt;
 
+mod by_location;
 mod cursor;
 mod direction;
 mod engine;
@@ -46,6 +47,7 @@
 pub mod lattice;
 mod visitor;
 
+pub use self::by_location::StatesByLocation;
 pub use self::cursor::{ResultsCursor, ResultsRefCursor};
 pub use self::direction::{Backward, Direction, Forward};
 pub use self::engine::{Engine, Results};
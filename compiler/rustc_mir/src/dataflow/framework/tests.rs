@@ -318,3 +318,39 @@ fn forward_cursor() {
     let analysis = MockAnalysis { body, dir: PhantomData::<Forward> };
     test_cursor(analysis)
 }
+
+fn test_states_by_location<D: Direction>(analysis: MockAnalysis<'tcx, D>) {
+    let body = analysis.body;
+
+    let results = Results { entry_sets: analysis.mock_entry_sets(), analysis };
+    let states = results.states_by_location(body);
+
+    // Only `START_BLOCK` is reachable in `mock_body`.
+    for (bb, block_data) in body.basic_blocks().iter_enumerated() {
+        for statement_index in 0..=block_data.statements.len() {
+            let loc = Location { block: bb, statement_index };
+            if bb == mir::START_BLOCK {
+                let expected = results.analysis.expected_state_at_target(SeekTarget::Before(loc));
+                assert_eq!(states.get(loc), Some(&expected));
+            } else {
+                assert_eq!(states.get(loc), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn backward_states_by_location() {
+    let body = mock_body();
+    let body = &body;
+    let analysis = MockAnalysis { body, dir: PhantomData::<Backward> };
+    test_states_by_location(analysis)
+}
+
+#[test]
+fn forward_states_by_location() {
+    let body = mock_body();
+    let body = &body;
+    let analysis = MockAnalysis { body, dir: PhantomData::<Forward> };
+    test_states_by_location(analysis)
+}
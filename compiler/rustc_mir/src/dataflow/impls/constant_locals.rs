@@ -0,0 +1,208 @@
+//! A simple constant propagation analysis over whole locals.
+//!
+//! Unlike the `ConstProp` MIR pass, this does not evaluate anything: it only tracks which locals
+//! were last assigned a constant operand on every path. That is enough for analyses outside of MIR
+//! transformations (e.g. lints) that want to know whether some argument is a known constant.
+
+use rustc_hir::def_id::LocalDefId;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::{
+    self, BasicBlock, ConstantLocalsResult, Local, Location, Operand, Rvalue, StatementKind,
+};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::{self, TyCtxt};
+
+use crate::dataflow::lattice::FlatSet;
+use crate::dataflow::{Analysis, AnalysisDomain};
+
+/// The value of a local in `ConstantLocals`.
+pub type ConstantValue<'tcx> = FlatSet<&'tcx ty::Const<'tcx>>;
+
+/// A dataflow analysis that tracks which locals definitely hold a known constant.
+///
+/// At a given point, a local is:
+///
+/// - `FlatSet::Elem(c)` if, on every path to that point, it was last assigned the constant `c`,
+///   either directly or by copying or moving another local known to hold `c`.
+/// - `FlatSet::Top` if it may hold anything else.
+/// - `FlatSet::Bottom` if it has not been assigned on any path (or its storage is dead).
+///
+/// Only assignments to a local as a whole are tracked; a write to any projection of a local
+/// makes the whole local `Top`. Locals whose address is taken anywhere in the body are always
+/// `Top`, since they may be written through a pointer.
+pub struct ConstantLocals {
+    borrowed: BitSet<Local>,
+}
+
+impl ConstantLocals {
+    pub fn new(body: &mir::Body<'_>) -> Self {
+        let mut borrowed = BorrowedLocals(BitSet::new_empty(body.local_decls.len()));
+        borrowed.visit_body(body);
+        ConstantLocals { borrowed: borrowed.0 }
+    }
+
+    fn assign(
+        &self,
+        state: &mut IndexVec<Local, ConstantValue<'tcx>>,
+        local: Local,
+        value: ConstantValue<'tcx>,
+    ) {
+        state[local] = if self.borrowed.contains(local) { FlatSet::Top } else { value };
+    }
+
+    fn eval_operand(
+        &self,
+        state: &IndexVec<Local, ConstantValue<'tcx>>,
+        operand: &Operand<'tcx>,
+    ) -> ConstantValue<'tcx> {
+        match operand {
+            Operand::Constant(constant) => FlatSet::Elem(constant.literal),
+            Operand::Copy(place) | Operand::Move(place) => match place.as_local() {
+                Some(local) => state[local].clone(),
+                None => FlatSet::Top,
+            },
+        }
+    }
+}
+
+impl AnalysisDomain<'tcx> for ConstantLocals {
+    type Domain = IndexVec<Local, ConstantValue<'tcx>>;
+
+    const NAME: &'static str = "constant_locals";
+
+    fn bottom_value(&self, body: &mir::Body<'tcx>) -> Self::Domain {
+        // bottom = not yet assigned
+        IndexVec::from_elem(FlatSet::Bottom, &body.local_decls)
+    }
+
+    fn initialize_start_block(&self, body: &mir::Body<'tcx>, state: &mut Self::Domain) {
+        // Function arguments, and anything that may be written through a pointer, are unknown.
+        for arg in body.args_iter() {
+            state[arg] = FlatSet::Top;
+        }
+        for local in self.borrowed.iter() {
+            state[local] = FlatSet::Top;
+        }
+    }
+}
+
+impl Analysis<'tcx> for ConstantLocals {
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    ) {
+        match statement.kind {
+            StatementKind::Assign(box (place, ref rvalue)) if place.as_local().is_some() => {
+                let value = match rvalue {
+                    Rvalue::Use(operand) => self.eval_operand(state, operand),
+                    _ => FlatSet::Top,
+                };
+                self.assign(state, place.local, value);
+            }
+
+            StatementKind::StorageDead(local) => self.assign(state, local, FlatSet::Bottom),
+
+            _ => MarkMutatedLocals { state }.visit_statement(statement, location),
+        }
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    ) {
+        match terminator.kind {
+            mir::TerminatorKind::DropAndReplace { place, ref value, .. }
+                if place.as_local().is_some() =>
+            {
+                let value = self.eval_operand(state, value);
+                self.assign(state, place.local, value);
+            }
+
+            _ => MarkMutatedLocals { state }.visit_terminator(terminator, location),
+        }
+    }
+
+    fn apply_call_return_effect(
+        &self,
+        state: &mut Self::Domain,
+        _block: BasicBlock,
+        _func: &mir::Operand<'tcx>,
+        _args: &[mir::Operand<'tcx>],
+        return_place: mir::Place<'tcx>,
+    ) {
+        state[return_place.local] = FlatSet::Top;
+    }
+
+    fn apply_yield_resume_effect(
+        &self,
+        state: &mut Self::Domain,
+        _resume_block: BasicBlock,
+        resume_place: mir::Place<'tcx>,
+    ) {
+        state[resume_place.local] = FlatSet::Top;
+    }
+}
+
+pub(super) fn provide(providers: &mut Providers) {
+    providers.constant_locals = constant_locals;
+}
+
+fn constant_locals(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> ConstantLocalsResult<'tcx> {
+    let body = tcx.optimized_mir(def_id.to_def_id());
+    let results = ConstantLocals::new(body).into_engine(tcx, body).iterate_to_fixpoint();
+    let states_by_location = results.states_by_location(body);
+
+    let states = body
+        .basic_blocks()
+        .iter_enumerated()
+        .map(|(block, block_data)| {
+            (0..=block_data.statements.len())
+                .map(|statement_index| {
+                    let state = states_by_location.get(Location { block, statement_index })?;
+                    let constants =
+                        state.iter_enumerated().filter_map(|(local, value)| match value {
+                            FlatSet::Elem(constant) => Some((local, *constant)),
+                            FlatSet::Bottom | FlatSet::Top => None,
+                        });
+                    Some(constants.collect())
+                })
+                .collect()
+        })
+        .collect();
+    ConstantLocalsResult { states }
+}
+
+/// Sets every local that is mutated by a statement or terminator to `Top`.
+struct MarkMutatedLocals<'a, 'tcx> {
+    state: &'a mut IndexVec<Local, ConstantValue<'tcx>>,
+}
+
+impl Visitor<'tcx> for MarkMutatedLocals<'_, 'tcx> {
+    fn visit_local(&mut self, local: &Local, context: PlaceContext, _: Location) {
+        if context.is_mutating_use() {
+            self.state[*local] = FlatSet::Top;
+        }
+    }
+}
+
+/// Collects every local whose address is taken.
+struct BorrowedLocals(BitSet<Local>);
+
+impl Visitor<'tcx> for BorrowedLocals {
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        self.super_rvalue(rvalue, location);
+
+        match rvalue {
+            Rvalue::Ref(_, _, place) | Rvalue::AddressOf(_, place) if !place.is_indirect() => {
+                self.0.insert(place.local);
+            }
+            _ => {}
+        }
+    }
+}
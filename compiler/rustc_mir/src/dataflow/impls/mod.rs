@@ -5,6 +5,7 @@
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::Idx;
 use rustc_middle::mir::{self, Body, Location};
+use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt};
 
 use super::MoveDataParamEnv;
@@ -22,15 +23,24 @@
 
 mod borrowed_locals;
 pub(super) mod borrows;
+mod constant_locals;
 mod init_locals;
 mod liveness;
 mod storage_liveness;
+mod taint;
 
 pub use self::borrowed_locals::{MaybeBorrowedLocals, MaybeMutBorrowedLocals};
 pub use self::borrows::Borrows;
+pub use self::constant_locals::{ConstantLocals, ConstantValue};
 pub use self::init_locals::MaybeInitializedLocals;
 pub use self::liveness::MaybeLiveLocals;
 pub use self::storage_liveness::{MaybeRequiresStorage, MaybeStorageLive};
+pub use self::taint::{MaybeTaintedLocals, TaintSpec, TaintedSinkCall};
+
+pub(crate) fn provide(providers: &mut Providers) {
+    constant_locals::provide(providers);
+    taint::provide(providers);
+}
 
 /// `MaybeInitializedPlaces` tracks all places that might be
 /// initialized upon reaching a particular point in the control flow
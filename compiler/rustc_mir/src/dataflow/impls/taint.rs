@@ -0,0 +1,223 @@
+//! A taint analysis tracking which locals may hold data derived from the results of "source"
+//! calls, and which calls to "sink" functions may receive such data.
+
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::{
+    self, BasicBlock, Local, Location, Operand, Rvalue, StatementKind, TaintFns,
+};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::{self, TyCtxt};
+
+use crate::dataflow::{Analysis, AnalysisDomain};
+
+pub use rustc_middle::mir::TaintedSinkCall;
+
+/// Defines the sources, sinks and sanitizers of a `MaybeTaintedLocals` analysis.
+///
+/// Calls are identified by their `func` operand. For direct calls, the `DefId` of the callee can
+/// be found by matching `func.ty(body, tcx).kind()` against `ty::FnDef`.
+pub trait TaintSpec<'tcx> {
+    /// Returns `true` if the value returned by a call to `func` is tainted.
+    fn is_source(&self, func: &Operand<'tcx>) -> bool;
+
+    /// Returns `true` if tainted data passed as an argument to `func` should be reported by
+    /// `MaybeTaintedLocals::tainted_sink_calls`.
+    fn is_sink(&self, func: &Operand<'tcx>) -> bool;
+
+    /// Returns `true` if the value returned by a call to `func` is never tainted, whatever its
+    /// arguments are. By default, the result of a call is tainted if any argument is.
+    fn is_sanitizer(&self, _func: &Operand<'tcx>) -> bool {
+        false
+    }
+}
+
+/// A dataflow analysis that tracks which locals may hold tainted data, as defined by a
+/// `TaintSpec`.
+///
+/// Taint flows from the return value of source calls, through any rvalue that reads a tainted
+/// local, and through the return value of any call with a tainted argument (unless the callee is a
+/// sanitizer). An assignment to a whole local replaces its taint; an assignment to a projection
+/// of a local can only add taint to it.
+///
+/// This is not an alias analysis: taint written through a reference (`*r = tainted`) taints the
+/// reference `r` rather than its referent, so data laundered through an alias of the referent
+/// can be missed.
+pub struct MaybeTaintedLocals<S> {
+    spec: S,
+}
+
+impl<S> MaybeTaintedLocals<S> {
+    pub fn new(spec: S) -> Self {
+        MaybeTaintedLocals { spec }
+    }
+
+    /// Runs the analysis on `body` and returns every call to a sink that may receive tainted data,
+    /// in no particular order.
+    pub fn tainted_sink_calls(
+        self,
+        tcx: TyCtxt<'tcx>,
+        body: &mir::Body<'tcx>,
+    ) -> Vec<TaintedSinkCall>
+    where
+        S: TaintSpec<'tcx>,
+    {
+        let mut cursor =
+            self.into_engine(tcx, body).iterate_to_fixpoint().into_results_cursor(body);
+
+        let mut calls = vec![];
+        for (block, block_data) in body.basic_blocks().iter_enumerated() {
+            let (func, args) = match &block_data.terminator().kind {
+                mir::TerminatorKind::Call { func, args, .. } => (func, args),
+                _ => continue,
+            };
+            if !cursor.analysis().spec.is_sink(func) {
+                continue;
+            }
+
+            let location = body.terminator_loc(block);
+            cursor.seek_before_primary_effect(location);
+            let state = cursor.get();
+            if let Some(arg) = args.iter().position(|arg| reads_tainted_operand(state, arg)) {
+                calls.push(TaintedSinkCall { location, arg });
+            }
+        }
+        calls
+    }
+}
+
+impl<S> AnalysisDomain<'tcx> for MaybeTaintedLocals<S> {
+    type Domain = BitSet<Local>;
+
+    const NAME: &'static str = "maybe_tainted_locals";
+
+    fn bottom_value(&self, body: &mir::Body<'tcx>) -> Self::Domain {
+        // bottom = untainted
+        BitSet::new_empty(body.local_decls.len())
+    }
+
+    fn initialize_start_block(&self, _: &mir::Body<'tcx>, _: &mut Self::Domain) {
+        // Nothing is tainted on entry.
+    }
+}
+
+impl<S: TaintSpec<'tcx>> Analysis<'tcx> for MaybeTaintedLocals<S> {
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        statement: &mir::Statement<'tcx>,
+        _: Location,
+    ) {
+        match statement.kind {
+            StatementKind::Assign(box (place, ref rvalue)) => {
+                let tainted = reads_tainted_rvalue(state, rvalue);
+                assign(state, place, tainted);
+            }
+
+            StatementKind::StorageDead(local) => {
+                state.remove(local);
+            }
+
+            _ => {}
+        }
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &mir::Terminator<'tcx>,
+        _: Location,
+    ) {
+        if let mir::TerminatorKind::DropAndReplace { place, ref value, .. } = terminator.kind {
+            let tainted = reads_tainted_operand(state, value);
+            assign(state, place, tainted);
+        }
+    }
+
+    fn apply_call_return_effect(
+        &self,
+        state: &mut Self::Domain,
+        _block: BasicBlock,
+        func: &mir::Operand<'tcx>,
+        args: &[mir::Operand<'tcx>],
+        return_place: mir::Place<'tcx>,
+    ) {
+        // The arguments were moved into the call, but their taint bits are still set, so they
+        // can be used here to decide whether the return value is tainted.
+        let tainted = self.spec.is_source(func)
+            || (!self.spec.is_sanitizer(func)
+                && args.iter().any(|arg| reads_tainted_operand(state, arg)));
+        assign(state, return_place, tainted);
+    }
+}
+
+pub(super) fn provide(providers: &mut Providers) {
+    providers.tainted_sink_calls = tainted_sink_calls;
+}
+
+fn tainted_sink_calls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    (def_id, fns): (LocalDefId, TaintFns<'tcx>),
+) -> &'tcx [TaintedSinkCall] {
+    let body = tcx.optimized_mir(def_id.to_def_id());
+    let calls = MaybeTaintedLocals::new(fns).tainted_sink_calls(tcx, body);
+    tcx.arena.alloc_from_iter(calls)
+}
+
+impl TaintSpec<'tcx> for TaintFns<'tcx> {
+    fn is_source(&self, func: &Operand<'tcx>) -> bool {
+        callee(func).map_or(false, |def_id| self.sources.contains(&def_id))
+    }
+
+    fn is_sink(&self, func: &Operand<'tcx>) -> bool {
+        callee(func).map_or(false, |def_id| self.sinks.contains(&def_id))
+    }
+
+    fn is_sanitizer(&self, func: &Operand<'tcx>) -> bool {
+        callee(func).map_or(false, |def_id| self.sanitizers.contains(&def_id))
+    }
+}
+
+/// Returns the `DefId` of the function called by a direct call to `func`.
+fn callee(func: &Operand<'_>) -> Option<DefId> {
+    match func.constant().map(|c| c.literal.ty.kind()) {
+        Some(&ty::FnDef(def_id, _)) => Some(def_id),
+        _ => None,
+    }
+}
+
+/// Updates the taint of `place` after it is assigned a value that is `tainted` (or not).
+fn assign(state: &mut BitSet<Local>, place: mir::Place<'_>, tainted: bool) {
+    if tainted {
+        state.insert(place.local);
+    } else if place.as_local().is_some() {
+        state.remove(place.local);
+    }
+}
+
+fn reads_tainted_operand(state: &BitSet<Local>, operand: &Operand<'_>) -> bool {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => state.contains(place.local),
+        Operand::Constant(_) => false,
+    }
+}
+
+fn reads_tainted_rvalue(state: &BitSet<Local>, rvalue: &Rvalue<'tcx>) -> bool {
+    let mut visitor = ReadsTainted { state, found: false };
+    // The location is only used for error reporting by the visitor, which never happens here.
+    visitor.visit_rvalue(rvalue, Location::START);
+    visitor.found
+}
+
+struct ReadsTainted<'a> {
+    state: &'a BitSet<Local>,
+    found: bool,
+}
+
+impl Visitor<'tcx> for ReadsTainted<'_> {
+    fn visit_local(&mut self, local: &Local, _: PlaceContext, _: Location) {
+        // This also counts locals used as indices (`a[i]`), which errs on the side of taint.
+        self.found |= self.state.contains(*local);
+    }
+}
@@ -7,13 +7,16 @@
 
 use crate::transform::MirPass;
 use rustc_index::bit_set::BitSet;
+use rustc_index::vec::IndexVec;
 use rustc_middle::mir::{self, Body, Local, Location};
 use rustc_middle::ty::{self, Ty, TyCtxt};
 
 use crate::dataflow::impls::{
-    DefinitelyInitializedPlaces, MaybeInitializedPlaces, MaybeLiveLocals, MaybeMutBorrowedLocals,
-    MaybeUninitializedPlaces,
+    ConstantLocals, ConstantValue, DefinitelyInitializedPlaces, MaybeInitializedPlaces,
+    MaybeLiveLocals, MaybeMutBorrowedLocals, MaybeTaintedLocals, MaybeUninitializedPlaces,
+    TaintSpec,
 };
+use crate::dataflow::lattice::FlatSet;
 use crate::dataflow::move_paths::{HasMoveData, MoveData};
 use crate::dataflow::move_paths::{LookupResult, MovePathIndex};
 use crate::dataflow::MoveDataParamEnv;
@@ -76,6 +79,26 @@ fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
             sanity_check_via_rustc_peek(tcx, body, &attributes, &flow_liveness);
         }
 
+        if has_rustc_mir_with(sess, &attributes, sym::rustc_peek_constant_locals).is_some() {
+            let flow_constants =
+                ConstantLocals::new(body).into_engine(tcx, body).iterate_to_fixpoint();
+
+            sanity_check_via_rustc_peek(tcx, body, &attributes, &flow_constants);
+        }
+
+        if has_rustc_mir_with(sess, &attributes, sym::rustc_peek_tainted_locals).is_some() {
+            let spec = PeekTaintSpec { tcx };
+            let flow_tainted =
+                MaybeTaintedLocals::new(spec).into_engine(tcx, body).iterate_to_fixpoint();
+
+            sanity_check_via_rustc_peek(tcx, body, &attributes, &flow_tainted);
+
+            for call in MaybeTaintedLocals::new(spec).tainted_sink_calls(tcx, body) {
+                let span = body.source_info(call.location).span;
+                tcx.sess.span_err(span, "rustc_peek: tainted data reaches sink");
+            }
+        }
+
         if has_rustc_mir_with(sess, &attributes, sym::stop_after_dataflow).is_some() {
             tcx.sess.fatal("stop_after_dataflow ended compilation");
         }
@@ -324,3 +347,77 @@ fn peek_at(
         }
     }
 }
+
+impl<'tcx> RustcPeekAt<'tcx> for ConstantLocals {
+    fn peek_at(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        place: mir::Place<'tcx>,
+        flow_state: &IndexVec<Local, ConstantValue<'tcx>>,
+        call: PeekCall,
+    ) {
+        warn!("peek_at: place={:?}", place);
+        let local = if let Some(l) = place.as_local() {
+            l
+        } else {
+            tcx.sess.span_err(call.span, "rustc_peek: argument was not a local");
+            return;
+        };
+
+        if let FlatSet::Bottom | FlatSet::Top = flow_state[local] {
+            tcx.sess.span_err(call.span, "rustc_peek: not a known constant");
+        }
+    }
+}
+
+/// The `TaintSpec` used by `rustc_peek_tainted_locals`: the results of functions named `source`
+/// are tainted, functions named `sink` are sinks, and functions named `sanitize` are sanitizers.
+#[derive(Clone, Copy)]
+struct PeekTaintSpec<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl PeekTaintSpec<'tcx> {
+    fn callee_is(&self, func: &mir::Operand<'tcx>, name: &str) -> bool {
+        match func.constant().map(|c| c.literal.ty.kind()) {
+            Some(&ty::FnDef(def_id, _)) => self.tcx.item_name(def_id).as_str() == name,
+            _ => false,
+        }
+    }
+}
+
+impl TaintSpec<'tcx> for PeekTaintSpec<'tcx> {
+    fn is_source(&self, func: &mir::Operand<'tcx>) -> bool {
+        self.callee_is(func, "source")
+    }
+
+    fn is_sink(&self, func: &mir::Operand<'tcx>) -> bool {
+        self.callee_is(func, "sink")
+    }
+
+    fn is_sanitizer(&self, func: &mir::Operand<'tcx>) -> bool {
+        self.callee_is(func, "sanitize")
+    }
+}
+
+impl<'tcx> RustcPeekAt<'tcx> for MaybeTaintedLocals<PeekTaintSpec<'tcx>> {
+    fn peek_at(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        place: mir::Place<'tcx>,
+        flow_state: &BitSet<Local>,
+        call: PeekCall,
+    ) {
+        warn!("peek_at: place={:?}", place);
+        let local = if let Some(l) = place.as_local() {
+            l
+        } else {
+            tcx.sess.span_err(call.span, "rustc_peek: argument was not a local");
+            return;
+        };
+
+        if !flow_state.contains(local) {
+            tcx.sess.span_err(call.span, "rustc_peek: bit not set");
+        }
+    }
+}
@@ -5,19 +5,23 @@
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
-use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_hir::{Node, PatKind, TyKind};
 use rustc_middle::hir::map::Map;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::middle::privacy;
+use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, DefIdTree, TyCtxt};
+use rustc_session::config::CrateType;
 use rustc_session::lint;
 
 use rustc_ast as ast;
 use rustc_span::symbol::{sym, Symbol};
 
+use std::iter;
+
 // Any local node that may call something in its body block should be
 // explored. For example, if it's a live Node::Item that is a
 // function, then we should explore its block to check for codes that
@@ -704,4 +708,202 @@ pub fn check_crate(tcx: TyCtxt<'_>) {
     let live_symbols = find_live(tcx, access_levels, krate);
     let mut visitor = DeadVisitor { tcx, live_symbols };
     intravisit::walk_crate(&mut visitor, krate);
+
+    if tcx.sess.opts.debugging_opts.unused_pub_items
+        && tcx.sess.crate_types().contains(&CrateType::Executable)
+    {
+        check_unused_pub_items(tcx);
+    }
+}
+
+/// Reports the public items of upstream crates that no crate in the build uses. Only the
+/// crates built with `-Z unused-pub-items` record their candidates and uses in their
+/// metadata, so all other crates are skipped.
+///
+/// A crate built without the flag may use any item of the crates it depends on, so the
+/// candidates of those crates are skipped as well rather than reported as unused.
+fn check_unused_pub_items(tcx: TyCtxt<'_>) {
+    let mut used = FxHashSet::default();
+    let mut unknown_uses = FxHashSet::default();
+    for &cnum in tcx.crates().iter().chain(iter::once(&LOCAL_CRATE)) {
+        match tcx.used_extern_items(cnum) {
+            Some(items) => used.extend(items.iter().copied()),
+            None => unknown_uses.extend(
+                tcx.crate_dependencies(cnum).iter().copied().filter(|&dep| dep != cnum),
+            ),
+        }
+    }
+
+    for &cnum in tcx.crates() {
+        if unknown_uses.contains(&cnum) {
+            continue;
+        }
+        for &def_id in tcx.unused_pub_item_candidates(cnum) {
+            if used.contains(&def_id) {
+                continue;
+            }
+            let lint = lint::builtin::UNUSED_PUB_ITEMS;
+            tcx.struct_span_lint_hir(lint, hir::CRATE_HIR_ID, tcx.def_span(def_id), |lint| {
+                let descr = tcx.def_kind(def_id).descr(def_id);
+                lint.build(&format!(
+                    "public {} `{}` is never used outside of crate `{}`",
+                    descr,
+                    tcx.def_path_str(def_id),
+                    tcx.crate_name(cnum),
+                ))
+                .emit()
+            });
+        }
+    }
+}
+
+/// Collects the items of other crates that the local crate refers to, be it through
+/// paths, method calls or type-relative paths.
+struct ExternItemUseCollector<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    maybe_typeck_results: Option<&'tcx ty::TypeckResults<'tcx>>,
+    used: FxHashSet<DefId>,
+}
+
+impl ExternItemUseCollector<'tcx> {
+    #[track_caller]
+    fn typeck_results(&self) -> &'tcx ty::TypeckResults<'tcx> {
+        self.maybe_typeck_results
+            .expect("`ExternItemUseCollector::typeck_results` called outside of body")
+    }
+
+    fn record(&mut self, def_id: DefId) {
+        if def_id.is_local() {
+            return;
+        }
+        self.used.insert(def_id);
+
+        // Only module-level items are candidates, so a use of a constructor, variant or
+        // associated item also counts as a use of the item that owns it.
+        let tcx = self.tcx;
+        let owner = match tcx.def_kind(def_id) {
+            DefKind::Ctor(CtorOf::Struct, _) | DefKind::Variant => tcx.parent(def_id),
+            DefKind::Ctor(CtorOf::Variant, _) => {
+                tcx.parent(def_id).and_then(|variant_id| tcx.parent(variant_id))
+            }
+            DefKind::AssocFn | DefKind::AssocConst | DefKind::AssocTy => {
+                match tcx.associated_item(def_id).container {
+                    ty::TraitContainer(trait_def_id) => Some(trait_def_id),
+                    ty::ImplContainer(impl_def_id) => match tcx.impl_trait_ref(impl_def_id) {
+                        Some(trait_ref) => Some(trait_ref.def_id),
+                        None => tcx.type_of(impl_def_id).ty_adt_def().map(|adt| adt.did),
+                    },
+                }
+            }
+            _ => None,
+        };
+        if let Some(owner) = owner {
+            if !owner.is_local() {
+                self.used.insert(owner);
+            }
+        }
+    }
+
+    fn record_res(&mut self, res: Res) {
+        if let Res::Def(_, def_id) = res {
+            self.record(def_id);
+        }
+    }
+}
+
+impl Visitor<'tcx> for ExternItemUseCollector<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, body: hir::BodyId) {
+        let old_maybe_typeck_results =
+            self.maybe_typeck_results.replace(self.tcx.typeck_body(body));
+        let body = self.tcx.hir().body(body);
+        self.visit_body(body);
+        self.maybe_typeck_results = old_maybe_typeck_results;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            hir::ExprKind::Path(ref qpath @ hir::QPath::TypeRelative(..))
+            | hir::ExprKind::Struct(ref qpath @ hir::QPath::TypeRelative(..), ..) => {
+                let res = self.typeck_results().qpath_res(qpath, expr.hir_id);
+                self.record_res(res);
+            }
+            hir::ExprKind::MethodCall(..) => {
+                if let Some(def_id) = self.typeck_results().type_dependent_def_id(expr.hir_id) {
+                    self.record(def_id);
+                }
+            }
+            _ => (),
+        }
+
+        intravisit::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+        match pat.kind {
+            PatKind::Path(ref qpath @ hir::QPath::TypeRelative(..))
+            | PatKind::Struct(ref qpath @ hir::QPath::TypeRelative(..), ..)
+            | PatKind::TupleStruct(ref qpath @ hir::QPath::TypeRelative(..), ..) => {
+                let res = self.typeck_results().qpath_res(qpath, pat.hir_id);
+                self.record_res(res);
+            }
+            _ => (),
+        }
+
+        intravisit::walk_pat(self, pat);
+    }
+
+    fn visit_path(&mut self, path: &'tcx hir::Path<'tcx>, _: hir::HirId) {
+        self.record_res(path.res);
+        intravisit::walk_path(self, path);
+    }
+}
+
+fn used_extern_items(tcx: TyCtxt<'_>, cnum: CrateNum) -> Option<&[DefId]> {
+    assert_eq!(cnum, LOCAL_CRATE);
+    let mut collector =
+        ExternItemUseCollector { tcx, maybe_typeck_results: None, used: FxHashSet::default() };
+    intravisit::walk_crate(&mut collector, tcx.hir().krate());
+
+    // Bring everything into deterministic order for hashing.
+    let mut used: Vec<_> = collector.used.into_iter().collect();
+    used.sort_by_cached_key(|&def_id| tcx.def_path_hash(def_id));
+    Some(tcx.arena.alloc_from_iter(used))
+}
+
+fn unused_pub_item_candidates(tcx: TyCtxt<'_>, cnum: CrateNum) -> &[DefId] {
+    assert_eq!(cnum, LOCAL_CRATE);
+    let access_levels = tcx.privacy_access_levels(LOCAL_CRATE);
+    let candidates = tcx.hir().krate().items.values().filter(|item| {
+        let is_candidate_kind = matches!(
+            item.kind,
+            hir::ItemKind::Fn(..)
+                | hir::ItemKind::Const(..)
+                | hir::ItemKind::Static(..)
+                | hir::ItemKind::Struct(..)
+                | hir::ItemKind::Enum(..)
+                | hir::ItemKind::Union(..)
+                | hir::ItemKind::Trait(..)
+                | hir::ItemKind::TraitAlias(..)
+                | hir::ItemKind::TyAlias(..)
+        );
+        is_candidate_kind
+            && access_levels.is_exported(item.hir_id)
+            && !has_allow_dead_code_or_lang_attr(tcx, item.hir_id, &item.attrs)
+            && !tcx.sess.contains_name(&item.attrs, sym::no_mangle)
+            && !tcx.sess.contains_name(&item.attrs, sym::export_name)
+            && tcx.lint_level_at_node(lint::builtin::UNUSED_PUB_ITEMS, item.hir_id).0
+                != lint::Allow
+    });
+    let def_ids = candidates.map(|item| tcx.hir().local_def_id(item.hir_id).to_def_id());
+    tcx.arena.alloc_from_iter(def_ids)
+}
+
+pub fn provide(providers: &mut Providers) {
+    *providers = Providers { used_extern_items, unused_pub_item_candidates, ..*providers };
 }
@@ -842,6 +842,10 @@ fn parse_target_feature(slot: &mut String, v: Option<&str>) -> bool {
         (default: no)"),
     borrowck: String = ("migrate".to_string(), parse_string, [UNTRACKED],
         "select which borrowck is used (`mir` or `migrate`) (default: `migrate`)"),
+    borrowck_explain: bool = (false, parse_bool, [UNTRACKED],
+        "explain lifetime errors by listing every region constraint on the path that \
+        caused them (with `-Z dump-mir-graphviz`, also write that path as a graphviz `.dot` \
+        file into `-Z dump-mir-dir`) (default: no)"),
     borrowck_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather borrowck statistics (default: no)"),
     cgu_partitioning_strategy: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1010,6 +1014,10 @@ fn parse_target_feature(slot: &mut String, v: Option<&str>) -> bool {
         (default: PLT is disabled if full relro is enabled)"),
     polonius: bool = (false, parse_bool, [UNTRACKED],
         "enable polonius-based borrow-checker (default: no)"),
+    polonius_algorithm: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "the analysis `-Z polonius` runs: `Hybrid` (a location-insensitive pre-pass, with the \
+        full analysis only for bodies where it finds potential errors), `DatafrogOpt`, `Naive`, \
+        `LocationInsensitive` or `Compare` (default: `Hybrid`)"),
     polymorphize: bool = (false, parse_bool, [TRACKED],
           "perform polymorphization analysis"),
     pre_link_arg: (/* redirected to pre_link_args */) = ((), parse_string_push, [UNTRACKED],
@@ -1073,6 +1081,10 @@ fn parse_target_feature(slot: &mut String, v: Option<&str>) -> bool {
         for example: `-Z self-profile-events=default,query-keys`
         all options: none, all, default, generic-activity, query-provider, query-cache-hit
                      query-blocked, incr-cache-load, query-keys, function-args, args, llvm"),
+    semver_check: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "compare the public API of the crate against an older version of it, passed as \
+        `--extern <name>=<path>` under the given name, and report the changes that need a new \
+        major or minor version"),
     share_generics: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "make the current crate share its generic instantiations"),
     show_span: Option<String> = (None, parse_opt_string, [TRACKED],
@@ -1133,6 +1145,10 @@ fn parse_target_feature(slot: &mut String, v: Option<&str>) -> bool {
         `mir` (the MIR), or `mir-cfg` (graphviz formatted MIR)"),
     unsound_mir_opts: bool = (false, parse_bool, [TRACKED],
         "enable unsound and buggy MIR optimizations (default: no)"),
+    unused_pub_items: bool = (false, parse_bool, [TRACKED],
+        "record in the crate metadata which items of other crates this crate uses, and when \
+        building an executable, warn about public items of crates built with this flag that \
+        no crate uses, unless a crate built without it depends on them (default: no)"),
     unstable_options: bool = (false, parse_bool, [UNTRACKED],
         "adds unstable command line options to rustc interface (default: no)"),
     use_ctors_section: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -941,11 +941,13 @@
         rustc_partition_codegened,
         rustc_partition_reused,
         rustc_peek,
+        rustc_peek_constant_locals,
         rustc_peek_definite_init,
         rustc_peek_indirectly_mutable,
         rustc_peek_liveness,
         rustc_peek_maybe_init,
         rustc_peek_maybe_uninit,
+        rustc_peek_tainted_locals,
         rustc_polymorphize_error,
         rustc_private,
         rustc_proc_macro_decls,
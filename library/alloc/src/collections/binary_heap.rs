@@ -144,7 +144,9 @@
 #![stable(feature = "rust1", since = "1.0.0")]
 
 use core::fmt;
-use core::iter::{FromIterator, FusedIterator, InPlaceIterable, SourceIter, TrustedLen};
+#[cfg(not(no_global_oom_handling))]
+use core::iter::FromIterator;
+use core::iter::{FusedIterator, InPlaceIterable, SourceIter, TrustedLen};
 use core::mem::{self, swap, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::ptr;
@@ -152,6 +154,7 @@
 use crate::slice;
 use crate::vec::{self, AsIntoIter, Vec};
 
+#[cfg(not(no_global_oom_handling))]
 use super::SpecExtend;
 
 /// A priority queue implemented with a binary heap.
@@ -307,6 +310,7 @@ pub fn pop(mut this: PeekMut<'a, T>) -> T {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Clone> Clone for BinaryHeap<T> {
     fn clone(&self) -> Self {
@@ -348,7 +352,7 @@ impl<T: Ord> BinaryHeap<T> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> BinaryHeap<T> {
-        BinaryHeap { data: vec![] }
+        BinaryHeap { data: Vec::new() }
     }
 
     /// Creates an empty `BinaryHeap` with a specific capacity.
@@ -365,6 +369,7 @@ pub fn new() -> BinaryHeap<T> {
     /// let mut heap = BinaryHeap::with_capacity(10);
     /// heap.push(4);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn with_capacity(capacity: usize) -> BinaryHeap<T> {
         BinaryHeap { data: Vec::with_capacity(capacity) }
@@ -466,6 +471,7 @@ pub fn pop(&mut self) -> Option<T> {
     /// The worst case cost of a *single* call to `push` is *O*(*n*). The worst case
     /// occurs when capacity is exhausted and needs a resize. The resize cost
     /// has been amortized in the previous figures.
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn push(&mut self, item: T) {
         let old_len = self.len();
@@ -611,6 +617,7 @@ fn rebuild(&mut self) {
     /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
     /// assert!(b.is_empty());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "binary_heap_append", since = "1.11.0")]
     pub fn append(&mut self, other: &mut Self) {
         if self.len() < other.len() {
@@ -809,6 +816,7 @@ pub fn capacity(&self) -> usize {
     /// ```
     ///
     /// [`reserve`]: BinaryHeap::reserve
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve_exact(&mut self, additional: usize) {
         self.data.reserve_exact(additional);
@@ -832,6 +840,7 @@ pub fn reserve_exact(&mut self, additional: usize) {
     /// assert!(heap.capacity() >= 100);
     /// heap.push(4);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve(&mut self, additional: usize) {
         self.data.reserve(additional);
@@ -851,6 +860,7 @@ pub fn reserve(&mut self, additional: usize) {
     /// heap.shrink_to_fit();
     /// assert!(heap.capacity() == 0);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn shrink_to_fit(&mut self) {
         self.data.shrink_to_fit();
@@ -875,6 +885,7 @@ pub fn shrink_to_fit(&mut self) {
     /// heap.shrink_to(10);
     /// assert!(heap.capacity() >= 10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[unstable(feature = "shrink_to", reason = "new API", issue = "56431")]
     pub fn shrink_to(&mut self, min_capacity: usize) {
@@ -1133,7 +1144,7 @@ impl<T> FusedIterator for Iter<'_, T> {}
 ///
 /// [`into_iter`]: BinaryHeap::into_iter
 #[stable(feature = "rust1", since = "1.0.0")]
-#[derive(Clone)]
+#[cfg_attr(not(no_global_oom_handling), derive(Clone))]
 pub struct IntoIter<T> {
     iter: vec::IntoIter<T>,
 }
@@ -1200,7 +1211,8 @@ fn as_into_iter(&mut self) -> &mut vec::IntoIter<Self::Item> {
 }
 
 #[unstable(feature = "binary_heap_into_iter_sorted", issue = "59278")]
-#[derive(Clone, Debug)]
+#[cfg_attr(not(no_global_oom_handling), derive(Clone))]
+#[derive(Debug)]
 pub struct IntoIterSorted<T> {
     inner: BinaryHeap<T>,
 }
@@ -1355,6 +1367,7 @@ fn from(heap: BinaryHeap<T>) -> Vec<T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord> FromIterator<T> for BinaryHeap<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BinaryHeap<T> {
@@ -1400,6 +1413,7 @@ fn into_iter(self) -> Iter<'a, T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord> Extend<T> for BinaryHeap<T> {
     #[inline]
@@ -1418,18 +1432,21 @@ fn extend_reserve(&mut self, additional: usize) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T: Ord, I: IntoIterator<Item = T>> SpecExtend<I> for BinaryHeap<T> {
     default fn spec_extend(&mut self, iter: I) {
         self.extend_desugared(iter.into_iter());
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T: Ord> SpecExtend<BinaryHeap<T>> for BinaryHeap<T> {
     fn spec_extend(&mut self, ref mut other: BinaryHeap<T>) {
         self.append(other);
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T: Ord> BinaryHeap<T> {
     fn extend_desugared<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         let iterator = iter.into_iter();
@@ -1441,6 +1458,7 @@ fn extend_desugared<I: IntoIterator<Item = T>>(&mut self, iter: I) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for BinaryHeap<T> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
@@ -41,6 +41,31 @@ pub unsafe fn awaken(self) -> &'a mut T {
         // SAFETY: our own safety conditions imply this reference is again unique.
         unsafe { &mut *self.ptr.as_ptr() }
     }
+
+    /// Borrows a new unique reference from the unique borrow initially captured,
+    /// while staying dormant ourselves.
+    ///
+    /// # Safety
+    ///
+    /// The reborrow must have ended, i.e., the reference returned by `new` and
+    /// all pointers and references derived from it, must not be used anymore.
+    /// The same applies to any reference previously returned by this method.
+    pub unsafe fn reborrow(&mut self) -> &'a mut T {
+        // SAFETY: our own safety conditions imply this reference is again unique.
+        unsafe { &mut *self.ptr.as_ptr() }
+    }
+
+    /// Borrows a new shared reference from the unique borrow initially captured.
+    ///
+    /// # Safety
+    ///
+    /// No unique reference derived from the borrow may be used while the
+    /// returned reference is alive.
+    pub unsafe fn reborrow_shared(&self) -> &'_ T {
+        // SAFETY: our own safety conditions imply that nobody mutates through
+        // another reference at the same time.
+        unsafe { &*self.ptr.as_ptr() }
+    }
 }
 
 #[cfg(test)]
@@ -2,19 +2,30 @@
 use core::cmp::Ordering;
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
-use core::iter::{FromIterator, FusedIterator};
+#[cfg(not(no_global_oom_handling))]
+use core::iter::FromIterator;
+use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop};
-use core::ops::{Index, RangeBounds};
+use core::ops::{Bound, Index, RangeBounds};
 use core::ptr;
 
 use super::borrow::DormantMutRef;
-use super::node::{self, marker, ForceResult::*, Handle, NodeRef};
+#[cfg(not(no_global_oom_handling))]
+use super::node::ForceResult::*;
+use super::node::{self, marker, Handle, NodeRef};
 use super::search::{self, SearchResult::*};
 use super::unwrap_unchecked;
+use crate::collections::TryReserveError;
 
+mod cursor;
 mod entry;
+mod ranked;
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub use cursor::{Cursor, CursorMut};
 pub use entry::{Entry, OccupiedEntry, VacantEntry};
+#[unstable(feature = "btree_rank", issue = "none")]
+pub use ranked::RankedBTreeMap;
 use Entry::*;
 
 /// Minimum number of elements in nodes that are not a root.
@@ -141,6 +152,7 @@ fn drop(&mut self) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Clone, V: Clone> Clone for BTreeMap<K, V> {
     fn clone(&self) -> BTreeMap<K, V> {
@@ -238,12 +250,14 @@ fn take(&mut self, key: &Q) -> Option<K> {
         let root_node = map.root.as_mut()?.node_as_mut();
         match search::search_tree(root_node, key) {
             Found(handle) => {
-                Some(OccupiedEntry { handle, dormant_map, _marker: PhantomData }.remove_kv().0)
+                let entry = OccupiedEntry { handle, dormant_map, _marker: PhantomData };
+                Some(entry.remove_kv::<marker::Uncounted>().0)
             }
             GoDown(_) => None,
         }
     }
 
+    #[cfg(not(no_global_oom_handling))]
     fn replace(&mut self, key: K) -> Option<K> {
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node = Self::ensure_is_owned(&mut map.root).node_as_mut();
@@ -791,6 +805,7 @@ pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
     /// assert_eq!(map.insert(37, "c"), Some("b"));
     /// assert_eq!(map[&37], "c");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         match self.entry(key) {
@@ -802,6 +817,40 @@ pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         }
     }
 
+    /// Inserts a key-value pair into the map, or returns an error if the tree
+    /// had to grow and the allocation failed.
+    ///
+    /// On success this behaves like [`insert`]. The map is left unchanged, and
+    /// the key and value are dropped, if an error is returned.
+    ///
+    /// [`insert`]: BTreeMap::insert
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::new();
+    /// assert_eq!(map.try_insert(37, "a"), Ok(None));
+    /// assert_eq!(map.try_insert(37, "b"), Ok(Some("a")));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, TryReserveError> {
+        match self.try_entry(key)? {
+            Occupied(mut entry) => Ok(Some(entry.insert(value))),
+            Vacant(entry) => {
+                entry.try_insert(value)?;
+                Ok(None)
+            }
+        }
+    }
+
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
     ///
@@ -915,6 +964,7 @@ pub fn retain<F>(&mut self, mut f: F)
     /// assert_eq!(a[&4], "e");
     /// assert_eq!(a[&5], "f");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_append", since = "1.11.0")]
     pub fn append(&mut self, other: &mut Self) {
         // Do we have to append anything at all?
@@ -1025,6 +1075,167 @@ pub fn range_mut<T: ?Sized, R>(&mut self, range: R) -> RangeMut<'_, K, V>
         }
     }
 
+    /// Returns a [`Cursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// Unlike `range`, the cursor can move past the bound in either direction,
+    /// so repeated lookups around the same position need only one search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let mut cursor = a.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// let cursor = a.lower_bound(Bound::Excluded(&4));
+    /// assert_eq!(cursor.key(), None);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let root = self.root.as_ref();
+        let current =
+            root.and_then(|root| root.node_as_ref().lower_bound_leaf_edge(bound).next_kv().ok());
+        Cursor { current, root }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let mut cursor = a.lower_bound_mut(Bound::Included(&2));
+    /// assert_eq!(cursor.remove_current(), Some((2, "b")));
+    /// *cursor.value_mut().unwrap() = "C";
+    /// assert!(a.into_iter().eq(vec![(1, "a"), (3, "C"), (4, "d")]));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (root, dormant_root) = DormantMutRef::new(&mut self.root);
+        let current = root
+            .as_mut()
+            .and_then(|root| root.node_as_mut().lower_bound_leaf_edge(bound).next_kv().ok());
+        CursorMut { current, root: dormant_root, length: &mut self.length }
+    }
+
+    /// Returns a [`Cursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(4, "d");
+    /// let mut cursor = a.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), Some(&1));
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), None);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let root = self.root.as_ref();
+        let current = root
+            .and_then(|root| root.node_as_ref().upper_bound_leaf_edge(bound).next_back_kv().ok());
+        Cursor { current, root }
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.insert(3, "c");
+    /// let mut cursor = a.upper_bound_mut(Bound::Included(&2));
+    /// assert_eq!(cursor.key(), Some(&1));
+    /// cursor.insert_after(2, "b");
+    /// assert!(a.into_iter().eq(vec![(1, "a"), (2, "b"), (3, "c")]));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (root, dormant_root) = DormantMutRef::new(&mut self.root);
+        let current = root
+            .as_mut()
+            .and_then(|root| root.node_as_mut().upper_bound_leaf_edge(bound).next_back_kv().ok());
+        CursorMut { current, root: dormant_root, length: &mut self.length }
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1043,6 +1254,7 @@ pub fn range_mut<T: ?Sized, R>(&mut self, range: R) -> RangeMut<'_, K, V>
     ///
     /// assert_eq!(count["a"], 3);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         // FIXME(@porglezomp) Avoid allocating if we don't insert
@@ -1056,6 +1268,49 @@ pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         }
     }
 
+    /// Gets the given key's corresponding entry in the map for in-place manipulation,
+    /// or returns an error if the map had no root node yet and allocating it failed.
+    ///
+    /// Use [`VacantEntry::try_insert`] to fill a vacant entry without aborting on
+    /// allocation failure.
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::btree_map::Entry;
+    ///
+    /// let mut count: BTreeMap<&str, usize> = BTreeMap::new();
+    ///
+    /// for x in vec!["a", "b", "a"] {
+    ///     match count.try_entry(x).expect("out of memory") {
+    ///         Entry::Occupied(mut o) => *o.get_mut() += 1,
+    ///         Entry::Vacant(v) => {
+    ///             v.try_insert(1).expect("out of memory");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(count["a"], 2);
+    /// assert_eq!(count["b"], 1);
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_entry(&mut self, key: K) -> Result<Entry<'_, K, V>, TryReserveError> {
+        let (map, dormant_map) = DormantMutRef::new(self);
+        let root_node = Self::try_ensure_is_owned(&mut map.root)?.node_as_mut();
+        Ok(match search::search_tree(root_node, &key) {
+            Found(handle) => Occupied(OccupiedEntry { handle, dormant_map, _marker: PhantomData }),
+            GoDown(handle) => {
+                Vacant(VacantEntry { key, handle, dormant_map, _marker: PhantomData })
+            }
+        })
+    }
+
     /// Splits the collection into two at the given key. Returns everything after the given key,
     /// including the key.
     ///
@@ -1085,6 +1340,7 @@ pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
     /// assert_eq!(b[&17], "d");
     /// assert_eq!(b[&41], "e");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_split_off", since = "1.11.0")]
     pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
     where
@@ -1619,7 +1875,7 @@ pub(super) fn next<F>(&mut self, pred: &mut F) -> Option<(K, V)>
             let (k, v) = kv.kv_mut();
             if pred(k, v) {
                 *self.length -= 1;
-                let (kv, pos) = kv.remove_kv_tracking(|| {
+                let (kv, pos) = kv.remove_kv_tracking::<marker::Uncounted, _>(|| {
                     // SAFETY: we will touch the root in a way that will not
                     // invalidate the position returned.
                     let root = unsafe { self.dormant_root.take().unwrap().awaken() };
@@ -1877,6 +2133,7 @@ unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a mut V) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
@@ -1886,6 +2143,7 @@ fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V> Extend<(K, V)> for BTreeMap<K, V> {
     #[inline]
@@ -1901,6 +2159,7 @@ fn extend_one(&mut self, (k, v): (K, V)) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for BTreeMap<K, V> {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
@@ -2160,9 +2419,21 @@ pub const fn is_empty(&self) -> bool {
 
     /// If the root node is the empty (non-allocated) root node, allocate our
     /// own node. Is an associated function to avoid borrowing the entire BTreeMap.
+    #[cfg(not(no_global_oom_handling))]
     fn ensure_is_owned(root: &mut Option<node::Root<K, V>>) -> &mut node::Root<K, V> {
         root.get_or_insert_with(node::Root::new_leaf)
     }
+
+    /// Like `ensure_is_owned`, but returns an error if allocating the node fails.
+    fn try_ensure_is_owned(
+        root: &mut Option<node::Root<K, V>>,
+    ) -> Result<&mut node::Root<K, V>, TryReserveError> {
+        if root.is_none() {
+            *root = Some(node::Root::try_new_leaf()?);
+        }
+        // SAFETY: we just made sure that there is a root.
+        Ok(unsafe { unwrap_unchecked(root.as_mut()) })
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,515 @@
+use core::fmt::{self, Debug};
+
+use super::super::borrow::DormantMutRef;
+#[cfg(not(no_global_oom_handling))]
+use super::super::node::InsertResult::*;
+use super::super::node::{self, marker, Handle, NodeRef};
+
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `Cursor` is created with the [`BTreeMap::lower_bound`] and [`BTreeMap::upper_bound`] methods.
+///
+/// [`BTreeMap::lower_bound`]: super::BTreeMap::lower_bound
+/// [`BTreeMap::upper_bound`]: super::BTreeMap::upper_bound
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    pub(super) current:
+        Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    pub(super) root: Option<&'a node::Root<K, V>>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        Cursor { current: self.current, root: self.root }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.key_value()).finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the tree during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying tree. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `CursorMut` is created with the [`BTreeMap::lower_bound_mut`] and
+/// [`BTreeMap::upper_bound_mut`] methods.
+///
+/// [`BTreeMap::lower_bound_mut`]: super::BTreeMap::lower_bound_mut
+/// [`BTreeMap::upper_bound_mut`]: super::BTreeMap::upper_bound_mut
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<'a, K: 'a, V: 'a> {
+    pub(super) current:
+        Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    pub(super) root: DormantMutRef<'a, Option<node::Root<K, V>>>,
+    pub(super) length: &'a mut usize,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for CursorMut<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.key_value()).finish()
+    }
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current.take() {
+            None => self.root.and_then(|root| root.node_as_ref().first_leaf_edge().next_kv().ok()),
+            Some(current) => current.next_leaf_edge().next_kv().ok(),
+        };
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current.take() {
+            None => {
+                self.root.and_then(|root| root.node_as_ref().last_leaf_edge().next_back_kv().ok())
+            }
+            Some(current) => current.next_back_leaf_edge().next_back_kv().ok(),
+        };
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key(&self) -> Option<&'a K> {
+        self.current.map(|current| current.into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value(&self) -> Option<&'a V> {
+        self.current.map(|current| current.into_kv().1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.current.map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let mut next = self.clone();
+        next.move_next();
+        next.key_value()
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let mut prev = self.clone();
+        prev.move_prev();
+        prev.key_value()
+    }
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current.take() {
+            // SAFETY: the cursor holds no other reference into the tree.
+            None => unsafe { self.root.reborrow() }
+                .as_mut()
+                .and_then(|root| root.node_as_mut().first_leaf_edge().next_kv().ok()),
+            Some(current) => current.next_leaf_edge().next_kv().ok(),
+        };
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current.take() {
+            // SAFETY: the cursor holds no other reference into the tree.
+            None => unsafe { self.root.reborrow() }
+                .as_mut()
+                .and_then(|root| root.node_as_mut().last_leaf_edge().next_back_kv().ok()),
+            Some(current) => current.next_back_leaf_edge().next_back_kv().ok(),
+        };
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key(&self) -> Option<&K> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value(&self) -> Option<&V> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv().1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv())
+    }
+
+    /// Returns a mutable reference to the value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.current.as_mut().map(|current| current.kv_mut().1)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.current.as_mut().map(|current| {
+            let (key, value) = current.kv_mut();
+            (&*key, value)
+        })
+    }
+
+    /// Returns a reference to the key and value of the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let (key, value) = match self.current {
+            // SAFETY: the cursor holds no other reference into the tree.
+            None => unsafe { self.root.reborrow() }
+                .as_mut()?
+                .node_as_mut()
+                .first_leaf_edge()
+                .next_kv()
+                .ok()?
+                .into_kv_mut(),
+            // SAFETY: the reborrowed handle doesn't outlive the borrow of `self`,
+            // and we only use it to look around.
+            Some(ref mut current) => {
+                unsafe { current.reborrow_mut() }.next_leaf_edge().next_kv().ok()?.into_kv_mut()
+            }
+        };
+        Some((&*key, value))
+    }
+
+    /// Returns a reference to the key and value of the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let (key, value) = match self.current {
+            // SAFETY: the cursor holds no other reference into the tree.
+            None => unsafe { self.root.reborrow() }
+                .as_mut()?
+                .node_as_mut()
+                .last_leaf_edge()
+                .next_back_kv()
+                .ok()?
+                .into_kv_mut(),
+            // SAFETY: the reborrowed handle doesn't outlive the borrow of `self`,
+            // and we only use it to look around.
+            Some(ref mut current) => unsafe { current.reborrow_mut() }
+                .next_back_leaf_edge()
+                .next_back_kv()
+                .ok()?
+                .into_kv_mut(),
+        };
+        Some((&*key, value))
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        Cursor {
+            current: self.current.as_ref().map(|current| current.reborrow()),
+            // SAFETY: nothing mutates the tree while `self` is borrowed.
+            root: unsafe { self.root.reborrow_shared() }.as_ref(),
+        }
+    }
+
+    /// Inserts a new element into the `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element, or at the "ghost" non-element.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
+        let edge = match self.current.take() {
+            // SAFETY: the cursor holds no other reference into the tree.
+            None => unsafe { self.root.reborrow() }
+                .get_or_insert_with(node::Root::new_leaf)
+                .node_as_mut()
+                .first_leaf_edge(),
+            Some(current) => current.next_leaf_edge(),
+        };
+        let kv = self.insert_at(edge, key, value);
+        self.current = kv.left_edge().next_back_kv().ok();
+    }
+
+    /// Inserts a new element into the `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element, or at the "ghost" non-element.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
+        let edge = match self.current.take() {
+            // SAFETY: the cursor holds no other reference into the tree.
+            None => unsafe { self.root.reborrow() }
+                .get_or_insert_with(node::Root::new_leaf)
+                .node_as_mut()
+                .last_leaf_edge(),
+            Some(current) => current.next_back_leaf_edge(),
+        };
+        let kv = self.insert_at(edge, key, value);
+        self.current = kv.right_edge().next_kv().ok();
+    }
+
+    /// Inserts a key-value pair at a leaf edge, growing the tree if needed, and
+    /// returns a handle to the pair in its final place.
+    #[cfg(not(no_global_oom_handling))]
+    fn insert_at(
+        &mut self,
+        edge: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
+        key: K,
+        value: V,
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        let mut spare = edge.spare_nodes();
+        let (split, kv) = edge.insert_recursing::<marker::Uncounted>(key, value, &mut spare);
+        if let Split(ins) = split {
+            drop(ins.left);
+            // SAFETY: we don't use `kv` to navigate until the new root is in place.
+            let root = unsafe { self.root.reborrow() }.as_mut().unwrap();
+            root.push_internal_level_from(&mut spare).push(ins.k, ins.v, ins.right);
+        }
+        *self.length += 1;
+        kv
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let (old_kv, pos) = self.remove_current_tracking()?;
+        self.current = pos.next_kv().ok();
+        Some(old_kv)
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the previous element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
+        let (old_kv, pos) = self.remove_current_tracking()?;
+        self.current = pos.next_back_kv().ok();
+        Some(old_kv)
+    }
+
+    /// Removes the current element and returns it along with the leaf edge
+    /// where it used to be.
+    fn remove_current_tracking(
+        &mut self,
+    ) -> Option<((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>)> {
+        let current = self.current.take()?;
+        let mut emptied_internal_root = false;
+        let (old_kv, pos) =
+            current.remove_kv_tracking::<marker::Uncounted, _>(|| emptied_internal_root = true);
+        *self.length -= 1;
+        if emptied_internal_root {
+            // SAFETY: popping the root doesn't invalidate handles to other nodes,
+            // and the emptied root holds no element that `pos` could lead to.
+            let root = unsafe { self.root.reborrow() }.as_mut().unwrap();
+            root.pop_internal_level();
+        }
+        Some((old_kv, pos))
+    }
+}
+
+impl<'a, K: Ord, V> CursorMut<'a, K, V> {
+    /// Inserts a new element into the `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element, or at the "ghost" non-element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares less than or equal to the current element (if
+    ///   any).
+    /// - the given key compares greater than or equal to the next element (if
+    ///   any).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map: BTreeMap<u32, &str> = vec![(1, "a"), (4, "d")].into_iter().collect();
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&1));
+    /// cursor.insert_after(2, "b");
+    /// cursor.move_next();
+    /// cursor.insert_after(3, "c");
+    /// assert_eq!(cursor.key_value(), Some((&2, &"b")));
+    /// assert!(map.into_iter().eq(vec![(1, "a"), (2, "b"), (3, "c"), (4, "d")]));
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, key: K, value: V) {
+        if let Some(current) = self.key() {
+            if &key <= current {
+                panic!("key must be ordered above the current element");
+            }
+        }
+        if let Some((next, _)) = self.peek_next() {
+            if &key >= next {
+                panic!("key must be ordered below the next element");
+            }
+        }
+        unsafe {
+            self.insert_after_unchecked(key, value);
+        }
+    }
+
+    /// Inserts a new element into the `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeMap`.
+    ///
+    /// The cursor keeps pointing at the same element, or at the "ghost" non-element.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares greater than or equal to the current element (if
+    ///   any).
+    /// - the given key compares less than or equal to the previous element (if
+    ///   any).
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, key: K, value: V) {
+        if let Some(current) = self.key() {
+            if &key >= current {
+                panic!("key must be ordered below the current element");
+            }
+        }
+        if let Some((prev, _)) = self.peek_prev() {
+            if &key <= prev {
+                panic!("key must be ordered above the previous element");
+            }
+        }
+        unsafe {
+            self.insert_before_unchecked(key, value);
+        }
+    }
+}
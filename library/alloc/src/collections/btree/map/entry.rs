@@ -3,8 +3,9 @@
 use core::mem;
 
 use super::super::borrow::DormantMutRef;
-use super::super::node::{marker, Handle, InsertResult::*, NodeRef};
+use super::super::node::{marker, Handle, InsertResult::*, NodeRef, SpareNodes, SubtreeLens};
 use super::BTreeMap;
+use crate::collections::TryReserveError;
 
 use Entry::*;
 
@@ -85,6 +86,7 @@ impl<'a, K: Ord, V> Entry<'a, K, V> {
     ///
     /// assert_eq!(map["poneyland"], 12);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn or_insert(self, default: V) -> &'a mut V {
         match self {
@@ -108,6 +110,7 @@ pub fn or_insert(self, default: V) -> &'a mut V {
     ///
     /// assert_eq!(map["poneyland"], "hoho".to_string());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
         match self {
@@ -133,6 +136,7 @@ pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
     ///
     /// assert_eq!(map["poneyland"], 9);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
         match self {
@@ -212,6 +216,7 @@ impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
     ///
     /// assert_eq!(map["poneyland"], None);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     pub fn or_default(self) -> &'a mut V {
         match self {
             Occupied(entry) => entry.into_mut(),
@@ -272,21 +277,70 @@ pub fn into_key(self) -> K {
     /// }
     /// assert_eq!(map["poneyland"], 37);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(self, value: V) -> &'a mut V {
-        let out_ptr = match self.handle.insert_recursing(self.key, value) {
-            (Fit(_), val_ptr) => {
+        let spare = self.handle.spare_nodes();
+        self.insert_with_spare::<marker::Uncounted>(value, spare)
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key, and returns
+    /// a mutable reference to it, or returns an error if the tree had to grow
+    /// and the allocation failed.
+    ///
+    /// The map is left unchanged, and the key and `value` are dropped, if an
+    /// error is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(try_reserve)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::btree_map::Entry;
+    ///
+    /// let mut map: BTreeMap<&str, u32> = BTreeMap::new();
+    ///
+    /// if let Entry::Vacant(o) = map.entry("poneyland") {
+    ///     o.try_insert(37).expect("out of memory");
+    /// }
+    /// assert_eq!(map["poneyland"], 37);
+    /// ```
+    #[unstable(feature = "try_reserve", reason = "new API", issue = "48043")]
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, TryReserveError> {
+        let spare = self.handle.try_spare_nodes()?;
+        Ok(self.insert_with_spare::<marker::Uncounted>(value, spare))
+    }
+
+    // Body of `insert` and `try_insert`, which `RankedBTreeMap` also calls with
+    // `C` being `marker::Counted`.
+    pub(super) fn insert_with_spare<C: SubtreeLens>(
+        self,
+        value: V,
+        mut spare: SpareNodes<K, V>,
+    ) -> &'a mut V {
+        let out_ptr = match self.handle.insert_recursing::<C>(self.key, value, &mut spare) {
+            (Fit(_), kv) => {
+                let val_ptr: *mut V = kv.into_val_mut();
                 // Safety: We have consumed self.handle and the handle returned.
                 let map = unsafe { self.dormant_map.awaken() };
                 map.length += 1;
                 val_ptr
             }
-            (Split(ins), val_ptr) => {
+            (Split(ins), kv) => {
+                let val_ptr: *mut V = kv.into_val_mut();
                 drop(ins.left);
                 // Safety: We have consumed self.handle and the reference returned.
                 let map = unsafe { self.dormant_map.awaken() };
                 let root = map.root.as_mut().unwrap();
-                root.push_internal_level().push(ins.k, ins.v, ins.right);
+                let mut new_root = root.push_internal_level_from(&mut spare);
+                new_root.push(ins.k, ins.v, ins.right);
+                if C::ENABLED {
+                    new_root.recalc_subtree_len();
+                }
                 map.length += 1;
                 val_ptr
             }
@@ -335,7 +389,7 @@ pub fn key(&self) -> &K {
     /// ```
     #[stable(feature = "map_entry_recover_keys2", since = "1.12.0")]
     pub fn remove_entry(self) -> (K, V) {
-        self.remove_kv()
+        self.remove_kv::<marker::Uncounted>()
     }
 
     /// Gets a reference to the value in the entry.
@@ -456,13 +510,13 @@ pub fn insert(&mut self, value: V) -> V {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn remove(self) -> V {
-        self.remove_kv().1
+        self.remove_kv::<marker::Uncounted>().1
     }
 
     // Body of `remove_entry`, separate to keep the above implementations short.
-    pub(super) fn remove_kv(self) -> (K, V) {
+    pub(super) fn remove_kv<C: SubtreeLens>(self) -> (K, V) {
         let mut emptied_internal_root = false;
-        let (old_kv, _) = self.handle.remove_kv_tracking(|| emptied_internal_root = true);
+        let (old_kv, _) = self.handle.remove_kv_tracking::<C, _>(|| emptied_internal_root = true);
         // SAFETY: we consumed the intermediate root borrow, `self.handle`.
         let map = unsafe { self.dormant_map.awaken() };
         map.length -= 1;
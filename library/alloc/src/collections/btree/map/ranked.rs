@@ -0,0 +1,359 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+#[cfg(not(no_global_oom_handling))]
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::RangeBounds;
+
+use super::super::borrow::DormantMutRef;
+use super::super::node::{marker, Handle};
+use super::super::search::{self, SearchResult::*};
+#[cfg(not(no_global_oom_handling))]
+use super::Entry;
+use super::{BTreeMap, Iter, OccupiedEntry, Range};
+
+/// A map based on a B-Tree that also answers order-statistic queries, like
+/// [`rank`] and [`nth_key_value`], in logarithmic time.
+///
+/// Each internal node of the tree keeps track of the number of elements below
+/// it, which makes inserting and removing elements somewhat slower than in a
+/// [`BTreeMap`]. A `RankedBTreeMap` can hold at most `u32::MAX` elements.
+///
+/// [`rank`]: RankedBTreeMap::rank
+/// [`nth_key_value`]: RankedBTreeMap::nth_key_value
+///
+/// # Examples
+///
+/// ```
+/// #![feature(btree_rank)]
+/// use std::collections::btree_map::RankedBTreeMap;
+///
+/// let mut scores = RankedBTreeMap::new();
+/// scores.insert(10, "a");
+/// scores.insert(20, "b");
+/// scores.insert(30, "c");
+/// assert_eq!(scores.rank(&20), 1);
+/// assert_eq!(scores.nth_key_value(2), Some((&30, &"c")));
+/// ```
+#[unstable(feature = "btree_rank", issue = "none")]
+pub struct RankedBTreeMap<K, V> {
+    map: BTreeMap<K, V>,
+}
+
+impl<K: Ord, V> RankedBTreeMap<K, V> {
+    /// Makes a new, empty `RankedBTreeMap`.
+    ///
+    /// Does not allocate anything on its own.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn new() -> RankedBTreeMap<K, V> {
+        RankedBTreeMap { map: BTreeMap::new() }
+    }
+
+    /// Clears the map, removing all elements.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the map,
+    /// like [`BTreeMap::range`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn range<T: ?Sized, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        T: Ord,
+        K: Borrow<T>,
+        R: RangeBounds<T>,
+    {
+        self.map.range(range)
+    }
+
+    /// Inserts a key-value pair into the map, like [`BTreeMap::insert`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map already holds `u32::MAX` elements and the key is new.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let len = self.map.len();
+        match self.map.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                assert!(len < u32::MAX as usize, "capacity overflow in RankedBTreeMap");
+                let spare = entry.handle.spare_nodes();
+                entry.insert_with_spare::<marker::Counted>(value, spare);
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (map, dormant_map) = DormantMutRef::new(&mut self.map);
+        let root_node = map.root.as_mut()?.node_as_mut();
+        match search::search_tree(root_node, key) {
+            Found(handle) => {
+                let entry = OccupiedEntry { handle, dormant_map, _marker: PhantomData };
+                Some(entry.remove_kv::<marker::Counted>())
+            }
+            GoDown(_) => None,
+        }
+    }
+
+    /// Returns the number of keys in the map that are less than the given key.
+    /// This is the index at which the key is, or would be, in the sorted order of the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// This takes *O*(log(*n*)) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_rank)]
+    /// use std::collections::btree_map::RankedBTreeMap;
+    ///
+    /// let mut scores = RankedBTreeMap::new();
+    /// scores.insert(10, "a");
+    /// scores.insert(20, "b");
+    /// scores.insert(30, "c");
+    /// assert_eq!(scores.rank(&5), 0);
+    /// assert_eq!(scores.rank(&20), 1);
+    /// assert_eq!(scores.rank(&25), 2);
+    /// assert_eq!(scores.rank(&35), 3);
+    /// ```
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn rank<Q: ?Sized>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        match self.map.root.as_ref().map(|root| root.node_as_ref().rank(key)) {
+            Some(Ok(rank) | Err(rank)) => rank,
+            None => 0,
+        }
+    }
+
+    /// Returns the number of elements in a sub-range of the map, such that
+    /// `map.range_len(r) == map.range(r).count()`.
+    ///
+    /// This takes *O*(log(*n*)) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_rank)]
+    /// use std::collections::btree_map::RankedBTreeMap;
+    /// use std::ops::Bound::{Excluded, Included};
+    ///
+    /// let mut scores = RankedBTreeMap::new();
+    /// scores.insert(10, "a");
+    /// scores.insert(20, "b");
+    /// scores.insert(30, "c");
+    /// assert_eq!(scores.range_len(15..), 2);
+    /// assert_eq!(scores.range_len(10..30), 2);
+    /// assert_eq!(scores.range_len((Excluded(&10), Included(&30))), 2);
+    /// ```
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn range_len<T: ?Sized, R>(&self, range: R) -> usize
+    where
+        T: Ord,
+        K: Borrow<T>,
+        R: RangeBounds<T>,
+    {
+        match &self.map.root {
+            Some(root) => root.node_as_ref().range_len(range),
+            None => 0,
+        }
+    }
+}
+
+impl<K, V> RankedBTreeMap<K, V> {
+    /// Returns the number of elements in the map.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns a reference to the underlying `BTreeMap`, which offers all
+    /// the queries that do not modify the map.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn as_map(&self) -> &BTreeMap<K, V> {
+        &self.map
+    }
+
+    /// Converts the map into a plain `BTreeMap`, which no longer keeps track
+    /// of the subtree lengths.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn into_map(self) -> BTreeMap<K, V> {
+        self.map
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Returns the key-value pair at index `n` in the sorted order of the map,
+    /// i.e. the pair with exactly `n` smaller keys, or `None` if the map
+    /// has no more than `n` elements.
+    ///
+    /// This takes *O*(log(*n*)) time, unlike `iter().nth(n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_rank)]
+    /// use std::collections::btree_map::RankedBTreeMap;
+    ///
+    /// let mut scores = RankedBTreeMap::new();
+    /// scores.insert(10, "a");
+    /// scores.insert(20, "b");
+    /// scores.insert(30, "c");
+    /// assert_eq!(scores.nth_key_value(0), Some((&10, &"a")));
+    /// assert_eq!(scores.nth_key_value(2), Some((&30, &"c")));
+    /// assert_eq!(scores.nth_key_value(3), None);
+    /// ```
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn nth_key_value(&self, n: usize) -> Option<(&K, &V)> {
+        let root_node = self.map.root.as_ref()?.node_as_ref();
+        root_node.select(n).map(Handle::into_kv)
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<K, V> From<BTreeMap<K, V>> for RankedBTreeMap<K, V> {
+    /// Converts a `BTreeMap` into a `RankedBTreeMap`, counting the elements
+    /// below each internal node in *O*(*n*) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map holds more than `u32::MAX` elements.
+    fn from(mut map: BTreeMap<K, V>) -> Self {
+        assert!(map.length <= u32::MAX as usize, "capacity overflow in RankedBTreeMap");
+        if let Some(root) = map.root.as_mut() {
+            root.node_as_mut().recalc_all_subtree_lens();
+        }
+        RankedBTreeMap { map }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<K: Clone, V: Clone> Clone for RankedBTreeMap<K, V> {
+    fn clone(&self) -> Self {
+        RankedBTreeMap::from(self.map.clone())
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<K: Ord, V> Default for RankedBTreeMap<K, V> {
+    /// Creates an empty `RankedBTreeMap<K, V>`.
+    fn default() -> Self {
+        RankedBTreeMap::new()
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<K: Debug, V: Debug> Debug for RankedBTreeMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<K: Ord, V> FromIterator<(K, V)> for RankedBTreeMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = RankedBTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<K: Ord, V> Extend<(K, V)> for RankedBTreeMap<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<'a, K, V> IntoIterator for &'a RankedBTreeMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.map.iter()
+    }
+}
@@ -1989,3 +1989,286 @@ fn test_insert_remove_intertwined_ord_chaos() {
     }
     map.check_invariants();
 }
+
+#[test]
+fn test_try_insert() {
+    // Miri is too slow
+    let size = if cfg!(miri) { MIN_INSERTS_HEIGHT_2 } else { 10000 };
+
+    let mut map = BTreeMap::new();
+    for i in 0..size {
+        assert_eq!(map.try_insert(i, i), Ok(None));
+    }
+    map.check();
+    assert_eq!(map.len(), size);
+    assert_eq!(map.try_insert(0, 1), Ok(Some(0)));
+    assert!(map.iter().enumerate().all(|(i, (&k, _))| i == k));
+
+    let mut map = BTreeMap::new();
+    for i in (0..size).rev() {
+        assert_eq!(map.try_insert(i, i), Ok(None));
+    }
+    map.check();
+    assert_eq!(map.len(), size);
+
+    let mut map = BTreeMap::new();
+    let mut expected = BTreeMap::new();
+    let mut rng = DeterministicRng::new();
+    for _ in 0..size {
+        let (k, v) = (rng.next() % 4096, rng.next());
+        assert_eq!(map.try_insert(k, v), Ok(expected.insert(k, v)));
+    }
+    map.check();
+    assert!(map.iter().eq(expected.iter()));
+}
+
+#[test]
+fn test_try_entry() {
+    let mut map = BTreeMap::new();
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        match map.try_entry(i % 50).unwrap() {
+            Vacant(e) => {
+                assert_eq!(e.try_insert(1), Ok(&mut 1));
+            }
+            Occupied(mut e) => *e.get_mut() += 1,
+        }
+    }
+    map.check();
+    assert_eq!(map.len(), 50);
+    assert_eq!(map[&0], 2);
+    assert_eq!(map[&49], 1);
+}
+
+#[test]
+fn test_cursor() {
+    let map: BTreeMap<_, _> = (0..MIN_INSERTS_HEIGHT_2).map(|i| (i * 2, i)).collect();
+    let max = MIN_INSERTS_HEIGHT_2 * 2;
+    for probe in 0..max + 2 {
+        let cursor = map.lower_bound(Bound::Included(&probe));
+        assert_eq!(cursor.key_value(), map.range(probe..).next());
+        assert_eq!(cursor.peek_prev(), map.range(..probe).next_back());
+        let cursor = map.lower_bound(Bound::Excluded(&probe));
+        assert_eq!(cursor.key_value(), map.range((Excluded(probe), Unbounded)).next());
+        let cursor = map.upper_bound(Bound::Included(&probe));
+        assert_eq!(cursor.key_value(), map.range(..=probe).next_back());
+        assert_eq!(cursor.peek_next(), map.range(probe + 1..).next());
+        let cursor = map.upper_bound(Bound::Excluded(&probe));
+        assert_eq!(cursor.key_value(), map.range(..probe).next_back());
+    }
+
+    let mut cursor = map.lower_bound::<usize>(Bound::Unbounded);
+    assert!(iter::from_fn(|| {
+        let kv = cursor.key_value();
+        cursor.move_next();
+        kv
+    })
+    .eq(map.iter()));
+    // The last call moved past the ghost element, which wraps around to the front.
+    assert_eq!(cursor.key(), Some(&0));
+
+    let mut cursor = map.upper_bound::<usize>(Bound::Unbounded);
+    assert!(iter::from_fn(|| {
+        let kv = cursor.key_value();
+        cursor.move_prev();
+        kv
+    })
+    .eq(map.iter().rev()));
+    assert_eq!(cursor.key(), Some(&(max - 2)));
+}
+
+#[test]
+fn test_cursor_empty() {
+    let mut map = BTreeMap::<i32, i32>::new();
+    let mut cursor = map.lower_bound(Bound::Included(&0));
+    assert_eq!(cursor.key(), None);
+    cursor.move_next();
+    assert_eq!(cursor.key(), None);
+    assert_eq!(cursor.peek_prev(), None);
+
+    let mut cursor = map.upper_bound_mut(Bound::Included(&0));
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(1, 1);
+    cursor.insert_before(2, 2);
+    assert_eq!(cursor.key(), None);
+    assert_eq!(cursor.peek_next(), Some((&1, &mut 1)));
+    assert_eq!(cursor.peek_prev(), Some((&2, &mut 2)));
+    map.check();
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_cursor_mut_insert() {
+    let mut map: BTreeMap<_, _> = (1..=MIN_INSERTS_HEIGHT_2).map(|i| (i * 4, i)).collect();
+    let mut cursor = map.lower_bound_mut::<usize>(Bound::Unbounded);
+    while let Some(&key) = cursor.key() {
+        cursor.insert_before(key - 1, 0);
+        cursor.insert_after(key + 1, 0);
+        assert_eq!(cursor.key(), Some(&key));
+        cursor.move_next();
+        cursor.move_next();
+    }
+    map.check();
+    let expected = (1..=MIN_INSERTS_HEIGHT_2).flat_map(|i| i * 4 - 1..=i * 4 + 1);
+    assert!(map.keys().copied().eq(expected));
+
+    // Insert at the far ends through the ghost, so that the root splits.
+    let mut map: BTreeMap<_, _> = (0..NODE_CAPACITY as i32).map(|i| (i, i)).collect();
+    let mut cursor = map.lower_bound_mut::<i32>(Bound::Unbounded);
+    cursor.move_prev();
+    for i in 0..MIN_INSERTS_HEIGHT_2 as i32 {
+        cursor.insert_after(-1 - i, 0);
+        cursor.insert_before(NODE_CAPACITY as i32 + i, 0);
+        assert_eq!(cursor.key(), None);
+    }
+    map.check();
+    assert_eq!(map.len(), NODE_CAPACITY + MIN_INSERTS_HEIGHT_2 * 2);
+    assert!(map.height() >= Some(2));
+    let min = -(MIN_INSERTS_HEIGHT_2 as i32);
+    let max = (NODE_CAPACITY + MIN_INSERTS_HEIGHT_2) as i32;
+    assert!(map.keys().copied().eq(min..max));
+}
+
+#[test]
+fn test_cursor_mut_remove() {
+    let size = MIN_INSERTS_HEIGHT_2 * 2;
+    let mut map: BTreeMap<_, _> = (0..size).map(|i| (i, i)).collect();
+    let mut cursor = map.lower_bound_mut::<usize>(Bound::Unbounded);
+    while let Some(v) = cursor.value_mut() {
+        *v += 1000;
+        cursor.move_next();
+        let (k, v) = cursor.remove_current().unwrap();
+        assert_eq!(k, v);
+        assert_eq!(k % 2, 1);
+    }
+    map.check();
+    assert!(map.iter().map(|(&k, &v)| (k, v)).eq((0..size).step_by(2).map(|k| (k, k + 1000))));
+
+    let mut cursor = map.upper_bound_mut::<usize>(Bound::Unbounded);
+    while let Some((k, _)) = cursor.remove_current_and_move_back() {
+        assert_eq!(cursor.peek_next(), None);
+        assert!(cursor.key() < Some(&k));
+    }
+    assert_eq!(cursor.as_cursor().peek_next(), None);
+    map.check();
+    assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the current element")]
+fn test_cursor_mut_insert_after_out_of_order() {
+    let mut map: BTreeMap<_, _> = (0..10).map(|i| (i * 2, i)).collect();
+    let mut cursor = map.lower_bound_mut(Bound::Included(&4));
+    cursor.insert_after(3, 0);
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the previous element")]
+fn test_cursor_mut_insert_before_out_of_order() {
+    let mut map: BTreeMap<_, _> = (0..10).map(|i| (i * 2, i)).collect();
+    let mut cursor = map.lower_bound_mut(Bound::Included(&4));
+    cursor.insert_before(2, 0);
+}
+
+impl<K, V> RankedBTreeMap<K, V> {
+    // Panics if the map is corrupted, including its subtree lengths, or if the
+    // keys are not in strictly ascending order.
+    fn check(&self)
+    where
+        K: Debug + Ord,
+    {
+        self.as_map().check();
+        if let Some(root) = &self.as_map().root {
+            assert_eq!(self.len(), root.node_as_ref().assert_subtree_lens());
+        }
+    }
+}
+
+#[test]
+fn test_ranked_rank_and_nth_key_value() {
+    let size = MIN_INSERTS_HEIGHT_2 * 2;
+    let map: RankedBTreeMap<_, _> = (0..size).map(|i| (i * 2, i)).collect();
+    map.check();
+    for i in 0..size {
+        assert_eq!(map.rank(&(i * 2)), i);
+        assert_eq!(map.rank(&(i * 2 + 1)), i + 1);
+        assert_eq!(map.nth_key_value(i), Some((&(i * 2), &i)));
+    }
+    assert_eq!(map.nth_key_value(size), None);
+    assert_eq!(RankedBTreeMap::<i32, ()>::new().rank(&0), 0);
+    assert_eq!(RankedBTreeMap::<i32, ()>::new().nth_key_value(0), None);
+}
+
+#[test]
+fn test_ranked_remove() {
+    let size = MIN_INSERTS_HEIGHT_2 * 2;
+    let mut map: RankedBTreeMap<_, _> = (0..size).map(|i| (i, ())).collect();
+    for i in (0..size).step_by(3) {
+        assert_eq!(map.remove(&i), Some(()));
+        assert_eq!(map.remove(&i), None);
+    }
+    map.check();
+    for (n, (&k, _)) in map.iter().enumerate() {
+        assert_eq!(map.rank(&k), n);
+        assert_eq!(map.nth_key_value(n), Some((&k, &())));
+    }
+}
+
+#[test]
+fn test_ranked_random() {
+    let mut rng = DeterministicRng::new();
+    let mut map = RankedBTreeMap::new();
+    for _ in 0..MIN_INSERTS_HEIGHT_2 * 4 {
+        let key = rng.next() % 1024;
+        if rng.next() % 3 == 0 {
+            map.remove(&key);
+        } else {
+            map.insert(key, ());
+        }
+    }
+    map.check();
+    for (n, (&k, _)) in map.iter().enumerate() {
+        assert_eq!(map.rank(&k), n);
+    }
+    while let Some((&k, _)) = map.nth_key_value(map.len() / 2) {
+        map.remove(&k);
+    }
+    map.check();
+}
+
+#[test]
+fn test_ranked_range_len() {
+    let size = MIN_INSERTS_HEIGHT_2;
+    let map: RankedBTreeMap<_, _> = (0..size).map(|i| (i * 2, ())).collect();
+    for i in (0..=size * 2).step_by(5) {
+        for j in (i..=size * 2).step_by(3) {
+            for &start in [Included(&i), Excluded(&i), Unbounded].iter() {
+                for &end in [Included(&j), Excluded(&j), Unbounded].iter() {
+                    if i == j && matches!((start, end), (Excluded(_), Excluded(_))) {
+                        continue;
+                    }
+                    assert_eq!(map.range_len((start, end)), map.range((start, end)).count());
+                }
+            }
+        }
+    }
+    assert_eq!(RankedBTreeMap::<i32, ()>::new().range_len(1..0), 0);
+}
+
+#[test]
+#[should_panic(expected = "range start is greater than range end in BTreeMap")]
+fn test_ranked_range_len_backwards() {
+    let map: RankedBTreeMap<_, _> = (0..10).map(|i| (i, ())).collect();
+    map.range_len(5..4);
+}
+
+#[test]
+fn test_ranked_from_and_clone() {
+    let size = MIN_INSERTS_HEIGHT_2 * 2;
+    let map = RankedBTreeMap::from((0..size).map(|i| (i, i)).collect::<BTreeMap<_, _>>());
+    map.check();
+    assert_eq!(map.nth_key_value(size / 2), Some((&(size / 2), &(size / 2))));
+    let clone = map.clone();
+    clone.check();
+    assert_eq!(clone.rank(&(size / 2)), size / 2);
+    assert_eq!(clone.into_map(), map.into_map());
+}
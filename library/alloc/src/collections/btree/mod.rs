@@ -1,3 +1,4 @@
+#[cfg(not(no_global_oom_handling))]
 mod append;
 mod borrow;
 pub mod map;
@@ -5,9 +6,11 @@
 mod merge_iter;
 mod navigate;
 mod node;
+mod rank;
 mod remove;
 mod search;
 pub mod set;
+#[cfg(not(no_global_oom_handling))]
 mod split;
 
 #[doc(hidden)]
@@ -16,6 +19,7 @@ trait Recover<Q: ?Sized> {
 
     fn get(&self, key: &Q) -> Option<&Self::Key>;
     fn take(&mut self, key: &Q) -> Option<Self::Key>;
+    #[cfg(not(no_global_oom_handling))]
     fn replace(&mut self, key: Self::Key) -> Option<Self::Key>;
 }
 
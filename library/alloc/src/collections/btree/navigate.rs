@@ -1,6 +1,6 @@
 use core::borrow::Borrow;
 use core::cmp::Ordering;
-use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
 use core::ops::RangeBounds;
 use core::ptr;
 
@@ -251,6 +251,7 @@ impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Internal>, marke
     /// Given an internal edge handle, returns [`Result::Ok`] with a handle to the neighboring KV
     /// on the right side, which is either in the same internal node or in an ancestor node.
     /// If the internal edge is the last one in the tree, returns [`Result::Err`] with the root node.
+    #[cfg(not(no_global_oom_handling))]
     pub fn next_kv(
         self,
     ) -> Result<
@@ -434,12 +435,60 @@ pub fn last_leaf_edge(self) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, m
     }
 }
 
+impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    /// Returns the leaf edge in or underneath a node that precedes the first KV
+    /// above `bound`, i.e., the edge from which `next_kv` finds that KV.
+    pub fn lower_bound_leaf_edge<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let (key, inclusive) = match bound {
+            Included(key) => (key, true),
+            Excluded(key) => (key, false),
+            Unbounded => return self.first_leaf_edge(),
+        };
+        match search::search_tree(self, key) {
+            SearchResult::Found(kv) if inclusive => kv.next_back_leaf_edge(),
+            SearchResult::Found(kv) => kv.next_leaf_edge(),
+            SearchResult::GoDown(edge) => edge,
+        }
+    }
+
+    /// Returns the leaf edge in or underneath a node that follows the last KV
+    /// below `bound`, i.e., the edge from which `next_back_kv` finds that KV.
+    pub fn upper_bound_leaf_edge<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let (key, inclusive) = match bound {
+            Included(key) => (key, true),
+            Excluded(key) => (key, false),
+            Unbounded => return self.last_leaf_edge(),
+        };
+        match search::search_tree(self, key) {
+            SearchResult::Found(kv) if inclusive => kv.next_leaf_edge(),
+            SearchResult::Found(kv) => kv.next_back_leaf_edge(),
+            SearchResult::GoDown(edge) => edge,
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
 pub enum Position<BorrowType, K, V> {
     Leaf(NodeRef<BorrowType, K, V, marker::Leaf>),
     Internal(NodeRef<BorrowType, K, V, marker::Internal>),
     InternalKV(Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::KV>),
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
     /// Visits leaf nodes and internal KVs in order of ascending keys, and also
     /// visits internal nodes as a whole in a depth first order, meaning that
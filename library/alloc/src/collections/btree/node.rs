@@ -33,8 +33,9 @@
 use core::mem::{self, MaybeUninit};
 use core::ptr::{self, NonNull, Unique};
 
-use crate::alloc::{AllocRef, Global, Layout};
+use crate::alloc::{AllocError, AllocRef, Global, Layout};
 use crate::boxed::Box;
+use crate::collections::TryReserveError;
 
 const B: usize = 6;
 pub const CAPACITY: usize = 2 * B - 1;
@@ -56,6 +57,11 @@ struct LeafNode<K, V> {
     /// The number of keys and values this node stores.
     len: u16,
 
+    /// The number of keys and values stored in this node and all of its descendants. Only
+    /// kept up to date in the internal nodes of a `RankedBTreeMap`, and only 32 bits wide
+    /// so that it fits into the padding after `len` on 64-bit targets.
+    subtree_len: u32,
+
     /// The arrays storing the actual data of the node. Only the first `len` elements of each
     /// array are initialized and valid.
     keys: [MaybeUninit<K>; CAPACITY],
@@ -74,6 +80,7 @@ unsafe fn new() -> Self {
             parent: None,
             parent_idx: MaybeUninit::uninit(),
             len: 0,
+            subtree_len: 0,
         }
     }
 }
@@ -131,6 +138,73 @@ fn as_ptr(&self) -> NonNull<LeafNode<K, V>> {
     }
 }
 
+/// Nodes allocated ahead of an insertion, so that the splits it causes on the way up
+/// to the root cannot run out of memory halfway through and leave the tree mangled.
+pub struct SpareNodes<K, V> {
+    leaf: Option<Box<LeafNode<K, V>>>,
+    /// A stack of internal nodes, chained through the otherwise unused parent links.
+    internal: Option<Box<InternalNode<K, V>>>,
+}
+
+impl<K, V> SpareNodes<K, V> {
+    /// Allocates enough nodes for `splits` node splits, the first of which splits a leaf.
+    #[cfg(not(no_global_oom_handling))]
+    fn new(splits: usize) -> Self {
+        let mut spare = SpareNodes { leaf: None, internal: None };
+        if splits > 0 {
+            spare.leaf = Some(Box::new(unsafe { LeafNode::new() }));
+            for _ in 1..splits {
+                spare.push_internal(Box::new(unsafe { InternalNode::new() }));
+            }
+        }
+        spare
+    }
+
+    /// Like `new`, but returns an error if any of the allocations fail.
+    fn try_new(splits: usize) -> Result<Self, TryReserveError> {
+        fn try_box<T>(value: T) -> Result<Box<T>, TryReserveError> {
+            Box::try_new(value).map_err(|AllocError| TryReserveError::AllocError {
+                layout: Layout::new::<T>(),
+                non_exhaustive: (),
+            })
+        }
+
+        let mut spare = SpareNodes { leaf: None, internal: None };
+        if splits > 0 {
+            spare.leaf = Some(try_box(unsafe { LeafNode::new() })?);
+            for _ in 1..splits {
+                spare.push_internal(try_box(unsafe { InternalNode::new() })?);
+            }
+        }
+        Ok(spare)
+    }
+
+    fn push_internal(&mut self, mut node: Box<InternalNode<K, V>>) {
+        node.data.parent = self.internal.take().map(|next| NonNull::from(Box::leak(next)));
+        self.internal = Some(node);
+    }
+
+    fn take_leaf(&mut self) -> Box<LeafNode<K, V>> {
+        self.leaf.take().expect("BTreeMap insertion ran out of spare leaf nodes")
+    }
+
+    fn take_internal(&mut self) -> Box<InternalNode<K, V>> {
+        let mut node =
+            self.internal.take().expect("BTreeMap insertion ran out of spare internal nodes");
+        self.internal = node.data.parent.take().map(|next| unsafe { Box::from_raw(next.as_ptr()) });
+        node
+    }
+}
+
+impl<K, V> Drop for SpareNodes<K, V> {
+    fn drop(&mut self) {
+        // Unchain the internal nodes so that each of them gets freed.
+        while self.internal.is_some() {
+            drop(self.take_internal());
+        }
+    }
+}
+
 /// An owned tree.
 ///
 /// Note that this does not have a destructor, and must be cleaned up manually.
@@ -145,15 +219,23 @@ unsafe impl<K: Send, V: Send> Send for Root<K, V> {}
 
 impl<K, V> Root<K, V> {
     /// Returns the number of levels below the root.
+    #[cfg(not(no_global_oom_handling))]
     pub fn height(&self) -> usize {
         self.height
     }
 
     /// Returns a new owned tree, with its own root node that is initially empty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn new_leaf() -> Self {
         Root { node: BoxedNode::from_leaf(Box::new(unsafe { LeafNode::new() })), height: 0 }
     }
 
+    /// Like `new_leaf`, but returns an error if the allocation fails.
+    pub fn try_new_leaf() -> Result<Self, TryReserveError> {
+        let mut spare = SpareNodes::try_new(1)?;
+        Ok(Root { node: BoxedNode::from_leaf(spare.take_leaf()), height: 0 })
+    }
+
     /// Borrows and returns an immutable reference to the node owned by the root.
     pub fn node_as_ref(&self) -> NodeRef<marker::Immut<'_>, K, V, marker::LeafOrInternal> {
         NodeRef { height: self.height, node: self.node.as_ptr(), _marker: PhantomData }
@@ -164,14 +246,6 @@ pub fn node_as_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::LeafOrIn
         NodeRef { height: self.height, node: self.node.as_ptr(), _marker: PhantomData }
     }
 
-    /// Borrows and returns a mutable reference to the leaf node owned by the root.
-    /// # Safety
-    /// The root node is a leaf.
-    unsafe fn leaf_node_as_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::Leaf> {
-        debug_assert!(self.height == 0);
-        NodeRef { height: self.height, node: self.node.as_ptr(), _marker: PhantomData }
-    }
-
     /// Borrows and returns a mutable reference to the internal node owned by the root.
     /// # Safety
     /// The root node is not a leaf.
@@ -196,8 +270,23 @@ fn into_boxed_node(self) -> BoxedNode<K, V> {
     /// Adds a new internal node with a single edge pointing to the previous root node,
     /// make that new node the root node, and return it. This increases the height by 1
     /// and is the opposite of `pop_internal_level`.
+    #[cfg(not(no_global_oom_handling))]
     pub fn push_internal_level(&mut self) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
-        let mut new_node = Box::new(unsafe { InternalNode::new() });
+        self.push_internal_node(Box::new(unsafe { InternalNode::new() }))
+    }
+
+    /// Like `push_internal_level`, but takes the new root node from `spare`.
+    pub fn push_internal_level_from(
+        &mut self,
+        spare: &mut SpareNodes<K, V>,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+        self.push_internal_node(spare.take_internal())
+    }
+
+    fn push_internal_node(
+        &mut self,
+        mut new_node: Box<InternalNode<K, V>>,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
         new_node.edges[0].write(unsafe { ptr::read(&mut self.node) });
 
         self.node = BoxedNode::from_internal(new_node);
@@ -352,6 +441,51 @@ fn as_internal_mut(this: &mut Self) -> &'a mut InternalNode<K, V> {
     }
 }
 
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+    /// Sets the subtree length of an internal node.
+    fn set_subtree_len(&mut self, subtree_len: usize) {
+        debug_assert!(subtree_len <= u32::MAX as usize);
+        Self::as_internal_mut(self).data.subtree_len = subtree_len as u32;
+    }
+
+    /// Recomputes the subtree length of an internal node from its own length
+    /// and the subtree lengths of its children.
+    pub fn recalc_subtree_len(&mut self) {
+        let len = self.len();
+        let mut subtree_len = len;
+        for i in 0..=len {
+            subtree_len += unsafe { Handle::new_edge(self.reborrow(), i) }.descend().subtree_len();
+        }
+        self.set_subtree_len(subtree_len);
+    }
+}
+
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    /// Adjusts the subtree length of every ancestor of this node, after one
+    /// key/value pair was added to (`grown`) or removed from this node's subtree.
+    fn adjust_ancestor_subtree_lens(self, grown: bool) {
+        let mut node = self;
+        while let Ok(parent) = node.ascend() {
+            let mut parent = parent.into_node();
+            let subtree_len = parent.reborrow().subtree_len();
+            parent.set_subtree_len(if grown { subtree_len + 1 } else { subtree_len - 1 });
+            node = parent.forget_type();
+        }
+    }
+
+    /// Recomputes the subtree length of every internal node in this subtree,
+    /// from the bottom up.
+    pub fn recalc_all_subtree_lens(self) {
+        if let ForceResult::Internal(mut internal) = self.force() {
+            for i in 0..=internal.len() {
+                let edge = unsafe { Handle::new_edge(internal.reborrow_mut(), i) };
+                edge.descend().recalc_all_subtree_lens();
+            }
+            internal.recalc_subtree_len();
+        }
+    }
+}
+
 impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
     /// Finds the length of the node. This is the number of keys or values.
     /// The number of edges is `len() + 1`.
@@ -363,8 +497,21 @@ pub fn len(&self) -> usize {
         unsafe { usize::from((*Self::as_leaf_ptr(self)).len) }
     }
 
+    /// Finds the number of key/value pairs in the subtree rooted at this node,
+    /// i.e. in this node and all of its descendants. Only meaningful in the
+    /// tree of a `RankedBTreeMap`.
+    pub fn subtree_len(&self) -> usize {
+        if self.height == 0 {
+            self.len()
+        } else {
+            // Like `len`, we only access the `subtree_len` field here.
+            unsafe { (*Self::as_leaf_ptr(self)).subtree_len as usize }
+        }
+    }
+
     /// Returns the height of this node with respect to the leaf level. Zero height means the
     /// node is a leaf itself.
+    #[cfg(not(no_global_oom_handling))]
     pub fn height(&self) -> usize {
         self.height
     }
@@ -450,6 +597,7 @@ pub fn last_edge(self) -> Handle<Self, marker::Edge> {
     }
 
     /// Note that `self` must be nonempty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn first_kv(self) -> Handle<Self, marker::KV> {
         let len = self.len();
         assert!(len > 0);
@@ -457,6 +605,7 @@ pub fn first_kv(self) -> Handle<Self, marker::KV> {
     }
 
     /// Note that `self` must be nonempty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn last_kv(self) -> Handle<Self, marker::KV> {
         let len = self.len();
         assert!(len > 0);
@@ -801,6 +950,7 @@ fn pop_front(&mut self) -> (K, V, Option<Root<K, V>>) {
         }
     }
 
+    #[cfg(not(no_global_oom_handling))]
     fn into_kv_pointers_mut(mut self) -> (*mut K, *mut V) {
         let leaf = Self::as_leaf_mut(&mut self);
         let keys = MaybeUninit::slice_as_mut_ptr(&mut leaf.keys);
@@ -1001,8 +1151,12 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// this edge. This method assumes that there is enough space in the node for the new
     /// pair to fit.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert_fit(&mut self, key: K, val: V) -> *mut V {
+    /// The returned handle points to the inserted pair.
+    fn insert_fit(
+        mut self,
+        key: K,
+        val: V,
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
         debug_assert!(self.node.len() < CAPACITY);
 
         unsafe {
@@ -1010,7 +1164,7 @@ fn insert_fit(&mut self, key: K, val: V) -> *mut V {
             slice_insert(self.node.reborrow_mut().into_key_area_slice(), self.idx, key);
             slice_insert(self.node.reborrow_mut().into_val_area_slice(), self.idx, val);
 
-            self.node.reborrow_mut().into_val_area_mut_at(self.idx).assume_init_mut()
+            Handle::new_kv(self.node, self.idx)
         }
     }
 }
@@ -1019,26 +1173,37 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// Inserts a new key/value pair between the key/value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room.
     ///
-    /// The returned pointer points to the inserted value.
-    fn insert(mut self, key: K, val: V) -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+    /// The returned handle points to the inserted pair.
+    fn insert(
+        self,
+        key: K,
+        val: V,
+        spare: &mut SpareNodes<K, V>,
+    ) -> (
+        InsertResult<'a, K, V, marker::Leaf>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
         if self.node.len() < CAPACITY {
-            let val_ptr = self.insert_fit(key, val);
-            let kv = unsafe { Handle::new_kv(self.node, self.idx) };
-            (InsertResult::Fit(kv), val_ptr)
+            let kv = self.insert_fit(key, val);
+            (InsertResult::Fit(unsafe { ptr::read(&kv) }), kv)
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let (mut left, k, v, mut right) = middle.split();
-            let mut insertion_edge = match insertion {
+            let (left, k, v, right) = middle.split(spare);
+            // The handle we return aliases `left` or `right`, which is fine as long as
+            // the caller doesn't use it until it's done growing the tree.
+            let insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
-                    Handle::new_edge(left.reborrow_mut(), insert_idx)
+                    Handle::new_edge(ptr::read(&left), insert_idx)
                 },
                 LeftOrRight::Right(insert_idx) => unsafe {
-                    Handle::new_edge(right.leaf_node_as_mut(), insert_idx)
+                    let node =
+                        NodeRef { height: 0, node: right.node.as_ptr(), _marker: PhantomData };
+                    Handle::new_edge(node, insert_idx)
                 },
             };
-            let val_ptr = insertion_edge.insert_fit(key, val);
-            (InsertResult::Split(SplitResult { left: left.forget_type(), k, v, right }), val_ptr)
+            let kv = insertion_edge.insert_fit(key, val);
+            (InsertResult::Split(SplitResult { left: left.forget_type(), k, v, right }), kv)
         }
     }
 }
@@ -1059,7 +1224,7 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// Inserts a new key/value pair and an edge that will go to the right of that new pair
     /// between this edge and the key/value pair to the right of this edge. This method assumes
     /// that there is enough space in the node for the new pair to fit.
-    fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V>) {
+    fn insert_fit<C: SubtreeLens>(&mut self, key: K, val: V, edge: Root<K, V>) {
         debug_assert!(self.node.len() < CAPACITY);
         debug_assert!(edge.height == self.node.height - 1);
 
@@ -1072,27 +1237,31 @@ fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V>) {
 
             self.node.correct_childrens_parent_links((self.idx + 1)..=self.node.len());
         }
+        if C::ENABLED {
+            self.node.recalc_subtree_len();
+        }
     }
 
     /// Inserts a new key/value pair and an edge that will go to the right of that new pair
     /// between this edge and the key/value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    fn insert(
+    fn insert<C: SubtreeLens>(
         mut self,
         key: K,
         val: V,
         edge: Root<K, V>,
+        spare: &mut SpareNodes<K, V>,
     ) -> InsertResult<'a, K, V, marker::Internal> {
         assert!(edge.height == self.node.height - 1);
 
         if self.node.len() < CAPACITY {
-            self.insert_fit(key, val, edge);
+            self.insert_fit::<C>(key, val, edge);
             let kv = unsafe { Handle::new_kv(self.node, self.idx) };
             InsertResult::Fit(kv)
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let (mut left, k, v, mut right) = middle.split();
+            let (mut left, k, v, mut right) = middle.split::<C>(spare);
             let mut insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(left.reborrow_mut(), insert_idx)
@@ -1101,7 +1270,7 @@ fn insert(
                     Handle::new_edge(right.internal_node_as_mut(), insert_idx)
                 },
             };
-            insertion_edge.insert_fit(key, val, edge);
+            insertion_edge.insert_fit::<C>(key, val, edge);
             InsertResult::Split(SplitResult { left: left.forget_type(), k, v, right })
         }
     }
@@ -1114,33 +1283,79 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     ///
     /// If the returned result is a `Fit`, its handle's node can be this edge's node or an ancestor.
     /// If the returned result is a `Split`, the `left` field will be the root node.
-    /// The returned pointer points to the inserted value.
-    pub fn insert_recursing(
+    /// The returned handle points to the inserted pair, which may have moved into
+    /// a new sibling of this edge's node.
+    ///
+    /// New nodes are taken from `spare`, which must have been obtained for this edge
+    /// from `spare_nodes` or `try_spare_nodes`.
+    ///
+    /// If `C` is `marker::Counted`, the subtree lengths of the internal nodes are kept
+    /// up to date, except for a new root node put on top of a `Split` result.
+    pub fn insert_recursing<C: SubtreeLens>(
         self,
         key: K,
         value: V,
-    ) -> (InsertResult<'a, K, V, marker::LeafOrInternal>, *mut V) {
-        let (mut split, val_ptr) = match self.insert(key, value) {
-            (InsertResult::Fit(handle), ptr) => {
-                return (InsertResult::Fit(handle.forget_node_type()), ptr);
+        spare: &mut SpareNodes<K, V>,
+    ) -> (
+        InsertResult<'a, K, V, marker::LeafOrInternal>,
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>,
+    ) {
+        let (mut split, kv) = match self.insert(key, value, spare) {
+            (InsertResult::Fit(mut handle), kv) => {
+                if C::ENABLED {
+                    let node = unsafe { handle.reborrow_mut() }.into_node();
+                    node.forget_type().adjust_ancestor_subtree_lens(true);
+                }
+                return (InsertResult::Fit(handle.forget_node_type()), kv);
             }
-            (InsertResult::Split(split), val_ptr) => (split, val_ptr),
+            (InsertResult::Split(split), kv) => (split, kv),
         };
 
         loop {
             split = match split.left.ascend() {
-                Ok(parent) => match parent.insert(split.k, split.v, split.right) {
-                    InsertResult::Fit(handle) => {
-                        return (InsertResult::Fit(handle.forget_node_type()), val_ptr);
+                Ok(parent) => match parent.insert::<C>(split.k, split.v, split.right, spare) {
+                    InsertResult::Fit(mut handle) => {
+                        if C::ENABLED {
+                            let node = unsafe { handle.reborrow_mut() }.into_node();
+                            node.forget_type().adjust_ancestor_subtree_lens(true);
+                        }
+                        return (InsertResult::Fit(handle.forget_node_type()), kv);
                     }
                     InsertResult::Split(split) => split,
                 },
                 Err(root) => {
-                    return (InsertResult::Split(SplitResult { left: root, ..split }), val_ptr);
+                    return (InsertResult::Split(SplitResult { left: root, ..split }), kv);
                 }
             };
         }
     }
+
+    /// Allocates the nodes that `insert_recursing` needs to insert at this edge.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn spare_nodes(&self) -> SpareNodes<K, V> {
+        SpareNodes::new(self.split_count())
+    }
+
+    /// Like `spare_nodes`, but returns an error if any of the allocations fail.
+    pub fn try_spare_nodes(&self) -> Result<SpareNodes<K, V>, TryReserveError> {
+        SpareNodes::try_new(self.split_count())
+    }
+
+    /// Counts the nodes that inserting at this edge splits: the leaf and each of its
+    /// ancestors, as long as they are full. If even the root is full, we need one
+    /// more node to put on top of the split root.
+    fn split_count(&self) -> usize {
+        let mut node = self.node.reborrow().forget_type();
+        let mut splits = 0;
+        while node.len() == CAPACITY {
+            splits += 1;
+            match node.ascend() {
+                Ok(parent) => node = parent.into_node().forget_type(),
+                Err(_) => return splits + 1,
+            }
+        }
+        splits
+    }
 }
 
 impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge> {
@@ -1169,6 +1384,7 @@ pub fn into_kv(self) -> (&'a K, &'a V) {
 }
 
 impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_key_mut(self) -> &'a mut K {
         unsafe { self.node.into_key_area_mut_at(self.idx).assume_init_mut() }
     }
@@ -1176,6 +1392,15 @@ pub fn into_key_mut(self) -> &'a mut K {
     pub fn into_val_mut(self) -> &'a mut V {
         unsafe { self.node.into_val_area_mut_at(self.idx).assume_init_mut() }
     }
+
+    pub fn into_kv_mut(mut self) -> (&'a mut K, &'a mut V) {
+        // We cannot call `into_key_mut` and `into_val_mut` in turn, because the
+        // second call invalidates the reference returned by the first.
+        let leaf = NodeRef::as_leaf_mut(&mut self.node);
+        let key = unsafe { leaf.keys.get_unchecked_mut(self.idx).assume_init_mut() };
+        let val = unsafe { leaf.vals.get_unchecked_mut(self.idx).assume_init_mut() };
+        (key, val)
+    }
 }
 
 impl<'a, K, V, NodeType> Handle<NodeRef<marker::ValMut<'a>, K, V, NodeType>, marker::KV> {
@@ -1237,11 +1462,14 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// - The node is truncated to only contain the key/value pairs to the left of
     ///   this handle.
     /// - The key and value pointed to by this handle are extracted.
-    /// - All the key/value pairs to the right of this handle are put into a newly
-    ///   allocated node.
-    pub fn split(mut self) -> (NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, K, V, Root<K, V>) {
+    /// - All the key/value pairs to the right of this handle are put into a node
+    ///   taken from `spare`.
+    pub fn split(
+        mut self,
+        spare: &mut SpareNodes<K, V>,
+    ) -> (NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, K, V, Root<K, V>) {
         unsafe {
-            let mut new_node = Box::new(LeafNode::new());
+            let mut new_node = spare.take_leaf();
 
             let (k, v) = self.split_leaf_data(&mut new_node);
 
@@ -1252,13 +1480,18 @@ pub fn split(mut self) -> (NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, K, V, R
 
     /// Removes the key/value pair pointed to by this handle and returns it, along with the edge
     /// that the key/value pair collapsed into.
-    pub fn remove(
+    ///
+    /// If `C` is `marker::Counted`, the subtree lengths of all ancestors are decremented.
+    pub fn remove<C: SubtreeLens>(
         mut self,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         unsafe {
             let k = slice_remove(self.node.reborrow_mut().into_key_area_slice(), self.idx);
             let v = slice_remove(self.node.reborrow_mut().into_val_area_slice(), self.idx);
             *self.node.reborrow_mut().into_len_mut() -= 1;
+            if C::ENABLED {
+                self.node.reborrow_mut().forget_type().adjust_ancestor_subtree_lens(false);
+            }
             ((k, v), self.left_edge())
         }
     }
@@ -1271,10 +1504,15 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     ///   left of this handle.
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key/value pairs to the right of this handle are put into
-    ///   a newly allocated node.
-    pub fn split(mut self) -> (NodeRef<marker::Mut<'a>, K, V, marker::Internal>, K, V, Root<K, V>) {
+    ///   a node taken from `spare`.
+    ///
+    /// If `C` is `marker::Counted`, the subtree lengths of both halves are recomputed.
+    pub fn split<C: SubtreeLens>(
+        mut self,
+        spare: &mut SpareNodes<K, V>,
+    ) -> (NodeRef<marker::Mut<'a>, K, V, marker::Internal>, K, V, Root<K, V>) {
         unsafe {
-            let mut new_node = Box::new(InternalNode::new());
+            let mut new_node = spare.take_internal();
             let new_len = self.split_new_node_len();
             // Move edges out before reducing length:
             ptr::copy_nonoverlapping(
@@ -1288,6 +1526,10 @@ pub fn split(mut self) -> (NodeRef<marker::Mut<'a>, K, V, marker::Internal>, K,
             let mut right = Root { node: BoxedNode::from_internal(new_node), height };
 
             right.internal_node_as_mut().correct_childrens_parent_links(0..=new_len);
+            if C::ENABLED {
+                right.internal_node_as_mut().recalc_subtree_len();
+                self.node.recalc_subtree_len();
+            }
 
             (self.node, k, v, right)
         }
@@ -1303,6 +1545,7 @@ pub struct BalancingContext<'a, K, V> {
 }
 
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
+    #[cfg(not(no_global_oom_handling))]
     pub fn consider_for_balancing(self) -> BalancingContext<'a, K, V> {
         let self1 = unsafe { ptr::read(&self) };
         let self2 = unsafe { ptr::read(&self) };
@@ -1358,10 +1601,12 @@ pub fn right_child_len(&self) -> usize {
         self.right_child.len()
     }
 
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_left_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
         self.left_child
     }
 
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_right_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
         self.right_child
     }
@@ -1381,7 +1626,9 @@ impl<'a, K: 'a, V: 'a> BalancingContext<'a, K, V> {
     /// to where the edge in that child node ended up,
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge(
+    ///
+    /// If `C` is `marker::Counted`, the subtree length of the merged node is kept up to date.
+    pub fn merge<C: SubtreeLens>(
         mut self,
         track_edge_idx: Option<LeftOrRight<usize>>,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
@@ -1442,6 +1689,10 @@ pub fn merge(
                 );
 
                 left_node.correct_childrens_parent_links(left_len + 1..=left_len + 1 + right_len);
+                if C::ENABLED {
+                    let subtree_len = left_node.subtree_len() + 1 + right_node.subtree_len();
+                    left_node.set_subtree_len(subtree_len);
+                }
 
                 Global.dealloc(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
             } else {
@@ -1461,7 +1712,9 @@ pub fn merge(
     /// of the parent, while pushing the old parent key/value pair into the right child.
     /// Returns a handle to the edge in the right child corresponding to where the original
     /// edge specified by `track_right_edge_idx` ended up.
-    pub fn steal_left(
+    ///
+    /// If `C` is `marker::Counted`, the subtree lengths of both children are kept up to date.
+    pub fn steal_left<C: SubtreeLens>(
         mut self,
         track_right_edge_idx: usize,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
@@ -1475,6 +1728,9 @@ pub fn steal_left(
                 ForceResult::Leaf(mut leaf) => leaf.push_front(k, v),
                 ForceResult::Internal(mut internal) => internal.push_front(k, v, edge.unwrap()),
             }
+            if C::ENABLED {
+                self.recalc_children_subtree_lens();
+            }
 
             Handle::new_edge(self.right_child, 1 + track_right_edge_idx)
         }
@@ -1484,7 +1740,9 @@ pub fn steal_left(
     /// of the parent, while pushing the old parent key/value pair onto the left child.
     /// Returns a handle to the edge in the left child specified by `track_left_edge_idx`,
     /// which didn't move.
-    pub fn steal_right(
+    ///
+    /// If `C` is `marker::Counted`, the subtree lengths of both children are kept up to date.
+    pub fn steal_right<C: SubtreeLens>(
         mut self,
         track_left_edge_idx: usize,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
@@ -1498,12 +1756,29 @@ pub fn steal_right(
                 ForceResult::Leaf(mut leaf) => leaf.push(k, v),
                 ForceResult::Internal(mut internal) => internal.push(k, v, edge.unwrap()),
             }
+            if C::ENABLED {
+                self.recalc_children_subtree_lens();
+            }
 
             Handle::new_edge(self.left_child, track_left_edge_idx)
         }
     }
 
+    /// Recomputes the subtree lengths of both children after moving key/value pairs
+    /// and edges between them. The subtree length of the parent remains the same.
+    fn recalc_children_subtree_lens(&mut self) {
+        let left = unsafe { self.left_child.reborrow_mut() };
+        let right = unsafe { self.right_child.reborrow_mut() };
+        if let (ForceResult::Internal(mut left), ForceResult::Internal(mut right)) =
+            (left.force(), right.force())
+        {
+            left.recalc_subtree_len();
+            right.recalc_subtree_len();
+        }
+    }
+
     /// This does stealing similar to `steal_left` but steals multiple elements at once.
+    #[cfg(not(no_global_oom_handling))]
     pub fn bulk_steal_left(&mut self, count: usize) {
         unsafe {
             let left_node = &mut self.left_child;
@@ -1560,6 +1835,7 @@ pub fn bulk_steal_left(&mut self, count: usize) {
     }
 
     /// The symmetric clone of `bulk_steal_left`.
+    #[cfg(not(no_global_oom_handling))]
     pub fn bulk_steal_right(&mut self, count: usize) {
         unsafe {
             let left_node = &mut self.left_child;
@@ -1615,6 +1891,7 @@ pub fn bulk_steal_right(&mut self, count: usize) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 unsafe fn move_kv<K, V>(
     source: (*mut K, *mut V),
     source_offset: usize,
@@ -1629,6 +1906,7 @@ unsafe fn move_kv<K, V>(
 }
 
 // Source and destination must have the same height.
+#[cfg(not(no_global_oom_handling))]
 unsafe fn move_edges<'a, K: 'a, V: 'a>(
     source: NodeRef<marker::Immut<'a>, K, V, marker::Internal>,
     source_offset: usize,
@@ -1714,6 +1992,7 @@ pub fn force(
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
     /// Move the suffix after `self` from one node to another one. `right` must be empty.
     /// The first edge of `right` remains unchanged.
+    #[cfg(not(no_global_oom_handling))]
     pub fn move_suffix(
         &mut self,
         right: &mut NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
@@ -1786,6 +2065,24 @@ pub enum Owned {}
 
     pub enum KV {}
     pub enum Edge {}
+
+    pub enum Counted {}
+    pub enum Uncounted {}
+}
+
+/// Whether the operations on a tree keep the subtree lengths of its internal nodes
+/// up to date. Only a `RankedBTreeMap` needs them, so only its operations pay for
+/// the bookkeeping.
+pub trait SubtreeLens {
+    const ENABLED: bool;
+}
+
+impl SubtreeLens for marker::Counted {
+    const ENABLED: bool = true;
+}
+
+impl SubtreeLens for marker::Uncounted {
+    const ENABLED: bool = false;
 }
 
 /// Inserts a value into a slice of initialized elements followed by one uninitialized element.
@@ -17,6 +17,19 @@ pub fn assert_back_pointers(self) {
         }
     }
 
+    // Asserts that each reachable internal node keeps track of the number of
+    // elements in its subtree, and returns that number for the given node.
+    pub fn assert_subtree_lens(self) -> usize {
+        let mut len = self.len();
+        if let ForceResult::Internal(node) = self.force() {
+            for idx in 0..=node.len() {
+                len += unsafe { Handle::new_edge(node, idx) }.descend().assert_subtree_lens();
+            }
+        }
+        assert_eq!(self.subtree_len(), len);
+        len
+    }
+
     // Renders a multi-line display of the keys in order and in tree hierarchy,
     // picturing the tree growing sideways from its root on the left to its
     // leaves on the right.
@@ -75,8 +88,10 @@ fn test_splitpoint() {
 #[test]
 fn test_partial_cmp_eq() {
     let mut root1: Root<i32, ()> = Root::new_leaf();
-    let mut leaf1 = unsafe { root1.leaf_node_as_mut() };
-    leaf1.push(1, ());
+    match root1.node_as_mut().force() {
+        ForceResult::Leaf(mut leaf1) => leaf1.push(1, ()),
+        ForceResult::Internal(_) => unreachable!(),
+    }
     root1.push_internal_level();
     let root2: Root<i32, ()> = Root::new_leaf();
 
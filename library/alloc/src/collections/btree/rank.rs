@@ -0,0 +1,131 @@
+use core::borrow::Borrow;
+use core::ops::{Bound::*, Range, RangeBounds};
+
+use super::node::{marker, ForceResult::*, Handle, NodeRef};
+use super::search::{search_node, SearchResult::*};
+
+// Order statistics, answered in logarithmic time with the help of the subtree
+// length that every internal node of a `RankedBTreeMap` keeps track of.
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+    /// Counts the keys in the tree that are less than `key`. Like `binary_search`
+    /// on a sorted slice, returns `Ok` with that count if the tree contains
+    /// a matching key, and `Err` with that count otherwise.
+    pub fn rank<Q: ?Sized>(self, key: &Q) -> Result<usize, usize>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut rank = 0;
+        loop {
+            match search_node(node, key) {
+                Found(kv) => {
+                    let idx = kv.idx();
+                    rank += idx;
+                    if let Internal(kv) = kv.force() {
+                        rank += edges_subtree_len(kv.into_node(), 0..idx + 1);
+                    }
+                    return Ok(rank);
+                }
+                GoDown(edge) => {
+                    let idx = edge.idx();
+                    rank += idx;
+                    match edge.force() {
+                        Leaf(_) => return Err(rank),
+                        Internal(edge) => {
+                            rank += edges_subtree_len(edge.into_node(), 0..idx);
+                            node = edge.descend();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Finds the key/value pair that has exactly `n` smaller keys in the tree,
+    /// or returns `None` if the tree has no more than `n` elements.
+    pub fn select(
+        self,
+        mut n: usize,
+    ) -> Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>> {
+        if n >= self.subtree_len() {
+            return None;
+        }
+        let mut node = self;
+        loop {
+            let internal = match node.force() {
+                // The check only fails if the subtree lengths are stale,
+                // which a `RankedBTreeMap` never leaves behind.
+                Leaf(leaf) if n < leaf.len() => {
+                    return Some(unsafe { Handle::new_kv(leaf, n) }.forget_node_type());
+                }
+                Leaf(_) => return None,
+                Internal(internal) => internal,
+            };
+            let mut idx = 0;
+            node = loop {
+                let child = unsafe { Handle::new_edge(internal, idx) }.descend();
+                let child_len = child.subtree_len();
+                if n < child_len || idx == internal.len() {
+                    break child;
+                }
+                n -= child_len;
+                if n == 0 {
+                    return Some(unsafe { Handle::new_kv(internal, idx) }.forget_node_type());
+                }
+                n -= 1;
+                idx += 1;
+            };
+        }
+    }
+
+    /// Counts the keys in the tree that lie within `range`.
+    ///
+    /// Panics in the same cases as `range_search`, except that an ill-defined
+    /// `Ord` implementation may go unnoticed.
+    pub fn range_len<Q, R>(self, range: R) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        match (range.start_bound(), range.end_bound()) {
+            (Excluded(s), Excluded(e)) if s == e => {
+                panic!("range start and end are equal and excluded in BTreeMap")
+            }
+            (Included(s) | Excluded(s), Included(e) | Excluded(e)) if s > e => {
+                panic!("range start is greater than range end in BTreeMap")
+            }
+            _ => {}
+        }
+        let start = match range.start_bound() {
+            Included(key) => match self.rank(key) {
+                Ok(rank) | Err(rank) => rank,
+            },
+            Excluded(key) => match self.rank(key) {
+                Ok(rank) => rank + 1,
+                Err(rank) => rank,
+            },
+            Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Included(key) => match self.rank(key) {
+                Ok(rank) => rank + 1,
+                Err(rank) => rank,
+            },
+            Excluded(key) => match self.rank(key) {
+                Ok(rank) | Err(rank) => rank,
+            },
+            Unbounded => self.subtree_len(),
+        };
+        end.saturating_sub(start)
+    }
+}
+
+/// Sums the lengths of the subtrees below the given range of edges.
+fn edges_subtree_len<'a, K: 'a, V: 'a>(
+    node: NodeRef<marker::Immut<'a>, K, V, marker::Internal>,
+    edges: Range<usize>,
+) -> usize {
+    edges.map(|idx| unsafe { Handle::new_edge(node, idx) }.descend().subtree_len()).sum()
+}
@@ -0,0 +1,284 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+#[cfg(not(no_global_oom_handling))]
+use core::iter::FromIterator;
+use core::ops::RangeBounds;
+
+use super::super::map::RankedBTreeMap;
+use super::{BTreeSet, Iter, Range};
+
+/// A set based on a B-Tree that also answers order-statistic queries, like
+/// [`rank`] and [`nth`], in logarithmic time.
+///
+/// This is to [`RankedBTreeMap`] what [`BTreeSet`] is to [`BTreeMap`]: it
+/// keeps track of the number of elements below each internal node, which
+/// makes inserting and removing elements somewhat slower than in a
+/// [`BTreeSet`]. A `RankedBTreeSet` can hold at most `u32::MAX` elements.
+///
+/// [`rank`]: RankedBTreeSet::rank
+/// [`nth`]: RankedBTreeSet::nth
+/// [`BTreeMap`]: super::super::map::BTreeMap
+///
+/// # Examples
+///
+/// ```
+/// #![feature(btree_rank)]
+/// use std::collections::btree_set::RankedBTreeSet;
+///
+/// let mut scores = RankedBTreeSet::new();
+/// scores.insert(10);
+/// scores.insert(20);
+/// scores.insert(30);
+/// assert_eq!(scores.rank(&20), 1);
+/// assert_eq!(scores.nth(2), Some(&30));
+/// ```
+#[unstable(feature = "btree_rank", issue = "none")]
+pub struct RankedBTreeSet<T> {
+    map: RankedBTreeMap<T, ()>,
+}
+
+impl<T: Ord> RankedBTreeSet<T> {
+    /// Makes a new, empty `RankedBTreeSet`.
+    ///
+    /// Does not allocate anything on its own.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn new() -> RankedBTreeSet<T> {
+        RankedBTreeSet { map: RankedBTreeMap::new() }
+    }
+
+    /// Clears the set, removing all elements.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns `true` if the set contains a value.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the set,
+    /// like [`BTreeSet::range`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn range<K: ?Sized, R>(&self, range: R) -> Range<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K>,
+        R: RangeBounds<K>,
+    {
+        Range { iter: self.map.range(range) }
+    }
+
+    /// Adds a value to the set, like [`BTreeSet::insert`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set already holds `u32::MAX` elements and the value is new.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    /// Removes a value from the set. Returns whether the value was
+    /// present in the set.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    /// Returns the number of values in the set that are less than the given value.
+    /// This is the index at which the value is, or would be, in the sorted order of the set.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// This takes *O*(log(*n*)) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_rank)]
+    /// use std::collections::btree_set::RankedBTreeSet;
+    ///
+    /// let set: RankedBTreeSet<_> = [10, 20, 30].iter().cloned().collect();
+    /// assert_eq!(set.rank(&5), 0);
+    /// assert_eq!(set.rank(&20), 1);
+    /// assert_eq!(set.rank(&35), 3);
+    /// ```
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn rank<Q: ?Sized>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.rank(value)
+    }
+
+    /// Returns the number of values in a sub-range of the set, such that
+    /// `set.range_len(r) == set.range(r).count()`.
+    ///
+    /// This takes *O*(log(*n*)) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_rank)]
+    /// use std::collections::btree_set::RankedBTreeSet;
+    ///
+    /// let set: RankedBTreeSet<_> = [10, 20, 30].iter().cloned().collect();
+    /// assert_eq!(set.range_len(15..), 2);
+    /// assert_eq!(set.range_len(..=20), 2);
+    /// ```
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn range_len<K: ?Sized, R>(&self, range: R) -> usize
+    where
+        K: Ord,
+        T: Borrow<K>,
+        R: RangeBounds<K>,
+    {
+        self.map.range_len(range)
+    }
+}
+
+impl<T> RankedBTreeSet<T> {
+    /// Returns the number of elements in the set.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Converts the set into a plain `BTreeSet`, which no longer keeps track
+    /// of the subtree lengths.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn into_set(self) -> BTreeSet<T> {
+        BTreeSet { map: self.map.into_map() }
+    }
+
+    /// Gets an iterator that visits the values in the set in ascending order.
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { iter: self.map.as_map().keys() }
+    }
+
+    /// Returns the value at index `n` in the sorted order of the set,
+    /// i.e. the value with exactly `n` smaller values, or `None` if the set
+    /// has no more than `n` elements.
+    ///
+    /// This takes *O*(log(*n*)) time, unlike `iter().nth(n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_rank)]
+    /// use std::collections::btree_set::RankedBTreeSet;
+    ///
+    /// let set: RankedBTreeSet<_> = [10, 20, 30].iter().cloned().collect();
+    /// assert_eq!(set.nth(1), Some(&20));
+    /// assert_eq!(set.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_rank", issue = "none")]
+    pub fn nth(&self, n: usize) -> Option<&T> {
+        self.map.nth_key_value(n).map(|(k, _)| k)
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<T> From<BTreeSet<T>> for RankedBTreeSet<T> {
+    /// Converts a `BTreeSet` into a `RankedBTreeSet`, counting the elements
+    /// below each internal node in *O*(*n*) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set holds more than `u32::MAX` elements.
+    fn from(set: BTreeSet<T>) -> Self {
+        RankedBTreeSet { map: RankedBTreeMap::from(set.map) }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<T: Clone> Clone for RankedBTreeSet<T> {
+    fn clone(&self) -> Self {
+        RankedBTreeSet { map: self.map.clone() }
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<T: Ord> Default for RankedBTreeSet<T> {
+    /// Creates an empty `RankedBTreeSet<T>`.
+    fn default() -> Self {
+        RankedBTreeSet::new()
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<T: Debug> Debug for RankedBTreeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<T: Ord> FromIterator<T> for RankedBTreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = RankedBTreeSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<T: Ord> Extend<T> for RankedBTreeSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(move |elem| {
+            self.insert(elem);
+        });
+    }
+}
+
+#[unstable(feature = "btree_rank", issue = "none")]
+impl<'a, T> IntoIterator for &'a RankedBTreeSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
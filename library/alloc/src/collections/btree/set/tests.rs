@@ -741,3 +741,67 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_try_insert() {
+    let mut set = BTreeSet::new();
+    for i in 0..1000 {
+        assert_eq!(set.try_insert(i), Ok(true));
+        assert_eq!(set.try_insert(i), Ok(false));
+    }
+    assert!(set.iter().copied().eq(0..1000));
+}
+
+#[test]
+fn test_cursor() {
+    let mut set: BTreeSet<_> = (0..100).map(|i| i * 2).collect();
+    let cursor = set.lower_bound(Bound::Excluded(&10));
+    assert_eq!(cursor.get(), Some(&12));
+    assert_eq!(cursor.peek_prev(), Some(&10));
+    let cursor = set.upper_bound(Bound::Included(&11));
+    assert_eq!(cursor.get(), Some(&10));
+    assert_eq!(cursor.peek_next(), Some(&12));
+
+    let mut cursor = set.lower_bound_mut(Bound::Included(&10));
+    cursor.insert_after(11);
+    cursor.insert_before(9);
+    assert_eq!(cursor.get(), Some(&10));
+    assert_eq!(cursor.remove_current(), Some(10));
+    assert_eq!(cursor.get(), Some(&11));
+    assert_eq!(cursor.remove_current_and_move_back(), Some(11));
+    assert_eq!(cursor.get(), Some(&9));
+    assert_eq!(cursor.as_cursor().peek_prev(), Some(&8));
+    assert_eq!(set.len(), 100);
+    assert!(set.range(8..=12).eq(&[8, 9, 12]));
+}
+
+#[test]
+fn test_ranked_rank_and_nth() {
+    let mut set: RankedBTreeSet<_> = (0..100).map(|i| i * 2).collect();
+    assert_eq!(set.rank(&10), 5);
+    assert_eq!(set.rank(&11), 6);
+    assert_eq!(set.nth(5), Some(&10));
+    assert_eq!(set.nth(100), None);
+    assert_eq!(set.range_len(10..=20), 6);
+    assert_eq!(set.range_len(..), 100);
+
+    assert!(set.remove(&10));
+    assert!(!set.remove(&10));
+    assert!(!set.insert(20));
+    assert!(set.insert(11));
+    assert_eq!(set.len(), 100);
+    assert_eq!(set.rank(&12), 6);
+    assert_eq!(set.nth(5), Some(&11));
+    let plain = set.clone().into_set();
+    assert!(set.iter().eq(plain.iter()));
+}
+
+#[test]
+fn test_ranked_from_and_clone() {
+    let set = RankedBTreeSet::from((0..100).collect::<BTreeSet<_>>());
+    let clone = set.clone();
+    assert_eq!(clone.nth(42), Some(&42));
+    assert_eq!(clone.range_len(10..20), 10);
+    assert!(clone.range(10..20).eq(set.range(10..20)));
+    assert_eq!(format!("{:?}", RankedBTreeSet::from(BTreeSet::from_iter(0..3))), "{0, 1, 2}");
+}
@@ -1,5 +1,5 @@
 use super::map::MIN_LEN;
-use super::node::{ForceResult::*, Root};
+use super::node::{marker, ForceResult::*, Root};
 use super::search::{search_node, SearchResult::*};
 use core::borrow::Borrow;
 
@@ -63,7 +63,7 @@ fn fix_right_border(&mut self) {
                 let mut last_kv = node.last_kv().consider_for_balancing();
 
                 if last_kv.can_merge() {
-                    cur_node = last_kv.merge(None).into_node();
+                    cur_node = last_kv.merge::<marker::Uncounted>(None).into_node();
                 } else {
                     let right_len = last_kv.right_child_len();
                     // `MIN_LEN + 1` to avoid readjust if merge happens on the next level.
@@ -89,7 +89,7 @@ fn fix_left_border(&mut self) {
                 let mut first_kv = node.first_kv().consider_for_balancing();
 
                 if first_kv.can_merge() {
-                    cur_node = first_kv.merge(None).into_node();
+                    cur_node = first_kv.merge::<marker::Uncounted>(None).into_node();
                 } else {
                     let left_len = first_kv.left_child_len();
                     // `MIN_LEN + 1` to avoid readjust if merge happens on the next level.
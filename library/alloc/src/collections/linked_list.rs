@@ -15,11 +15,14 @@
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::iter::{FromIterator, FusedIterator};
+#[cfg(not(no_global_oom_handling))]
+use core::iter::FromIterator;
+use core::iter::FusedIterator;
 use core::marker::PhantomData;
 use core::mem;
 use core::ptr::NonNull;
 
+#[cfg(not(no_global_oom_handling))]
 use super::SpecExtend;
 use crate::boxed::Box;
 
@@ -117,6 +120,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 impl<T> Node<T> {
+    #[cfg(not(no_global_oom_handling))]
     fn new(element: T) -> Self {
         Node { next: None, prev: None, element }
     }
@@ -759,6 +763,7 @@ pub fn back_mut(&mut self) -> Option<&mut T> {
     /// dl.push_front(1);
     /// assert_eq!(dl.front().unwrap(), &1);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn push_front(&mut self, elt: T) {
         self.push_front_node(box Node::new(elt));
@@ -802,6 +807,7 @@ pub fn pop_front(&mut self) -> Option<T> {
     /// d.push_back(3);
     /// assert_eq!(3, *d.back().unwrap());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn push_back(&mut self, elt: T) {
         self.push_back_node(box Node::new(elt));
@@ -1104,6 +1110,7 @@ impl<T> IterMut<'_, T> {
     /// The inserted element does not appear in the iteration.
     ///
     /// This method will be removed soon.
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[unstable(
         feature = "linked_list_extras",
@@ -1427,6 +1434,7 @@ impl<'a, T> CursorMut<'a, T> {
     ///
     /// If the cursor is pointing at the "ghost" non-element then the new element is
     /// inserted at the front of the `LinkedList`.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "linked_list_cursors", issue = "58533")]
     pub fn insert_after(&mut self, item: T) {
         unsafe {
@@ -1447,6 +1455,7 @@ pub fn insert_after(&mut self, item: T) {
     ///
     /// If the cursor is pointing at the "ghost" non-element then the new element is
     /// inserted at the end of the `LinkedList`.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "linked_list_cursors", issue = "58533")]
     pub fn insert_before(&mut self, item: T) {
         unsafe {
@@ -1684,6 +1693,7 @@ impl<T> ExactSizeIterator for IntoIter<T> {}
 #[stable(feature = "fused", since = "1.26.0")]
 impl<T> FusedIterator for IntoIter<T> {}
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> FromIterator<T> for LinkedList<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
@@ -1725,6 +1735,7 @@ fn into_iter(self) -> IterMut<'a, T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Extend<T> for LinkedList<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
@@ -1737,18 +1748,21 @@ fn extend_one(&mut self, elem: T) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<I: IntoIterator> SpecExtend<I> for LinkedList<I::Item> {
     default fn spec_extend(&mut self, iter: I) {
         iter.into_iter().for_each(move |elt| self.push_back(elt));
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T> SpecExtend<LinkedList<T>> for LinkedList<T> {
     fn spec_extend(&mut self, ref mut other: LinkedList<T>) {
         self.append(other);
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, T: 'a + Copy> Extend<&'a T> for LinkedList<T> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
@@ -1790,6 +1804,7 @@ fn cmp(&self, other: &Self) -> Ordering {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Clone> Clone for LinkedList<T> {
     fn clone(&self) -> Self {
@@ -5,6 +5,7 @@
 pub mod binary_heap;
 mod btree;
 pub mod linked_list;
+mod sorted_vec;
 pub mod vec_deque;
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -25,6 +26,20 @@ pub mod btree_set {
 #[doc(no_inline)]
 pub use binary_heap::BinaryHeap;
 
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub mod sorted_vec_map {
+    //! An ordered map based on a sorted vector.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub use super::sorted_vec::map::*;
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub mod sorted_vec_set {
+    //! An ordered set based on a sorted vector.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub use super::sorted_vec::set::*;
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use btree_map::BTreeMap;
@@ -37,6 +52,14 @@ pub mod btree_set {
 #[doc(no_inline)]
 pub use linked_list::LinkedList;
 
+#[unstable(feature = "sorted_vec", issue = "none")]
+#[doc(no_inline)]
+pub use sorted_vec_map::SortedVecMap;
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+#[doc(no_inline)]
+pub use sorted_vec_set::SortedVecSet;
+
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use vec_deque::VecDeque;
@@ -96,6 +119,7 @@ fn fmt(
 }
 
 /// An intermediate trait for specialization of `Extend`.
+#[cfg(not(no_global_oom_handling))]
 #[doc(hidden)]
 trait SpecExtend<I: IntoIterator> {
     /// Extends `self` with the contents of the given iterator.
@@ -0,0 +1,790 @@
+//! An ordered map based on a sorted vector.
+
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::mem;
+use core::ops::{Index, RangeBounds};
+use core::slice;
+
+use super::{assert_strictly_ascending, dedup_sorted, range_indices, search, sort_and_dedup};
+use crate::vec::{self, Vec};
+
+/// An ordered map based on a vector of key-value pairs sorted by key.
+///
+/// Compared to [`BTreeMap`], lookups are binary searches over contiguous memory
+/// and iteration is as cheap as iterating over a slice, while insertions and
+/// removals take *O*(*n*) time because they shift the elements after them.
+/// This makes `SortedVecMap` a good fit for small or read-mostly maps, and for
+/// maps built in one go with [`FromIterator`] or [`SortedVecMap::from_sorted_iter`].
+///
+/// It is a logic error for a key to be modified in such a way that the key's
+/// ordering relative to any other key, as determined by the [`Ord`] trait,
+/// changes while it is in the map. This is normally only possible through
+/// [`Cell`], [`RefCell`], global state, I/O, or unsafe code. The behavior
+/// resulting from such a logic error is not specified, but will not result
+/// in undefined behavior.
+///
+/// [`BTreeMap`]: crate::collections::BTreeMap
+/// [`Cell`]: core::cell::Cell
+/// [`RefCell`]: core::cell::RefCell
+///
+/// # Examples
+///
+/// ```
+/// #![feature(sorted_vec)]
+/// use std::collections::SortedVecMap;
+///
+/// let mut ratings: SortedVecMap<_, _> =
+///     vec![("Zoë", 4), ("Ann", 5), ("Bea", 3)].into_iter().collect();
+/// ratings.insert("Cid", 2);
+///
+/// assert_eq!(ratings.get("Bea"), Some(&3));
+/// assert!(ratings.keys().eq(["Ann", "Bea", "Cid", "Zoë"].iter()));
+/// assert_eq!(ratings.range("B".."D").count(), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct SortedVecMap<K, V> {
+    data: Vec<(K, V)>,
+}
+
+/// An iterator over the entries of a `SortedVecMap`.
+///
+/// This `struct` is created by the [`iter`] method on [`SortedVecMap`]. See its
+/// documentation for more.
+///
+/// [`iter`]: SortedVecMap::iter
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct Iter<'a, K: 'a, V: 'a> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+/// A mutable iterator over the entries of a `SortedVecMap`.
+///
+/// This `struct` is created by the [`iter_mut`] method on [`SortedVecMap`]. See its
+/// documentation for more.
+///
+/// [`iter_mut`]: SortedVecMap::iter_mut
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct IterMut<'a, K: 'a, V: 'a> {
+    iter: slice::IterMut<'a, (K, V)>,
+}
+
+/// An owning iterator over the entries of a `SortedVecMap`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`SortedVecMap`]
+/// (provided by the `IntoIterator` trait). See its documentation for more.
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct IntoIter<K, V> {
+    iter: vec::IntoIter<(K, V)>,
+}
+
+/// An iterator over the keys of a `SortedVecMap`.
+///
+/// This `struct` is created by the [`keys`] method on [`SortedVecMap`]. See its
+/// documentation for more.
+///
+/// [`keys`]: SortedVecMap::keys
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct Keys<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+/// An iterator over the values of a `SortedVecMap`.
+///
+/// This `struct` is created by the [`values`] method on [`SortedVecMap`]. See its
+/// documentation for more.
+///
+/// [`values`]: SortedVecMap::values
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct Values<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+fn key<K, V>(entry: &(K, V)) -> &K {
+    &entry.0
+}
+
+impl<K, V> SortedVecMap<K, V> {
+    /// Makes a new, empty `SortedVecMap`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut map = SortedVecMap::new();
+    /// map.insert(1, "a");
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub const fn new() -> SortedVecMap<K, V> {
+        SortedVecMap { data: Vec::new() }
+    }
+
+    /// Makes a new, empty `SortedVecMap` with room for at least `capacity` entries.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> SortedVecMap<K, V> {
+        SortedVecMap { data: Vec::with_capacity(capacity) }
+    }
+
+    /// Makes a `SortedVecMap` from entries that are already sorted by key,
+    /// in *O*(*n*) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are not in strictly ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let map = SortedVecMap::from_sorted_iter((1..4).map(|i| (i, i * 10)));
+    /// assert_eq!(map.get(&2), Some(&20));
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn from_sorted_iter<I>(iter: I) -> SortedVecMap<K, V>
+    where
+        K: Ord,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let data: Vec<(K, V)> = iter.into_iter().collect();
+        assert_strictly_ascending(&data, key);
+        SortedVecMap { data }
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut map = SortedVecMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn get_key_value<Q: ?Sized>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let idx = search(&self.data, key, k).ok()?;
+        let (k, v) = &self.data[idx];
+        Some((k, v))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut map = SortedVecMap::new();
+    /// map.insert(1, "a");
+    /// if let Some(x) = map.get_mut(&1) {
+    ///     *x = "b";
+    /// }
+    /// assert_eq!(map[&1], "b");
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let idx = search(&self.data, key, k).ok()?;
+        Some(&mut self.data[idx].1)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        search(&self.data, key, k).is_ok()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated, though.
+    ///
+    /// This takes *O*(*n*) time in the worst case, because the entries after
+    /// the new one are shifted over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut map = SortedVecMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.insert(37, "b"), Some("a"));
+    /// assert_eq!(map[&37], "b");
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn insert(&mut self, k: K, value: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        match search(&self.data, key, &k) {
+            Ok(idx) => Some(mem::replace(&mut self.data[idx].1, value)),
+            Err(idx) => {
+                self.data.insert(idx, (k, value));
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut map = SortedVecMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn remove_entry<Q: ?Sized>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let idx = search(&self.data, key, k).ok()?;
+        Some(self.data.remove(idx))
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut map: SortedVecMap<i32, i32> = (0..8).map(|x| (x, x * 10)).collect();
+    /// // Keep only the elements with even-numbered keys.
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.data.drain_filter(|(k, v)| !f(k, v));
+    }
+
+    /// Moves all elements from `other` into `Self`, leaving `other` empty.
+    ///
+    /// If a key from `other` is already present in `self`, the respective
+    /// value from `self` will be overwritten with the respective value from `other`.
+    ///
+    /// This merges the two maps in *O*(*n* + *m*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let mut a: SortedVecMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let mut b: SortedVecMap<_, _> = vec![(3, "d"), (4, "e")].into_iter().collect();
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.len(), 4);
+    /// assert_eq!(b.len(), 0);
+    /// assert_eq!(a[&3], "d");
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn append(&mut self, other: &mut Self)
+    where
+        K: Ord,
+    {
+        self.data.merge_sorted_by(&mut other.data, |a, b| a.0.cmp(&b.0));
+        dedup_sorted(&mut self.data, key, |later, first| mem::swap(&mut later.1, &mut first.1));
+    }
+
+    /// Clears the map, removing all elements.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    /// Returns the first key-value pair in the map.
+    /// The key in this pair is the minimum key in the map.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.data.first().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the last key-value pair in the map.
+    /// The key in this pair is the maximum key in the map.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.data.last().map(|(k, v)| (k, v))
+    }
+
+    /// Removes and returns the first element in the map.
+    /// The key of this element is the minimum key that was in the map.
+    ///
+    /// Unlike [`pop_last`], this shifts all other elements over.
+    ///
+    /// [`pop_last`]: SortedVecMap::pop_last
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.data.is_empty() { None } else { Some(self.data.remove(0)) }
+    }
+
+    /// Removes and returns the last element in the map.
+    /// The key of this element is the maximum key that was in the map.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.data.pop()
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    /// use std::ops::Bound::Included;
+    ///
+    /// let map: SortedVecMap<_, _> = vec![(3, "a"), (5, "b"), (8, "c")].into_iter().collect();
+    /// assert!(map.range((Included(&4), Included(&8))).eq(vec![(&5, &"b"), (&8, &"c")]));
+    /// assert_eq!(Some((&5, &"b")), map.range(4..).next());
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn range<T: ?Sized, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        T: Ord,
+        K: Borrow<T>,
+        R: RangeBounds<T>,
+    {
+        let range = range_indices(&self.data, key, range);
+        Iter { iter: self.data[range].iter() }
+    }
+
+    /// Constructs a mutable double-ended iterator over a sub-range of elements in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn range_mut<T: ?Sized, R>(&mut self, range: R) -> IterMut<'_, K, V>
+    where
+        T: Ord,
+        K: Borrow<T>,
+        R: RangeBounds<T>,
+    {
+        let range = range_indices(&self.data, key, range);
+        IterMut { iter: self.data[range].iter_mut() }
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { iter: self.data.iter() }
+    }
+
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut { iter: self.data.iter_mut() }
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// Returns the entries of the map as a slice, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecMap;
+    ///
+    /// let map: SortedVecMap<_, _> = vec![(2, 'b'), (1, 'a')].into_iter().collect();
+    /// assert_eq!(map.as_slice(), [(1, 'a'), (2, 'b')]);
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn as_slice(&self) -> &[(K, V)] {
+        &self.data
+    }
+
+    /// Converts the map into a vector of its entries, sorted by key.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn into_vec(self) -> Vec<(K, V)> {
+        self.data
+    }
+
+    /// Returns the number of elements in the map.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> Default for SortedVecMap<K, V> {
+    /// Creates an empty `SortedVecMap<K, V>`.
+    fn default() -> SortedVecMap<K, V> {
+        SortedVecMap::new()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Debug, V: Debug> Debug for SortedVecMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Hash, V: Hash> Hash for SortedVecMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for elt in self {
+            elt.hash(state);
+        }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, Q: ?Sized, V> Index<&Q> for SortedVecMap<K, V>
+where
+    K: Borrow<Q>,
+    Q: Ord,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the `SortedVecMap`.
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Ord, V> FromIterator<(K, V)> for SortedVecMap<K, V> {
+    /// Collects the entries and sorts them in *O*(*n* \* log(*n*)) time. Like
+    /// repeated calls to `insert`, the last value given for a key wins.
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> SortedVecMap<K, V> {
+        let mut data: Vec<(K, V)> = iter.into_iter().collect();
+        sort_and_dedup(&mut data, key, |later, first| mem::swap(&mut later.1, &mut first.1));
+        SortedVecMap { data }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Ord, V> Extend<(K, V)> for SortedVecMap<K, V> {
+    /// Sorts the new entries and merges them in, rather than inserting them
+    /// one at a time.
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        let mut other: SortedVecMap<K, V> = iter.into_iter().collect();
+        self.append(&mut other);
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K: Ord + Copy, V: Copy> Extend<(&'a K, &'a V)> for SortedVecMap<K, V> {
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> IntoIterator for SortedVecMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { iter: self.data.into_iter() }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> IntoIterator for &'a SortedVecMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> IntoIterator for &'a mut SortedVecMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone() }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        self.iter.next().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a mut V)> {
+        self.iter.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<(K, V)> {
+        self.iter.next_back()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Debug, V: Debug> Debug for IntoIter<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter.as_slice()).finish()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> Clone for Keys<'_, K, V> {
+    fn clone(&self) -> Self {
+        Keys { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K: Debug, V> Debug for Keys<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V> Clone for Values<'_, K, V> {
+    fn clone(&self) -> Self {
+        Values { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<K, V: Debug> Debug for Values<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
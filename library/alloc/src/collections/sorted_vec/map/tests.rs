@@ -0,0 +1,158 @@
+use super::*;
+use crate::boxed::Box;
+use std::cmp::Ordering;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::ops::RangeBounds;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+
+#[test]
+fn test_basic() {
+    let mut map = SortedVecMap::new();
+    for i in (0..100).rev() {
+        assert_eq!(map.insert(i * 2, i), None);
+    }
+    assert_eq!(map.len(), 100);
+    assert!(map.keys().copied().eq((0..100).map(|i| i * 2)));
+    assert_eq!(map.insert(10, 50), Some(5));
+    assert_eq!(map.get(&10), Some(&50));
+    assert_eq!(map.get(&11), None);
+    assert_eq!(map.get_key_value(&12), Some((&12, &6)));
+    *map.get_mut(&12).unwrap() = 60;
+    assert_eq!(map[&12], 60);
+    assert!(map.contains_key(&0));
+    assert_eq!(map.remove(&0), Some(0));
+    assert_eq!(map.remove(&0), None);
+    assert_eq!(map.remove_entry(&198), Some((198, 99)));
+    assert_eq!(map.first_key_value(), Some((&2, &1)));
+    assert_eq!(map.last_key_value(), Some((&196, &98)));
+    assert_eq!(map.pop_first(), Some((2, 1)));
+    assert_eq!(map.pop_last(), Some((196, 98)));
+    assert_eq!(map.len(), 96);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.pop_first(), None);
+}
+
+#[test]
+fn test_from_iter_last_value_wins() {
+    let map: SortedVecMap<_, _> = [(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')]
+        .iter()
+        .copied()
+        .collect();
+    assert!(map.iter().eq([(&1, &'e'), (&2, &'d'), (&3, &'c')].iter().copied()));
+}
+
+#[test]
+fn test_from_sorted_iter() {
+    let map = SortedVecMap::from_sorted_iter((0..10).map(|i| (i, i * i)));
+    assert_eq!(map[&3], 9);
+    assert!(map.values().copied().eq((0..10).map(|i| i * i)));
+}
+
+#[test]
+#[should_panic(expected = "keys are not in strictly ascending order")]
+fn test_from_sorted_iter_unsorted() {
+    SortedVecMap::from_sorted_iter([(1, ()), (1, ())].iter().copied());
+}
+
+#[test]
+fn test_range() {
+    let map: SortedVecMap<_, _> = (0..20).map(|i| (i * 2, i)).collect();
+    for i in 0..41 {
+        for j in i..41 {
+            for &start in [Included(&i), Excluded(&i), Unbounded].iter() {
+                for &end in [Included(&j), Excluded(&j), Unbounded].iter() {
+                    if i == j && matches!((start, end), (Excluded(_), Excluded(_))) {
+                        continue;
+                    }
+                    let expected = map.iter().filter(|(k, _)| (start, end).contains(*k));
+                    assert!(map.range((start, end)).eq(expected));
+                }
+            }
+        }
+    }
+    for (_, v) in map.range_mut(10..20) {
+        *v += 100;
+    }
+    let expected = (0..20).map(|i| if (5..10).contains(&i) { i + 100 } else { i });
+    assert!(map.values().copied().eq(expected));
+}
+
+#[test]
+#[should_panic(expected = "range start is greater than range end in SortedVecMap")]
+fn test_range_backwards() {
+    let map: SortedVecMap<_, _> = (0..10).map(|i| (i, ())).collect();
+    map.range(5..4);
+}
+
+#[test]
+#[should_panic(expected = "range start and end are equal and excluded in SortedVecMap")]
+fn test_range_equal_excluded() {
+    let map: SortedVecMap<_, _> = (0..10).map(|i| (i, ())).collect();
+    map.range((Excluded(5), Excluded(5)));
+}
+
+#[test]
+fn test_append_and_extend() {
+    let mut a: SortedVecMap<_, _> = (0..10).map(|i| (i * 2, 'a')).collect();
+    let mut b: SortedVecMap<_, _> = (0..10).map(|i| (i * 3, 'b')).collect();
+    a.append(&mut b);
+    assert!(b.is_empty());
+    assert_eq!(a.len(), 17);
+    assert!(a.keys().zip(a.keys().skip(1)).all(|(x, y)| x < y));
+    assert_eq!(a[&6], 'b');
+    assert_eq!(a[&4], 'a');
+
+    a.extend([(5, 'c'), (4, 'c'), (100, 'c')].iter().copied());
+    assert_eq!(a.len(), 19);
+    assert_eq!(a[&4], 'c');
+    assert_eq!(a.last_key_value(), Some((&100, &'c')));
+}
+
+#[test]
+fn test_retain() {
+    let mut map: SortedVecMap<i32, i32> = (0..100).map(|x| (x, x * 10)).collect();
+    map.retain(|&k, v| {
+        *v += 1;
+        k % 2 == 0
+    });
+    assert_eq!(map.len(), 50);
+    assert_eq!(map[&2], 21);
+    assert_eq!(map.get(&3), None);
+}
+
+#[test]
+fn test_into_iter_drops() {
+    let map: SortedVecMap<_, _> = (0..10).map(|i| (i, Box::new(i))).collect();
+    let mut iter = map.into_iter();
+    assert_eq!(iter.len(), 10);
+    assert_eq!(iter.next_back(), Some((9, Box::new(9))));
+    assert_eq!(iter.next(), Some((0, Box::new(0))));
+}
+
+#[test]
+fn test_append_panicking_ord() {
+    static ARMED: AtomicBool = AtomicBool::new(false);
+
+    #[derive(PartialEq, Eq, PartialOrd)]
+    struct Bomb(i32);
+
+    impl Ord for Bomb {
+        fn cmp(&self, other: &Bomb) -> Ordering {
+            if ARMED.load(SeqCst) && (self.0 == 7 || other.0 == 7) {
+                panic!("boom");
+            }
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut map: SortedVecMap<_, _> = (0..10).map(|i| (Bomb(i * 2), Box::new(i))).collect();
+    let mut other: SortedVecMap<_, _> = (0..10).map(|i| (Bomb(i * 3 + 1), Box::new(i))).collect();
+    ARMED.store(true, SeqCst);
+    catch_unwind(AssertUnwindSafe(|| map.append(&mut other))).unwrap_err();
+    ARMED.store(false, SeqCst);
+    // Every entry ended up in `map`, and is dropped exactly once along with it.
+    assert_eq!(map.len(), 20);
+    assert!(other.is_empty());
+}
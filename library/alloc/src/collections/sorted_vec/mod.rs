@@ -0,0 +1,92 @@
+use core::borrow::Borrow;
+use core::ops::{Bound::*, Range, RangeBounds};
+
+use crate::vec::Vec;
+
+pub mod map;
+pub mod set;
+
+/// Looks up `target` in a slice sorted by `key`, like `binary_search`.
+fn search<T, K, Q: ?Sized>(data: &[T], key: impl Fn(&T) -> &K, target: &Q) -> Result<usize, usize>
+where
+    K: Borrow<Q>,
+    Q: Ord,
+{
+    data.binary_search_by(|elem| key(elem).borrow().cmp(target))
+}
+
+/// Finds the indices of the elements of a slice sorted by `key` that lie within `range`.
+fn range_indices<T, K, Q: ?Sized, R>(data: &[T], key: impl Fn(&T) -> &K, range: R) -> Range<usize>
+where
+    K: Borrow<Q>,
+    Q: Ord,
+    R: RangeBounds<Q>,
+{
+    match (range.start_bound(), range.end_bound()) {
+        (Excluded(s), Excluded(e)) if s == e => {
+            panic!("range start and end are equal and excluded in SortedVecMap")
+        }
+        (Included(s) | Excluded(s), Included(e) | Excluded(e)) if s > e => {
+            panic!("range start is greater than range end in SortedVecMap")
+        }
+        _ => {}
+    }
+    let start = match range.start_bound() {
+        Included(s) => match search(data, &key, s) {
+            Ok(idx) | Err(idx) => idx,
+        },
+        Excluded(s) => match search(data, &key, s) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        },
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(e) => match search(data, &key, e) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        },
+        Excluded(e) => match search(data, &key, e) {
+            Ok(idx) | Err(idx) => idx,
+        },
+        Unbounded => data.len(),
+    };
+    start..end
+}
+
+/// Sorts the elements by `key` and removes all but one of each run of equal keys.
+/// Of each run, the first element stays in place and `merge` folds every later
+/// element into it, before the later one is dropped.
+#[cfg(not(no_global_oom_handling))]
+fn sort_and_dedup<T, K: Ord>(
+    data: &mut Vec<T>,
+    key: impl Fn(&T) -> &K,
+    merge: impl FnMut(&mut T, &mut T),
+) {
+    data.sort_by(|a, b| key(a).cmp(key(b)));
+    dedup_sorted(data, key, merge);
+}
+
+/// Like `sort_and_dedup`, for elements that are already sorted by `key`.
+#[cfg(not(no_global_oom_handling))]
+fn dedup_sorted<T, K: Ord>(
+    data: &mut Vec<T>,
+    key: impl Fn(&T) -> &K,
+    mut merge: impl FnMut(&mut T, &mut T),
+) {
+    data.dedup_by(|later, first| {
+        let equal = key(later) == key(first);
+        if equal {
+            merge(later, first);
+        }
+        equal
+    });
+}
+
+/// Asserts that the keys produced by `from_sorted_iter` are strictly ascending.
+#[cfg(not(no_global_oom_handling))]
+fn assert_strictly_ascending<T, K: Ord>(data: &[T], key: impl Fn(&T) -> &K) {
+    for i in 1..data.len() {
+        assert!(key(&data[i - 1]) < key(&data[i]), "keys are not in strictly ascending order");
+    }
+}
@@ -0,0 +1,749 @@
+//! An ordered set based on a sorted vector.
+
+use core::borrow::Borrow;
+use core::cmp::Ordering::{Equal, Greater, Less};
+use core::fmt::{self, Debug};
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::{BitAnd, BitOr, BitXor, RangeBounds, Sub};
+use core::slice;
+
+use super::{assert_strictly_ascending, dedup_sorted, range_indices, search, sort_and_dedup};
+use crate::vec::{self, Vec};
+
+/// An ordered set based on a sorted vector.
+///
+/// See [`SortedVecMap`]'s documentation for a discussion of the trade-offs
+/// compared to [`BTreeSet`]. On top of those, the values of a `SortedVecSet`
+/// can be borrowed as a sorted slice, see [`as_slice`] and [`range`].
+///
+/// It is a logic error for an item to be modified in such a way that the item's
+/// ordering relative to any other item, as determined by the [`Ord`] trait,
+/// changes while it is in the set. This is normally only possible through
+/// [`Cell`], [`RefCell`], global state, I/O, or unsafe code. The behavior
+/// resulting from such a logic error is not specified, but will not result
+/// in undefined behavior.
+///
+/// [`SortedVecMap`]: crate::collections::SortedVecMap
+/// [`BTreeSet`]: crate::collections::BTreeSet
+/// [`as_slice`]: SortedVecSet::as_slice
+/// [`range`]: SortedVecSet::range
+/// [`Cell`]: core::cell::Cell
+/// [`RefCell`]: core::cell::RefCell
+///
+/// # Examples
+///
+/// ```
+/// #![feature(sorted_vec)]
+/// use std::collections::SortedVecSet;
+///
+/// let mut books: SortedVecSet<_> =
+///     vec!["Odyssey", "Emma", "Dracula"].into_iter().collect();
+/// books.insert("Beloved");
+///
+/// assert!(books.contains("Emma"));
+/// assert_eq!(books.as_slice(), ["Beloved", "Dracula", "Emma", "Odyssey"]);
+/// assert_eq!(books.range("C".."F"), ["Dracula", "Emma"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct SortedVecSet<T> {
+    data: Vec<T>,
+}
+
+/// An iterator over the items of a `SortedVecSet`.
+///
+/// This `struct` is created by the [`iter`] method on [`SortedVecSet`]. See its
+/// documentation for more.
+///
+/// [`iter`]: SortedVecSet::iter
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct Iter<'a, T: 'a> {
+    iter: slice::Iter<'a, T>,
+}
+
+/// An owning iterator over the items of a `SortedVecSet`.
+///
+/// This `struct` is created by the [`into_iter`] method on [`SortedVecSet`]
+/// (provided by the `IntoIterator` trait). See its documentation for more.
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub struct IntoIter<T> {
+    iter: vec::IntoIter<T>,
+}
+
+fn identity<T>(value: &T) -> &T {
+    value
+}
+
+impl<T> SortedVecSet<T> {
+    /// Makes a new, empty `SortedVecSet`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let mut set: SortedVecSet<i32> = SortedVecSet::new();
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub const fn new() -> SortedVecSet<T> {
+        SortedVecSet { data: Vec::new() }
+    }
+
+    /// Makes a new, empty `SortedVecSet` with room for at least `capacity` values.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> SortedVecSet<T> {
+        SortedVecSet { data: Vec::with_capacity(capacity) }
+    }
+
+    /// Makes a `SortedVecSet` from values that are already sorted, in *O*(*n*) time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values are not in strictly ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let set = SortedVecSet::from_sorted_iter(1..4);
+    /// assert_eq!(set.as_slice(), [1, 2, 3]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn from_sorted_iter<I>(iter: I) -> SortedVecSet<T>
+    where
+        T: Ord,
+        I: IntoIterator<Item = T>,
+    {
+        let data: Vec<T> = iter.into_iter().collect();
+        assert_strictly_ascending(&data, identity);
+        SortedVecSet { data }
+    }
+
+    /// Returns `true` if the set contains a value.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let set: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// assert_eq!(set.contains(&1), true);
+    /// assert_eq!(set.contains(&4), false);
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        search(&self.data, identity, value).is_ok()
+    }
+
+    /// Returns a reference to the value in the set, if any, that is equal to the given value.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn get<Q: ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        let idx = search(&self.data, identity, value).ok()?;
+        Some(&self.data[idx])
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// If the set did not have this value present, `true` is returned.
+    ///
+    /// If the set did have this value present, `false` is returned, and the
+    /// entry is not updated.
+    ///
+    /// This takes *O*(*n*) time in the worst case, because the values after
+    /// the new one are shifted over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let mut set = SortedVecSet::new();
+    /// assert_eq!(set.insert(2), true);
+    /// assert_eq!(set.insert(2), false);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn insert(&mut self, value: T) -> bool
+    where
+        T: Ord,
+    {
+        match search(&self.data, identity, &value) {
+            Ok(_) => false,
+            Err(idx) => {
+                self.data.insert(idx, value);
+                true
+            }
+        }
+    }
+
+    /// Removes a value from the set. Returns whether the value was
+    /// present in the set.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Removes and returns the value in the set, if any, that is equal to the given one.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn take<Q: ?Sized>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        let idx = search(&self.data, identity, value).ok()?;
+        Some(self.data.remove(idx))
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.data.retain(f);
+    }
+
+    /// Moves all elements from `other` into `Self`, leaving `other` empty.
+    ///
+    /// This merges the two sets in *O*(*n* + *m*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let mut a: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let mut b: SortedVecSet<_> = vec![3, 4, 5].into_iter().collect();
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.as_slice(), [1, 2, 3, 4, 5]);
+    /// assert!(b.is_empty());
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn append(&mut self, other: &mut Self)
+    where
+        T: Ord,
+    {
+        self.data.merge_sorted(&mut other.data);
+        dedup_sorted(&mut self.data, identity, |_, _| {});
+    }
+
+    /// Returns the union of `self` and `other`: the values in either set.
+    ///
+    /// Unlike `BTreeSet::union`, this builds a new set, in *O*(*n* + *m*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let a: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: SortedVecSet<_> = vec![2, 3, 4].into_iter().collect();
+    /// assert_eq!(a.union(&b).as_slice(), [1, 2, 3, 4]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn union(&self, other: &SortedVecSet<T>) -> SortedVecSet<T>
+    where
+        T: Ord + Clone,
+    {
+        self.merge(other, true, true, true)
+    }
+
+    /// Returns the intersection of `self` and `other`: the values in both sets.
+    ///
+    /// Unlike `BTreeSet::intersection`, this builds a new set, in *O*(*n* + *m*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let a: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: SortedVecSet<_> = vec![2, 3, 4].into_iter().collect();
+    /// assert_eq!(a.intersection(&b).as_slice(), [2, 3]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn intersection(&self, other: &SortedVecSet<T>) -> SortedVecSet<T>
+    where
+        T: Ord + Clone,
+    {
+        self.merge(other, false, true, false)
+    }
+
+    /// Returns the difference of `self` and `other`: the values in `self`
+    /// but not in `other`.
+    ///
+    /// Unlike `BTreeSet::difference`, this builds a new set, in *O*(*n* + *m*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let a: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: SortedVecSet<_> = vec![2, 3, 4].into_iter().collect();
+    /// assert_eq!(a.difference(&b).as_slice(), [1]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn difference(&self, other: &SortedVecSet<T>) -> SortedVecSet<T>
+    where
+        T: Ord + Clone,
+    {
+        self.merge(other, true, false, false)
+    }
+
+    /// Returns the symmetric difference of `self` and `other`: the values
+    /// in either set, but not in both.
+    ///
+    /// Unlike `BTreeSet::symmetric_difference`, this builds a new set,
+    /// in *O*(*n* + *m*) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let a: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let b: SortedVecSet<_> = vec![2, 3, 4].into_iter().collect();
+    /// assert_eq!(a.symmetric_difference(&b).as_slice(), [1, 4]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn symmetric_difference(&self, other: &SortedVecSet<T>) -> SortedVecSet<T>
+    where
+        T: Ord + Clone,
+    {
+        self.merge(other, true, false, true)
+    }
+
+    /// Walks both sets in order, keeping clones of the values that are only in
+    /// `self`, in both sets, or only in `other`, as requested.
+    #[cfg(not(no_global_oom_handling))]
+    fn merge(
+        &self,
+        other: &SortedVecSet<T>,
+        keep_left: bool,
+        keep_both: bool,
+        keep_right: bool,
+    ) -> SortedVecSet<T>
+    where
+        T: Ord + Clone,
+    {
+        let mut data = Vec::new();
+        let (mut left, mut right) = (&self.data[..], &other.data[..]);
+        while let (Some(l), Some(r)) = (left.first(), right.first()) {
+            match l.cmp(r) {
+                Less => {
+                    if keep_left {
+                        data.push(l.clone());
+                    }
+                    left = &left[1..];
+                }
+                Equal => {
+                    if keep_both {
+                        data.push(l.clone());
+                    }
+                    left = &left[1..];
+                    right = &right[1..];
+                }
+                Greater => {
+                    if keep_right {
+                        data.push(r.clone());
+                    }
+                    right = &right[1..];
+                }
+            }
+        }
+        if keep_left {
+            data.extend_from_slice(left);
+        }
+        if keep_right {
+            data.extend_from_slice(right);
+        }
+        SortedVecSet { data }
+    }
+
+    /// Returns `true` if `self` has no elements in common with `other`.
+    /// This is equivalent to checking for an empty intersection.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn is_disjoint(&self, other: &SortedVecSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        let (mut left, mut right) = (&self.data[..], &other.data[..]);
+        while let (Some(l), Some(r)) = (left.first(), right.first()) {
+            match l.cmp(r) {
+                Less => left = &left[1..],
+                Equal => return false,
+                Greater => right = &right[1..],
+            }
+        }
+        true
+    }
+
+    /// Returns `true` if the set is a subset of another,
+    /// i.e., `other` contains at least all the values in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let sup: SortedVecSet<_> = vec![1, 2, 3].into_iter().collect();
+    /// let set: SortedVecSet<_> = vec![1, 3].into_iter().collect();
+    /// assert!(set.is_subset(&sup));
+    /// assert!(!sup.is_subset(&set));
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn is_subset(&self, other: &SortedVecSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        if self.len() > other.len() {
+            return false;
+        }
+        let mut right = &other.data[..];
+        for l in &self.data {
+            loop {
+                match right.first().map(|r| l.cmp(r)) {
+                    None | Some(Less) => return false,
+                    Some(Equal) => break,
+                    Some(Greater) => right = &right[1..],
+                }
+            }
+            right = &right[1..];
+        }
+        true
+    }
+
+    /// Returns `true` if the set is a superset of another,
+    /// i.e., `self` contains at least all the values in `other`.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn is_superset(&self, other: &SortedVecSet<T>) -> bool
+    where
+        T: Ord,
+    {
+        other.is_subset(self)
+    }
+
+    /// Clears the set, removing all values.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    /// Returns a reference to the first value in the set, if any.
+    /// This value is always the minimum of all values in the set.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn first(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a reference to the last value in the set, if any.
+    /// This value is always the maximum of all values in the set.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn last(&self) -> Option<&T> {
+        self.data.last()
+    }
+
+    /// Removes the first value from the set and returns it, if any.
+    /// The first value is always the minimum value in the set.
+    ///
+    /// Unlike [`pop_last`], this shifts all other values over.
+    ///
+    /// [`pop_last`]: SortedVecSet::pop_last
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn pop_first(&mut self) -> Option<T> {
+        if self.data.is_empty() { None } else { Some(self.data.remove(0)) }
+    }
+
+    /// Removes the last value from the set and returns it, if any.
+    /// The last value is always the maximum value in the set.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    /// Returns the values of the set within the given range, as a sorted slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(sorted_vec)]
+    /// use std::collections::SortedVecSet;
+    ///
+    /// let set: SortedVecSet<_> = vec![3, 5, 8].into_iter().collect();
+    /// assert_eq!(set.range(4..), [5, 8]);
+    /// assert_eq!(set.range(..=5), [3, 5]);
+    /// ```
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn range<K: ?Sized, R>(&self, range: R) -> &[T]
+    where
+        K: Ord,
+        T: Borrow<K>,
+        R: RangeBounds<K>,
+    {
+        &self.data[range_indices(&self.data, identity, range)]
+    }
+
+    /// Gets an iterator that visits the values in the set in ascending order.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { iter: self.data.iter() }
+    }
+
+    /// Returns the values of the set as a sorted slice.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Converts the set into a sorted vector of its values.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the number of elements in the set.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[unstable(feature = "sorted_vec", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> Default for SortedVecSet<T> {
+    /// Creates an empty `SortedVecSet<T>`.
+    fn default() -> SortedVecSet<T> {
+        SortedVecSet::new()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Debug> Debug for SortedVecSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Ord> FromIterator<T> for SortedVecSet<T> {
+    /// Collects the values and sorts them in *O*(*n* \* log(*n*)) time.
+    /// Like repeated calls to `insert`, the first of equal values wins.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> SortedVecSet<T> {
+        let mut data: Vec<T> = iter.into_iter().collect();
+        sort_and_dedup(&mut data, identity, |_, _| {});
+        SortedVecSet { data }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Ord> Extend<T> for SortedVecSet<T> {
+    /// Sorts the new values and merges them in, rather than inserting them
+    /// one at a time.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut other: SortedVecSet<T> = iter.into_iter().collect();
+        self.append(&mut other);
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for SortedVecSet<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Ord + Clone> BitOr<&SortedVecSet<T>> for &SortedVecSet<T> {
+    type Output = SortedVecSet<T>;
+
+    /// Returns the union of `self` and `rhs` as a new `SortedVecSet<T>`.
+    fn bitor(self, rhs: &SortedVecSet<T>) -> SortedVecSet<T> {
+        self.union(rhs)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Ord + Clone> BitAnd<&SortedVecSet<T>> for &SortedVecSet<T> {
+    type Output = SortedVecSet<T>;
+
+    /// Returns the intersection of `self` and `rhs` as a new `SortedVecSet<T>`.
+    fn bitand(self, rhs: &SortedVecSet<T>) -> SortedVecSet<T> {
+        self.intersection(rhs)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Ord + Clone> Sub<&SortedVecSet<T>> for &SortedVecSet<T> {
+    type Output = SortedVecSet<T>;
+
+    /// Returns the difference of `self` and `rhs` as a new `SortedVecSet<T>`.
+    fn sub(self, rhs: &SortedVecSet<T>) -> SortedVecSet<T> {
+        self.difference(rhs)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Ord + Clone> BitXor<&SortedVecSet<T>> for &SortedVecSet<T> {
+    type Output = SortedVecSet<T>;
+
+    /// Returns the symmetric difference of `self` and `rhs` as a new `SortedVecSet<T>`.
+    fn bitxor(self, rhs: &SortedVecSet<T>) -> SortedVecSet<T> {
+        self.symmetric_difference(rhs)
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> IntoIterator for SortedVecSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { iter: self.data.into_iter() }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, T> IntoIterator for &'a SortedVecSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> FusedIterator for Iter<'_, T> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { iter: self.iter.clone() }
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T> FusedIterator for IntoIter<T> {}
+
+#[unstable(feature = "sorted_vec", issue = "none")]
+impl<T: Debug> Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.iter.as_slice()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
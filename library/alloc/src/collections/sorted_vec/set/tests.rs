@@ -0,0 +1,68 @@
+use super::*;
+use crate::vec::Vec;
+
+fn set_of(values: &[i32]) -> SortedVecSet<i32> {
+    values.iter().copied().collect()
+}
+
+#[test]
+fn test_basic() {
+    let mut set = SortedVecSet::new();
+    assert!(set.insert(3));
+    assert!(set.insert(1));
+    assert!(!set.insert(3));
+    assert!(set.insert(2));
+    assert_eq!(set.as_slice(), [1, 2, 3]);
+    assert!(set.contains(&2));
+    assert_eq!(set.get(&2), Some(&2));
+    assert!(set.remove(&2));
+    assert!(!set.remove(&2));
+    assert_eq!(set.take(&1), Some(1));
+    assert_eq!(set.first(), Some(&3));
+    assert_eq!(set.last(), Some(&3));
+    assert_eq!(set.pop_last(), Some(3));
+    assert_eq!(set.pop_first(), None);
+}
+
+#[test]
+fn test_from_iter_and_range() {
+    let set: SortedVecSet<_> = [5, 1, 4, 1, 5, 9, 2, 6].iter().copied().collect();
+    assert_eq!(set.as_slice(), [1, 2, 4, 5, 6, 9]);
+    assert_eq!(set.range(3..6), [4, 5]);
+    assert_eq!(set.range(..=2), [1, 2]);
+    assert!(set.range(10..).is_empty());
+    assert!(set.iter().rev().copied().eq(set.clone().into_iter().rev()));
+    assert_eq!(SortedVecSet::from_sorted_iter(0..5).into_vec(), (0..5).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_set_operations() {
+    let a = set_of(&[1, 3, 5, 7, 9]);
+    let b = set_of(&[2, 3, 5, 8]);
+    assert_eq!((&a | &b).as_slice(), [1, 2, 3, 5, 7, 8, 9]);
+    assert_eq!((&a & &b).as_slice(), [3, 5]);
+    assert_eq!((&a - &b).as_slice(), [1, 7, 9]);
+    assert_eq!((&b - &a).as_slice(), [2, 8]);
+    assert_eq!((&a ^ &b).as_slice(), [1, 2, 7, 8, 9]);
+
+    assert!(!a.is_disjoint(&b));
+    assert!(a.is_disjoint(&set_of(&[0, 2, 4])));
+    assert!(set_of(&[3, 5]).is_subset(&a));
+    assert!(set_of(&[]).is_subset(&a));
+    assert!(!set_of(&[3, 4]).is_subset(&a));
+    assert!(!set_of(&[3, 10]).is_subset(&a));
+    assert!(a.is_superset(&set_of(&[1, 9])));
+}
+
+#[test]
+fn test_append_and_extend() {
+    let mut a = set_of(&[1, 2, 3]);
+    let mut b = set_of(&[3, 4, 5]);
+    a.append(&mut b);
+    assert_eq!(a.as_slice(), [1, 2, 3, 4, 5]);
+    assert!(b.is_empty());
+    a.extend([0, 5, 6].iter());
+    assert_eq!(a.as_slice(), [0, 1, 2, 3, 4, 5, 6]);
+    a.retain(|&x| x % 2 == 0);
+    assert_eq!(a.as_slice(), [0, 2, 4, 6]);
+}
@@ -50,6 +50,17 @@
 //! The [`alloc`](alloc/index.html) module defines the low-level interface to the
 //! default global allocator. It is not compatible with the libc allocator API.
 //!
+//! ## Fallible allocation
+//!
+//! Most collections offer `try_` variants of their growing methods, such as
+//! [`Vec::try_push`] and [`Box::try_new`], which report allocation failure as an
+//! error instead of aborting. Building this crate with
+//! `--cfg no_global_oom_handling` removes every API that would abort on
+//! allocation failure, leaving only the fallible ones.
+//!
+//! [`Vec::try_push`]: vec::Vec::try_push
+//! [`Box::try_new`]: boxed::Box::try_new
+//!
 //! [`Arc`]: sync
 //! [`Box`]: boxed
 //! [`Cell`]: core::cell
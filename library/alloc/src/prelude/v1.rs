@@ -9,6 +9,9 @@
 #[unstable(feature = "alloc_prelude", issue = "58935")]
 pub use crate::boxed::Box;
 #[unstable(feature = "alloc_prelude", issue = "58935")]
-pub use crate::string::{String, ToString};
+pub use crate::string::String;
+#[cfg(not(no_global_oom_handling))]
+#[unstable(feature = "alloc_prelude", issue = "58935")]
+pub use crate::string::ToString;
 #[unstable(feature = "alloc_prelude", issue = "58935")]
 pub use crate::vec::Vec;
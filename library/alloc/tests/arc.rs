@@ -5,6 +5,8 @@
 use std::mem;
 use std::sync::{Arc, Weak};
 
+use crate::CountingAlloc;
+
 #[test]
 fn uninhabited() {
     enum Void {}
@@ -195,3 +197,33 @@ fn next(&mut self) -> Option<Self::Item> {
     assert_trusted_len(&iter);
     assert_eq!(&[Box::new(42), Box::new(24)], &*iter.collect::<Rc<[_]>>());
 }
+
+#[test]
+fn allocator() {
+    let alloc = CountingAlloc::default();
+    let mut x = Arc::new_in(vec![1, 2], alloc.clone());
+    assert_eq!(alloc.live(), 1);
+
+    let y = Arc::clone(&x);
+    let weak = Arc::downgrade(&x);
+    assert_eq!(alloc.live(), 1);
+
+    // Clones the contents into a second allocation from the same allocator.
+    Arc::make_mut(&mut x).push(3);
+    assert_eq!(alloc.live(), 2);
+    assert_eq!(*x, [1, 2, 3]);
+    assert_eq!(*y, [1, 2]);
+
+    drop(y);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(alloc.live(), 2);
+    drop(weak);
+    assert_eq!(alloc.live(), 1);
+
+    assert_eq!(Arc::try_unwrap(x).ok(), Some(vec![1, 2, 3]));
+    assert_eq!(alloc.live(), 0);
+
+    let empty: Weak<u32, _> = Weak::new_in(alloc.clone());
+    assert!(empty.upgrade().is_none());
+    assert_eq!(alloc.live(), 0);
+}
@@ -57,3 +57,13 @@ fn box_deref_lval() {
     x.set(1000);
     assert_eq!(x.get(), 1000);
 }
+
+#[test]
+fn try_new() {
+    let b = Box::try_new(5).unwrap();
+    assert_eq!(*b, 5);
+
+    assert!(Box::try_new_in(5, crate::FailingAlloc).is_err());
+    // Zero-sized values never allocate.
+    assert!(Box::try_new_in((), crate::FailingAlloc).is_ok());
+}
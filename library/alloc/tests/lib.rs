@@ -19,11 +19,16 @@
 #![feature(deque_range)]
 #![feature(inplace_iteration)]
 #![feature(iter_map_while)]
+#![feature(merge_sorted)]
 #![feature(int_bits_const)]
 #![feature(vecdeque_binary_search)]
 
+use std::alloc::{AllocError, AllocRef, Global, Layout};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+use std::sync::Arc;
 
 mod arc;
 mod binary_heap;
@@ -47,6 +52,48 @@ fn hash<T: Hash>(t: &T) -> u64 {
     s.finish()
 }
 
+/// An allocator forwarding to `Global` that counts its live allocations.
+///
+/// Clones share the counter, so a test can hand a clone to a collection and
+/// check afterwards that everything was allocated from and returned to it.
+#[derive(Clone, Default)]
+struct CountingAlloc {
+    live: Arc<AtomicUsize>,
+}
+
+impl CountingAlloc {
+    fn live(&self) -> usize {
+        self.live.load(SeqCst)
+    }
+}
+
+unsafe impl AllocRef for CountingAlloc {
+    fn alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = Global.alloc(layout)?;
+        self.live.fetch_add(1, SeqCst);
+        Ok(ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.fetch_sub(1, SeqCst);
+        unsafe { Global.dealloc(ptr, layout) }
+    }
+}
+
+/// An allocator that fails every allocation, for testing the fallible APIs.
+#[derive(Clone, Copy, Default)]
+struct FailingAlloc;
+
+unsafe impl AllocRef for FailingAlloc {
+    fn alloc(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+
+    unsafe fn dealloc(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        unreachable!("nothing was ever allocated")
+    }
+}
+
 // FIXME: Instantiated functions with i128 in the signature is not supported in Emscripten.
 // See https://github.com/kripken/emscripten-fastcomp/issues/169
 #[cfg(not(target_os = "emscripten"))]
@@ -1460,6 +1460,21 @@ fn test_to_vec() {
     assert_eq!(ys, [1, 2, 3]);
 }
 
+#[test]
+fn test_merge_sorted() {
+    let xs = [1, 3, 5, 7];
+    let ys = [2, 3, 8];
+    assert_eq!(xs.merge_sorted(&ys), [1, 2, 3, 3, 5, 7, 8]);
+    assert_eq!(xs.merge_sorted(&[]), xs);
+    let empty: [i32; 0] = [];
+    assert_eq!(empty.merge_sorted(&ys), ys);
+
+    let xs = [(1, 'a'), (2, 'a')];
+    let ys = [(1, 'b'), (2, 'b')];
+    let merged = xs.merge_sorted_by(&ys, |x, y| x.0.cmp(&y.0));
+    assert_eq!(merged, [(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+}
+
 #[test]
 fn test_in_place_iterator_specialization() {
     let src: Box<[usize]> = box [1, 2, 3];
@@ -3,6 +3,8 @@
 use std::ops::Bound::*;
 use std::panic;
 
+use crate::CountingAlloc;
+
 pub trait IntoCow<'a, B: ?Sized>
 where
     B: ToOwned,
@@ -776,3 +778,45 @@ fn test_str_concat() {
     let s: String = format!("{}{}", a, b);
     assert_eq!(s.as_bytes()[9], 'd' as u8);
 }
+
+#[test]
+fn test_string_allocator() {
+    let alloc = CountingAlloc::default();
+    let mut s = String::new_in(alloc.clone());
+    assert_eq!(alloc.live(), 0);
+    s.push_str("hello world");
+    assert_eq!(alloc.live(), 1);
+
+    let t = s.clone();
+    assert_eq!(alloc.live(), 2);
+    assert_eq!(t.as_str(), "hello world");
+
+    let world = s.split_off(6);
+    assert_eq!(alloc.live(), 3);
+    assert_eq!(s.as_str(), "hello ");
+    assert_eq!(world.as_str(), "world");
+
+    let boxed = world.into_boxed_str();
+    assert_eq!(alloc.live(), 3);
+    assert_eq!(&*boxed, "world");
+
+    drop((s, t, boxed));
+    assert_eq!(alloc.live(), 0);
+
+    let s = String::with_capacity_in(8, alloc.clone());
+    assert_eq!(alloc.live(), 1);
+    drop(s);
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_try_push_str() {
+    let mut s = String::try_with_capacity(1).unwrap();
+    s.try_push_str("hello").unwrap();
+    s.try_push(' ').unwrap();
+    s.try_push('ä').unwrap();
+    s.try_push_str("").unwrap();
+    assert_eq!(s, "hello ä");
+
+    assert!(String::try_with_capacity(usize::MAX).is_err());
+}
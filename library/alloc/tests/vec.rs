@@ -9,6 +9,8 @@
 use std::rc::Rc;
 use std::vec::{Drain, IntoIter};
 
+use crate::{CountingAlloc, FailingAlloc};
+
 struct DropCounter<'a> {
     count: &'a mut u32,
 }
@@ -795,6 +797,59 @@ fn test_append() {
     assert_eq!(vec2, []);
 }
 
+#[test]
+fn test_merge_sorted() {
+    let mut vec = vec![(1, 'a'), (3, 'a'), (3, 'b'), (7, 'a')];
+    let mut vec2 = vec![(0, 'c'), (3, 'c'), (8, 'c')];
+    vec.merge_sorted_by(&mut vec2, |x, y| x.0.cmp(&y.0));
+    assert_eq!(vec, [(0, 'c'), (1, 'a'), (3, 'a'), (3, 'b'), (3, 'c'), (7, 'a'), (8, 'c')]);
+    assert_eq!(vec2, []);
+
+    let mut empty = Vec::new();
+    vec2 = vec![(9, 'd')];
+    empty.merge_sorted_by(&mut vec2, |x, y| x.0.cmp(&y.0));
+    vec.merge_sorted_by(&mut empty, |x, y| x.0.cmp(&y.0));
+    assert_eq!(vec.len(), 8);
+    assert_eq!(vec.last(), Some(&(9, 'd')));
+
+    let mut odd: Vec<u32> = (0..100).map(|i| i * 2 + 1).collect();
+    let mut even: Vec<u32> = (0..50).map(|i| i * 2).collect();
+    odd.merge_sorted(&mut even);
+    assert!(odd[..100].iter().copied().eq(0..100));
+    assert!(odd[100..].iter().copied().eq((50..100).map(|i| i * 2 + 1)));
+}
+
+#[test]
+fn test_merge_sorted_panic() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static DROPS: AtomicU32 = AtomicU32::new(0);
+
+    struct D(u32);
+
+    impl Drop for D {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let mut vec: Vec<_> = (0..10).map(|i| D(i * 2)).collect();
+    let mut vec2: Vec<_> = (0..10).map(|i| D(i * 2 + 1)).collect();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        vec.merge_sorted_by(&mut vec2, |x, y| {
+            if x.0 == 13 || y.0 == 13 {
+                panic!("panic in `compare`");
+            }
+            x.0.cmp(&y.0)
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec.len(), 20);
+    assert!(vec2.is_empty());
+    drop(vec);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 20);
+}
+
 #[test]
 fn test_split_off() {
     let mut vec = vec![1, 2, 3, 4, 5, 6];
@@ -1954,3 +2009,75 @@ fn test_vec_swap() {
     assert_eq!(a[0], 42);
     assert_eq!(n, 0);
 }
+
+#[test]
+fn test_vec_allocator() {
+    let alloc = CountingAlloc::default();
+    let mut v = Vec::new_in(alloc.clone());
+    assert_eq!(alloc.live(), 0);
+    v.extend_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(alloc.live(), 1);
+
+    let w = v.clone();
+    assert_eq!(alloc.live(), 2);
+    assert_eq!(w, [1, 2, 3, 4]);
+
+    let tail = v.split_off(2);
+    assert_eq!(alloc.live(), 3);
+    assert_eq!(v, [1, 2]);
+    assert_eq!(tail, [3, 4]);
+
+    let boxed = tail.into_boxed_slice();
+    assert_eq!(alloc.live(), 3);
+    assert_eq!(*boxed, [3, 4]);
+
+    drop((v, w, boxed));
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_vec_with_capacity_in() {
+    let alloc = CountingAlloc::default();
+    let mut v: Vec<u8, _> = Vec::with_capacity_in(10, alloc.clone());
+    assert_eq!(alloc.live(), 1);
+    assert!(v.capacity() >= 10);
+    v.push(1);
+    v.shrink_to_fit();
+    assert_eq!(alloc.live(), 1);
+    drop(v);
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_try_push() {
+    let mut v = Vec::try_with_capacity(1).unwrap();
+    for i in 0..100 {
+        v.try_push(i).unwrap();
+    }
+    assert_eq!(v, (0..100).collect::<Vec<_>>());
+
+    v.try_insert(0, -1).unwrap();
+    v.try_extend_from_slice(&[100, 101]).unwrap();
+    assert_eq!(v.len(), 103);
+    assert_eq!(v[..2], [-1, 0]);
+    assert_eq!(v[100..], [99, 100, 101]);
+
+    assert_eq!(Vec::<u32>::try_with_capacity(usize::MAX), Err(CapacityOverflow));
+}
+
+#[test]
+fn test_try_push_alloc_failure() {
+    assert!(matches!(Vec::<u8, _>::try_with_capacity_in(1, FailingAlloc), Err(AllocError { .. })));
+
+    let mut v: Vec<Box<u8>, _> = Vec::new_in(FailingAlloc);
+    assert!(matches!(v.try_push(Box::new(1)), Err(AllocError { .. })));
+    assert!(matches!(v.try_insert(0, Box::new(2)), Err(AllocError { .. })));
+    assert!(v.try_extend_from_slice(&[Box::new(3)]).is_err());
+    assert!(v.is_empty());
+
+    // Zero-sized types never allocate.
+    let mut v = Vec::new_in(FailingAlloc);
+    v.try_push(()).unwrap();
+    v.try_extend_from_slice(&[(), ()]).unwrap();
+    assert_eq!(v.len(), 3);
+}
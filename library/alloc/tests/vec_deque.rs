@@ -5,7 +5,7 @@
 use std::ops::Bound::*;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
-use crate::hash;
+use crate::{hash, CountingAlloc, FailingAlloc};
 
 use Taggy::*;
 use Taggypar::*;
@@ -1728,3 +1728,48 @@ fn test_zero_sized_push() {
         }
     }
 }
+
+#[test]
+fn test_allocator() {
+    let alloc = CountingAlloc::default();
+    let mut d = VecDeque::new_in(alloc.clone());
+    assert_eq!(alloc.live(), 0);
+    d.extend(0..8);
+    d.push_front(-1);
+    assert_eq!(alloc.live(), 1);
+
+    let e = d.clone();
+    assert_eq!(alloc.live(), 2);
+    assert!(e.iter().eq(d.iter()));
+
+    let tail = d.split_off(5);
+    assert_eq!(alloc.live(), 3);
+    assert!(d.iter().eq([-1, 0, 1, 2, 3].iter()));
+    assert!(tail.iter().eq([4, 5, 6, 7].iter()));
+
+    drop((d, e, tail));
+    assert_eq!(alloc.live(), 0);
+
+    let d: VecDeque<u8, _> = VecDeque::with_capacity_in(10, alloc.clone());
+    assert_eq!(alloc.live(), 1);
+    assert!(d.capacity() >= 10);
+    drop(d);
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn test_try_push() {
+    let mut d = VecDeque::try_with_capacity(1).unwrap();
+    for i in 0..50 {
+        d.try_push_back(i).unwrap();
+        d.try_push_front(-i - 1).unwrap();
+    }
+    assert_eq!(d.len(), 100);
+    assert!(d.iter().copied().eq(-50..50));
+
+    assert_eq!(VecDeque::<u8>::try_with_capacity(usize::MAX), Err(CapacityOverflow));
+    assert!(matches!(
+        VecDeque::<u8, _>::try_with_capacity_in(1, FailingAlloc),
+        Err(AllocError { .. })
+    ));
+}
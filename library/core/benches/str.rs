@@ -0,0 +1,32 @@
+use std::str;
+use test::black_box;
+use test::Bencher;
+
+const ENGLISH: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do \
+                       eiusmod tempor incididunt ut labore et dolore magna aliqua. ";
+const FRENCH: &str = "Le cœur déçu mais l'âme plutôt naïve, Louÿs rêva de crapaüter en canoë \
+                      au delà des îles, près du mälström où brûlent les novæ. ";
+const RUSSIAN: &str = "Съешь же ещё этих мягких французских булок, да выпей же чаю. ";
+const CHINESE: &str = "我能吞下玻璃而不伤身体。天地玄黄，宇宙洪荒。";
+
+// `from_utf8` skips over ascii a vector at a time on some targets, which pays off for long ascii
+// runs but not for the short ones between the words of non-Latin scripts.
+macro_rules! from_utf8_benches {
+    ($($name: ident: $text: ident,)+) => {
+        $(
+            #[bench]
+            fn $name(bencher: &mut Bencher) {
+                let text = $text.repeat(64);
+                bencher.bytes = text.len() as u64;
+                bencher.iter(|| str::from_utf8(black_box(text.as_bytes())).is_ok())
+            }
+        )+
+    };
+}
+
+from_utf8_benches! {
+    from_utf8_ascii: ENGLISH,
+    from_utf8_mostly_ascii: FRENCH,
+    from_utf8_cyrillic: RUSSIAN,
+    from_utf8_cjk: CHINESE,
+}
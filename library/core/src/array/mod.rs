@@ -12,6 +12,7 @@
 use crate::fmt;
 use crate::hash::{self, Hash};
 use crate::marker::Unsize;
+use crate::mem::{self, MaybeUninit};
 use crate::ops::{Index, IndexMut};
 use crate::slice::{Iter, IterMut};
 
@@ -34,6 +35,87 @@
     unsafe { &mut *(s as *mut T).cast::<[T; 1]>() }
 }
 
+/// Creates an array `[T; N]` where each element is `cb(i)`, with `i` being its index.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(array_from_fn)]
+/// let array: [usize; 5] = core::array::from_fn(|i| i * 2);
+/// assert_eq!(array, [0, 2, 4, 6, 8]);
+/// ```
+#[unstable(feature = "array_from_fn", issue = "none")]
+pub fn from_fn<T, F, const N: usize>(cb: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    let mut iter = (0..N).map(cb);
+    // SAFETY: The range yields exactly `N` indices.
+    unsafe { collect_into_array_unchecked(&mut iter) }
+}
+
+/// Creates an array `[T; N]` where each element is `cb(i)`, with `i` being its index, or
+/// returns the first error `cb` returns.
+///
+/// The elements are created in order, and `cb` isn't called again after it returned an
+/// error. The elements created up to that point are dropped.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(array_from_fn)]
+/// use std::convert::TryInto;
+///
+/// let array: Result<[u8; 5], _> = core::array::try_from_fn(|i| (i * 70).try_into());
+/// assert!(array.is_err());
+///
+/// let array: Result<[u8; 4], _> = core::array::try_from_fn(|i| (i * 70).try_into());
+/// assert_eq!(array, Ok([0, 70, 140, 210]));
+/// ```
+#[unstable(feature = "array_from_fn", issue = "none")]
+pub fn try_from_fn<T, E, F, const N: usize>(cb: F) -> Result<[T; N], E>
+where
+    F: FnMut(usize) -> Result<T, E>,
+{
+    match try_collect_into_array(&mut (0..N).map(cb)) {
+        Some(result) => result,
+        // SAFETY: The range yields exactly `N` indices.
+        None => unsafe { crate::hint::unreachable_unchecked() },
+    }
+}
+
+/// Collects an iterator that yields exactly `N` items into an array `[T; N]`.
+///
+/// Returns `None` if the iterator yields fewer or more than `N` items, in
+/// which case the items collected so far are dropped. If the iterator panics,
+/// the items collected so far are dropped as well.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(array_from_fn)]
+/// let squares: Option<[u32; 4]> = core::array::try_from_iter((1..=4).map(|i| i * i));
+/// assert_eq!(squares, Some([1, 4, 9, 16]));
+///
+/// let too_short: Option<[u32; 4]> = core::array::try_from_iter(1..=3);
+/// assert_eq!(too_short, None);
+///
+/// let too_long: Option<[u32; 4]> = core::array::try_from_iter(1..=5);
+/// assert_eq!(too_long, None);
+/// ```
+#[unstable(feature = "array_from_fn", issue = "none")]
+pub fn try_from_iter<T, I, const N: usize>(iter: I) -> Option<[T; N]>
+where
+    I: IntoIterator<Item = T>,
+{
+    let mut iter = iter.into_iter();
+    let array = collect_into_array(&mut iter)?;
+    if iter.next().is_some() {
+        return None;
+    }
+    Some(array)
+}
+
 /// Utility trait implemented only on arrays of fixed size
 ///
 /// This trait can be used to implement other traits on fixed-size arrays
@@ -425,42 +507,35 @@ macro_rules! array_impl_default {
     /// assert_eq!(y, [6, 9, 3, 3]);
     /// ```
     #[unstable(feature = "array_map", issue = "75243")]
-    pub fn map<F, U>(self, mut f: F) -> [U; N]
+    pub fn map<F, U>(self, f: F) -> [U; N]
     where
         F: FnMut(T) -> U,
     {
-        use crate::mem::MaybeUninit;
-        struct Guard<T, const N: usize> {
-            dst: *mut T,
-            initialized: usize,
-        }
+        // SAFETY: The iterator yields exactly `N` elements.
+        unsafe { collect_into_array_unchecked(&mut IntoIter::new(self).map(f)) }
+    }
 
-        impl<T, const N: usize> Drop for Guard<T, N> {
-            fn drop(&mut self) {
-                debug_assert!(self.initialized <= N);
-
-                let initialized_part =
-                    crate::ptr::slice_from_raw_parts_mut(self.dst, self.initialized);
-                // SAFETY: this raw slice will contain only initialized objects
-                // that's why, it is allowed to drop it.
-                unsafe {
-                    crate::ptr::drop_in_place(initialized_part);
-                }
-            }
-        }
-        let mut dst = MaybeUninit::uninit_array::<N>();
-        let mut guard: Guard<U, N> =
-            Guard { dst: MaybeUninit::slice_as_mut_ptr(&mut dst), initialized: 0 };
-        for (src, dst) in IntoIter::new(self).zip(&mut dst) {
-            dst.write(f(src));
-            guard.initialized += 1;
-        }
-        // FIXME: Convert to crate::mem::transmute once it works with generics.
-        // unsafe { crate::mem::transmute::<[MaybeUninit<U>; N], [U; N]>(dst) }
-        crate::mem::forget(guard);
-        // SAFETY: At this point we've properly initialized the whole array
-        // and we just need to cast it to the correct type.
-        unsafe { crate::mem::transmute_copy::<_, [U; N]>(&dst) }
+    /// 'Zips up' two arrays into a single array of pairs.
+    ///
+    /// `zip()` returns a new array where every element is a tuple where the
+    /// first element comes from the first array, and the second element comes
+    /// from the second array. In other words, it zips two arrays together,
+    /// into a single one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_zip)]
+    /// let x = [1, 2, 3];
+    /// let y = [4, 5, 6];
+    /// let z = x.zip(y);
+    /// assert_eq!(z, [(1, 4), (2, 5), (3, 6)]);
+    /// ```
+    #[unstable(feature = "array_zip", issue = "none")]
+    pub fn zip<U>(self, rhs: [U; N]) -> [(T, U); N] {
+        let mut iter = IntoIter::new(self).zip(IntoIter::new(rhs));
+        // SAFETY: The iterator yields exactly `N` elements.
+        unsafe { collect_into_array_unchecked(&mut iter) }
     }
 
     /// Returns a slice containing the entire array. Equivalent to `&s[..]`.
@@ -475,4 +550,139 @@ pub fn as_slice(&self) -> &[T] {
     pub fn as_mut_slice(&mut self) -> &mut [T] {
         self
     }
+
+    /// Borrows each element and returns an array of references with the same
+    /// size as `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_methods)]
+    /// let floats = [3.1, 2.7, -1.0];
+    /// let float_refs: [&f64; 3] = floats.each_ref();
+    /// assert_eq!(float_refs, [&3.1, &2.7, &-1.0]);
+    /// ```
+    ///
+    /// This method is particularly useful if combined with other methods, like
+    /// [`map`](#method.map). This way, you can avoid moving the original
+    /// array if its elements are not `Copy`.
+    ///
+    /// ```
+    /// #![feature(array_methods, array_map)]
+    /// let strings = ["Ferris".to_string(), "♥".to_string(), "Rust".to_string()];
+    /// let is_ascii = strings.each_ref().map(|s| s.is_ascii());
+    /// assert_eq!(is_ascii, [true, false, true]);
+    ///
+    /// // We can still access the original array: it has not been moved.
+    /// assert_eq!(strings.len(), 3);
+    /// ```
+    #[unstable(feature = "array_methods", issue = "76118")]
+    pub fn each_ref(&self) -> [&T; N] {
+        // SAFETY: The iterator yields exactly `N` elements.
+        unsafe { collect_into_array_unchecked(&mut self.iter()) }
+    }
+
+    /// Borrows each element mutably and returns an array of mutable references
+    /// with the same size as `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(array_methods)]
+    /// let mut floats = [3.1, 2.7, -1.0];
+    /// let float_refs: [&mut f64; 3] = floats.each_mut();
+    /// *float_refs[0] = 0.0;
+    /// assert_eq!(float_refs, [&mut 0.0, &mut 2.7, &mut -1.0]);
+    /// assert_eq!(floats, [0.0, 2.7, -1.0]);
+    /// ```
+    #[unstable(feature = "array_methods", issue = "76118")]
+    pub fn each_mut(&mut self) -> [&mut T; N] {
+        // SAFETY: The iterator yields exactly `N` elements.
+        unsafe { collect_into_array_unchecked(&mut self.iter_mut()) }
+    }
+}
+
+/// Pulls `N` items from `iter` and returns them as an array.
+///
+/// # Safety
+///
+/// The iterator must yield at least `N` items. Like with `collect_into_array`,
+/// the items that were yielded so far are dropped if `iter.next()` panics.
+unsafe fn collect_into_array_unchecked<I, const N: usize>(iter: &mut I) -> [I::Item; N]
+where
+    I: Iterator,
+{
+    match collect_into_array(iter) {
+        Some(array) => array,
+        // SAFETY: The caller guarantees that the iterator yields at least `N` items.
+        None => unsafe { crate::hint::unreachable_unchecked() },
+    }
+}
+
+/// Pulls `N` items from `iter` and returns them as an array. If the iterator
+/// yields fewer than `N` items, `None` is returned and the items yielded so
+/// far are dropped.
+///
+/// If `iter.next()` panics, the items yielded so far are dropped as well.
+fn collect_into_array<I, const N: usize>(iter: &mut I) -> Option<[I::Item; N]>
+where
+    I: Iterator,
+{
+    let mut map = iter.map(Ok::<_, Infallible>);
+    match try_collect_into_array(&mut map)? {
+        Ok(array) => Some(array),
+        Err(never) => match never {},
+    }
+}
+
+/// Pulls `N` items from `iter` and returns them as an array, stopping at the
+/// first error. If the iterator yields fewer than `N` items, `None` is
+/// returned.
+///
+/// Whenever this doesn't return a full array, including when `iter.next()`
+/// panics, the items yielded so far are dropped.
+fn try_collect_into_array<I, T, E, const N: usize>(iter: &mut I) -> Option<Result<[T; N], E>>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    struct Guard<T, const N: usize> {
+        dst: *mut T,
+        initialized: usize,
+    }
+
+    impl<T, const N: usize> Drop for Guard<T, N> {
+        fn drop(&mut self) {
+            debug_assert!(self.initialized <= N);
+
+            let initialized_part = crate::ptr::slice_from_raw_parts_mut(self.dst, self.initialized);
+            // SAFETY: this raw slice will contain only initialized objects
+            // that's why, it is allowed to drop it.
+            unsafe {
+                crate::ptr::drop_in_place(initialized_part);
+            }
+        }
+    }
+
+    let mut dst = MaybeUninit::uninit_array::<N>();
+    let mut guard: Guard<T, N> =
+        Guard { dst: MaybeUninit::slice_as_mut_ptr(&mut dst), initialized: 0 };
+    while guard.initialized < N {
+        let item = match iter.next()? {
+            Ok(item) => item,
+            Err(err) => return Some(Err(err)),
+        };
+        // SAFETY: `guard.initialized < N`, which is the length of `dst`, and the
+        // element hasn't been written yet, so nothing is overwritten without being
+        // dropped.
+        unsafe {
+            guard.dst.add(guard.initialized).write(item);
+        }
+        guard.initialized += 1;
+    }
+    // FIXME: Convert to crate::mem::transmute once it works with generics.
+    // unsafe { crate::mem::transmute::<[MaybeUninit<T>; N], [T; N]>(dst) }
+    mem::forget(guard);
+    // SAFETY: At this point we've properly initialized the whole array
+    // and we just need to cast it to the correct type.
+    Some(Ok(unsafe { mem::transmute_copy::<_, [T; N]>(&dst) }))
 }
@@ -1,6 +1,7 @@
 use crate::fmt::{Debug, Display, Formatter, LowerExp, Result, UpperExp};
 use crate::mem::MaybeUninit;
 use crate::num::flt2dec;
+use crate::str;
 
 // Don't inline this so callers don't use the stack space this function
 // requires unless they have to.
@@ -151,6 +152,51 @@ fn float_to_exponential_common<T>(fmt: &mut Formatter<'_>, num: &T, upper: bool)
     }
 }
 
+/// Writes `num` the way its `Display` implementation does into the start of `out`, and returns
+/// the written part. This backs `to_shortest_str` on the float types, which make sure that `out`
+/// is long enough.
+pub(crate) fn float_to_decimal_str<T>(num: T, out: &mut [u8]) -> &str
+where
+    T: flt2dec::DecodableFloat,
+{
+    let mut buf: [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS] = MaybeUninit::uninit_array();
+    let mut parts: [MaybeUninit<flt2dec::Part<'_>>; 4] = MaybeUninit::uninit_array();
+    let formatted = flt2dec::to_shortest_str(
+        flt2dec::strategy::grisu::format_shortest,
+        num,
+        flt2dec::Sign::Minus,
+        0,
+        &mut buf,
+        &mut parts,
+    );
+    let len = formatted.write(out).expect("buffer too small for the formatted float");
+    // SAFETY: `flt2dec` only produces ASCII.
+    unsafe { str::from_utf8_unchecked(&out[..len]) }
+}
+
+/// Writes `num` the way its `LowerExp` implementation does into the start of `out`, and returns
+/// the written part. This backs `to_shortest_exp_str` on the float types, which make sure that
+/// `out` is long enough.
+pub(crate) fn float_to_exponential_str<T>(num: T, out: &mut [u8]) -> &str
+where
+    T: flt2dec::DecodableFloat,
+{
+    let mut buf: [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS] = MaybeUninit::uninit_array();
+    let mut parts: [MaybeUninit<flt2dec::Part<'_>>; 6] = MaybeUninit::uninit_array();
+    let formatted = flt2dec::to_shortest_exp_str(
+        flt2dec::strategy::grisu::format_shortest,
+        num,
+        flt2dec::Sign::Minus,
+        (0, 0),
+        false,
+        &mut buf,
+        &mut parts,
+    );
+    let len = formatted.write(out).expect("buffer too small for the formatted float");
+    // SAFETY: `flt2dec` only produces ASCII.
+    unsafe { str::from_utf8_unchecked(&out[..len]) }
+}
+
 macro_rules! floating {
     ($ty:ident) => {
         #[stable(feature = "rust1", since = "1.0.0")]
@@ -11,8 +11,8 @@
 use crate::str;
 
 mod builders;
-mod float;
-mod num;
+pub(crate) mod float;
+pub(crate) mod num;
 
 #[stable(feature = "fmt_flags_align", since = "1.28.0")]
 /// Possible alignments returned by `Formatter::align`
@@ -206,63 +206,94 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
       6061626364656667686970717273747576777879\
       8081828384858687888990919293949596979899";
 
+/// Unsigned integers whose decimal digits can be written into a raw buffer.
+trait DecimalDigits: Copy {
+    /// Writes the digits of `self` into `buf_ptr[..curr]`, ending just before `curr`, and
+    /// returns the index of the first one.
+    ///
+    /// # Safety
+    ///
+    /// `buf_ptr` must be valid for writes of `curr` bytes, and `curr` must be at least the
+    /// number of digits of `self`.
+    unsafe fn write_digits(self, buf_ptr: *mut u8, curr: isize) -> isize;
+}
+
+/// Integers that can be formatted straight into a byte buffer, without a `Formatter`.
+/// This backs `format_into` on the integer types.
+pub(crate) trait FormatInto: Copy {
+    /// Writes `self` in decimal, with a leading `-` if it is negative, into the end of `buf`
+    /// and returns the written part.
+    ///
+    /// # Safety
+    ///
+    /// `buf` must be long enough for any value of `Self`, including the sign.
+    unsafe fn format_into(self, buf: &mut [u8]) -> &str;
+}
+
 macro_rules! impl_Display {
     ($($t:ident),* as $u:ident via $conv_fn:ident named $name:ident) => {
-        fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // 2^128 is about 3*10^38, so 39 gives an extra byte of space
-            let mut buf = [MaybeUninit::<u8>::uninit(); 39];
-            let mut curr = buf.len() as isize;
-            let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
-            let lut_ptr = DEC_DIGITS_LUT.as_ptr();
-
-            // SAFETY: Since `d1` and `d2` are always less than or equal to `198`, we
-            // can copy from `lut_ptr[d1..d1 + 1]` and `lut_ptr[d2..d2 + 1]`. To show
-            // that it's OK to copy into `buf_ptr`, notice that at the beginning
-            // `curr == buf.len() == 39 > log(n)` since `n < 2^128 < 10^39`, and at
-            // each step this is kept the same as `n` is divided. Since `n` is always
-            // non-negative, this means that `curr > 0` so `buf_ptr[curr..curr + 1]`
-            // is safe to access.
-            unsafe {
-                // need at least 16 bits for the 4-characters-at-a-time to work.
-                assert!(crate::mem::size_of::<$u>() >= 2);
-
-                // eagerly decode 4 characters at a time
-                while n >= 10000 {
-                    let rem = (n % 10000) as isize;
-                    n /= 10000;
-
-                    let d1 = (rem / 100) << 1;
-                    let d2 = (rem % 100) << 1;
-                    curr -= 4;
-
-                    // We are allowed to copy to `buf_ptr[curr..curr + 3]` here since
-                    // otherwise `curr < 0`. But then `n` was originally at least `10000^10`
-                    // which is `10^40 > 2^128 > n`.
-                    ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
-                    ptr::copy_nonoverlapping(lut_ptr.offset(d2), buf_ptr.offset(curr + 2), 2);
-                }
+        impl DecimalDigits for $u {
+            unsafe fn write_digits(self, buf_ptr: *mut u8, mut curr: isize) -> isize {
+                let mut n = self;
+                let lut_ptr = DEC_DIGITS_LUT.as_ptr();
+
+                // SAFETY: Since `d1` and `d2` are always less than or equal to `198`, we
+                // can copy from `lut_ptr[d1..d1 + 1]` and `lut_ptr[d2..d2 + 1]`. To show
+                // that it's OK to copy into `buf_ptr`, notice that the caller guarantees that
+                // `curr` is at least the number of digits of `n` at the beginning, and at
+                // each step this is kept the same as `n` is divided. This means that
+                // `curr > 0` so `buf_ptr[curr..curr + 1]` is safe to access.
+                unsafe {
+                    // need at least 16 bits for the 4-characters-at-a-time to work.
+                    assert!(crate::mem::size_of::<$u>() >= 2);
+
+                    // eagerly decode 4 characters at a time
+                    while n >= 10000 {
+                        let rem = (n % 10000) as isize;
+                        n /= 10000;
+
+                        let d1 = (rem / 100) << 1;
+                        let d2 = (rem % 100) << 1;
+                        curr -= 4;
+
+                        // We are allowed to copy to `buf_ptr[curr..curr + 3]` here since
+                        // otherwise `curr < 0`. But then `n` had more digits than the caller
+                        // guaranteed room for.
+                        ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+                        ptr::copy_nonoverlapping(lut_ptr.offset(d2), buf_ptr.offset(curr + 2), 2);
+                    }
 
-                // if we reach here numbers are <= 9999, so at most 4 chars long
-                let mut n = n as isize; // possibly reduce 64bit math
+                    // if we reach here numbers are <= 9999, so at most 4 chars long
+                    let mut n = n as isize; // possibly reduce 64bit math
 
-                // decode 2 more chars, if > 2 chars
-                if n >= 100 {
-                    let d1 = (n % 100) << 1;
-                    n /= 100;
-                    curr -= 2;
-                    ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
-                }
+                    // decode 2 more chars, if > 2 chars
+                    if n >= 100 {
+                        let d1 = (n % 100) << 1;
+                        n /= 100;
+                        curr -= 2;
+                        ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+                    }
 
-                // decode last 1 or 2 chars
-                if n < 10 {
-                    curr -= 1;
-                    *buf_ptr.offset(curr) = (n as u8) + b'0';
-                } else {
-                    let d1 = n << 1;
-                    curr -= 2;
-                    ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+                    // decode last 1 or 2 chars
+                    if n < 10 {
+                        curr -= 1;
+                        *buf_ptr.offset(curr) = (n as u8) + b'0';
+                    } else {
+                        let d1 = n << 1;
+                        curr -= 2;
+                        ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+                    }
                 }
+                curr
             }
+        }
+
+        fn $name(n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // 2^128 is about 3*10^38, so 39 gives an extra byte of space
+            let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+            let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
+            // SAFETY: `buf` has room for 39 digits, and `n < 2^128 < 10^39`.
+            let curr = unsafe { n.write_digits(buf_ptr, buf.len() as isize) };
 
             // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid
             // UTF-8 since `DEC_DIGITS_LUT` is
@@ -286,6 +317,30 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 };
                 $name(n, is_nonnegative, f)
             }
+        }
+
+        impl FormatInto for $t {
+            #[allow(unused_comparisons)]
+            unsafe fn format_into(self, buf: &mut [u8]) -> &str {
+                let is_nonnegative = self >= 0;
+                let n = if is_nonnegative {
+                    self.$conv_fn()
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!self.$conv_fn()).wrapping_add(1)
+                };
+                let buf_ptr = buf.as_mut_ptr();
+                // SAFETY: The caller guarantees that `buf` has room for all the digits and the
+                // sign, and all the chars are valid UTF-8 since `DEC_DIGITS_LUT` is
+                unsafe {
+                    let mut curr = n.write_digits(buf_ptr, buf.len() as isize);
+                    if !is_nonnegative {
+                        curr -= 1;
+                        *buf_ptr.offset(curr) = b'-';
+                    }
+                    str::from_utf8_unchecked(&buf[curr as usize..])
+                }
+            }
         })*
     };
 }
@@ -479,16 +534,19 @@ mod imp {
 }
 impl_Exp!(i128, u128 as u128 via to_u128 named exp_u128);
 
-/// Helper function for writing a u64 into `buf` going from last to first, with `curr`.
-fn parse_u64_into<const N: usize>(mut n: u64, buf: &mut [MaybeUninit<u8>; N], curr: &mut isize) {
-    let buf_ptr = MaybeUninit::slice_as_mut_ptr(buf);
+/// Helper function for writing a u64 into `buf_ptr` going from last to first, with `curr`.
+///
+/// # Safety
+///
+/// `buf_ptr` must be valid for writes of `curr` bytes.
+unsafe fn parse_u64_into(mut n: u64, buf_ptr: *mut u8, curr: &mut isize) {
     let lut_ptr = DEC_DIGITS_LUT.as_ptr();
     assert!(*curr > 19);
 
     // SAFETY:
     // Writes at most 19 characters into the buffer. Guaranteed that any ptr into LUT is at most
     // 198, so will never OOB. There is a check above that there are at least 19 characters
-    // remaining.
+    // remaining, and the caller guarantees that they are all writable.
     unsafe {
         if n >= 1e16 as u64 {
             let to_parse = n % 1e16 as u64;
@@ -591,57 +649,94 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 /// into at most 2 u64s, and then chunks by 10e16, 10e8, 10e4, 10e2, and then 10e1.
 /// It also has to handle 1 last item, as 10^40 > 2^128 > 10^39, whereas
 /// 10^20 > 2^64 > 10^19.
-fn fmt_u128(n: u128, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    // 2^128 is about 3*10^38, so 39 gives an extra byte of space
-    let mut buf = [MaybeUninit::<u8>::uninit(); 39];
-    let mut curr = buf.len() as isize;
+impl DecimalDigits for u128 {
+    unsafe fn write_digits(self, buf_ptr: *mut u8, mut curr: isize) -> isize {
+        let end = curr;
+        let (n, rem) = udiv_1e19(self);
+        // SAFETY: The caller guarantees that `buf_ptr[..curr]` is writable and has room for all
+        // the digits. `parse_u64_into` wants room for more than 19 digits, so it's only used
+        // for chunks that are followed by more digits: the number has at least 20 digits before
+        // the first call, and at least 39 before the second one.
+        unsafe {
+            if n == 0 {
+                return rem.write_digits(buf_ptr, curr);
+            }
+            parse_u64_into(rem, buf_ptr, &mut curr);
 
-    let (n, rem) = udiv_1e19(n);
-    parse_u64_into(rem, &mut buf, &mut curr);
+            // 0 pad up to point
+            let target = end - 19;
+            ptr::write_bytes(buf_ptr.offset(target), b'0', (curr - target) as usize);
+            curr = target;
 
-    if n != 0 {
-        // 0 pad up to point
-        let target = (buf.len() - 19) as isize;
-        // SAFETY: Guaranteed that we wrote at most 19 bytes, and there must be space
-        // remaining since it has length 39
-        unsafe {
-            ptr::write_bytes(
-                MaybeUninit::slice_as_mut_ptr(&mut buf).offset(target),
-                b'0',
-                (curr - target) as usize,
-            );
-        }
-        curr = target;
-
-        let (n, rem) = udiv_1e19(n);
-        parse_u64_into(rem, &mut buf, &mut curr);
-        // Should this following branch be annotated with unlikely?
-        if n != 0 {
-            let target = (buf.len() - 38) as isize;
-            // The raw `buf_ptr` pointer is only valid until `buf` is used the next time,
-            // buf `buf` is not used in this scope so we are good.
-            let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
-            // SAFETY: At this point we wrote at most 38 bytes, pad up to that point,
-            // There can only be at most 1 digit remaining.
-            unsafe {
-                ptr::write_bytes(buf_ptr.offset(target), b'0', (curr - target) as usize);
-                curr = target - 1;
-                *buf_ptr.offset(curr) = (n as u8) + b'0';
+            let (n, rem) = udiv_1e19(n);
+            if n == 0 {
+                return rem.write_digits(buf_ptr, curr);
             }
+            parse_u64_into(rem, buf_ptr, &mut curr);
+
+            // At this point we wrote 38 digits, pad up to that point. There can only be at most
+            // 1 digit remaining.
+            let target = end - 38;
+            ptr::write_bytes(buf_ptr.offset(target), b'0', (curr - target) as usize);
+            curr = target - 1;
+            *buf_ptr.offset(curr) = (n as u8) + b'0';
         }
+        curr
     }
+}
+
+fn fmt_u128(n: u128, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // 2^128 is about 3*10^38, so 39 gives an extra byte of space
+    let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+    let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
+    // SAFETY: `buf` has room for 39 digits, and `n < 2^128 < 10^39`.
+    let curr = unsafe { n.write_digits(buf_ptr, buf.len() as isize) };
 
     // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid
     // UTF-8 since `DEC_DIGITS_LUT` is
     let buf_slice = unsafe {
         str::from_utf8_unchecked(slice::from_raw_parts(
-            MaybeUninit::slice_as_mut_ptr(&mut buf).offset(curr),
+            buf_ptr.offset(curr),
             buf.len() - curr as usize,
         ))
     };
     f.pad_integral(is_nonnegative, "", buf_slice)
 }
 
+impl FormatInto for u128 {
+    unsafe fn format_into(self, buf: &mut [u8]) -> &str {
+        // SAFETY: The caller guarantees that `buf` has room for all the digits, and all the
+        // chars are valid UTF-8 since `DEC_DIGITS_LUT` is
+        unsafe {
+            let curr = self.write_digits(buf.as_mut_ptr(), buf.len() as isize);
+            str::from_utf8_unchecked(&buf[curr as usize..])
+        }
+    }
+}
+
+impl FormatInto for i128 {
+    unsafe fn format_into(self, buf: &mut [u8]) -> &str {
+        let is_nonnegative = self >= 0;
+        let n = if is_nonnegative {
+            self.to_u128()
+        } else {
+            // convert the negative num to positive by summing 1 to it's 2 complement
+            (!self.to_u128()).wrapping_add(1)
+        };
+        let buf_ptr = buf.as_mut_ptr();
+        // SAFETY: The caller guarantees that `buf` has room for all the digits and the sign,
+        // and all the chars are valid UTF-8 since `DEC_DIGITS_LUT` is
+        unsafe {
+            let mut curr = n.write_digits(buf_ptr, buf.len() as isize);
+            if !is_nonnegative {
+                curr -= 1;
+                *buf_ptr.offset(curr) = b'-';
+            }
+            str::from_utf8_unchecked(&buf[curr as usize..])
+        }
+    }
+}
+
 /// Partition of `n` into n > 1e19 and rem <= 1e19
 fn udiv_1e19(n: u128) -> (u128, u64) {
     const DIV: u64 = 1e19 as u64;
@@ -0,0 +1,138 @@
+//! Converting hexadecimal strings such as `1.8p3` into floating point numbers.
+//!
+//! Unlike decimal, every hexadecimal digit maps to exactly four bits, so this needs neither
+//! approximations nor bignums: we collect the leading digits into a `u64`, remember whether any
+//! of the digits that didn't fit were non-zero, and round once at the end.
+
+use crate::cmp;
+use crate::num::dec2flt::parse::Sign;
+use crate::num::dec2flt::rawfp::{encode_normal, encode_subnormal, RawFloat, Unpacked};
+use crate::num::dec2flt::{extract_sign, pfe_empty, pfe_invalid, ParseFloatError};
+
+/// Binary exponents beyond this are certainly infinity or zero, whatever the digits are.
+/// Clamping to it keeps the exponent arithmetic from overflowing.
+const MAX_ABS_EXP: i64 = 1 << 32;
+
+/// Converts a hexadecimal string into a floating point number.
+pub fn hex2flt<T: RawFloat>(s: &str) -> Result<T, ParseFloatError> {
+    if s.is_empty() {
+        return Err(pfe_empty());
+    }
+    let (sign, s) = extract_sign(s);
+    let flt = match s {
+        "inf" => T::INFINITY,
+        "NaN" => T::NAN,
+        _ => parse_hex(s.as_bytes()).ok_or_else(pfe_invalid)?,
+    };
+
+    match sign {
+        Sign::Positive => Ok(flt),
+        Sign::Negative => Ok(-flt),
+    }
+}
+
+/// Parses `(hexdigits | hexdigits? '.' hexdigits?) (('p' | 'P') ('+' | '-')? digits)?`, with at
+/// least one hexadecimal digit, and rounds the value to the closest `T`.
+fn parse_hex<T: RawFloat>(s: &[u8]) -> Option<T> {
+    // The leading digits that fit, and whether any of the ones after them are non-zero.
+    let mut sig: u64 = 0;
+    let mut sticky = false;
+    // The value is `sig * 2^exp`, give or take the sticky digits.
+    let mut exp: i64 = 0;
+    let mut any_digits = false;
+    let mut seen_point = false;
+
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] == b'.' && !seen_point {
+            seen_point = true;
+        } else if let Some(d) = (s[i] as char).to_digit(16) {
+            any_digits = true;
+            if sig >> 60 == 0 {
+                sig = sig << 4 | d as u64;
+                if seen_point {
+                    exp = exp.saturating_sub(4);
+                }
+            } else {
+                sticky |= d != 0;
+                if !seen_point {
+                    exp = exp.saturating_add(4);
+                }
+            }
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if !any_digits {
+        return None;
+    }
+
+    match s.get(i) {
+        None => {}
+        Some(&b'p' | &b'P') => {
+            let (sign, digits) = match s.get(i + 1) {
+                Some(&b'-') => (Sign::Negative, &s[i + 2..]),
+                Some(&b'+') => (Sign::Positive, &s[i + 2..]),
+                _ => (Sign::Positive, &s[i + 1..]),
+            };
+            if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+                return None;
+            }
+            let abs_exp = digits
+                .iter()
+                .fold(0, |acc, &d| cmp::min(acc * 10 + (d - b'0') as i64, MAX_ABS_EXP));
+            exp = match sign {
+                Sign::Positive => exp.saturating_add(abs_exp),
+                Sign::Negative => exp.saturating_sub(abs_exp),
+            };
+        }
+        Some(_) => return None,
+    }
+
+    if sig == 0 {
+        return Some(T::ZERO);
+    }
+    Some(round_to_float(sig, exp, sticky))
+}
+
+/// Rounds `sig * 2^exp` to the closest `T`, with half-to-even. `sticky` says whether the exact
+/// value is a tiny bit larger than that, which decides the ties.
+fn round_to_float<T: RawFloat>(sig: u64, exp: i64, sticky: bool) -> T {
+    let lz = sig.leading_zeros();
+    let sig = sig << lz;
+    let exp = exp - lz as i64;
+    // The value is now in `[2^(exp + 63), 2^(exp + 64))`.
+    let top = exp + 63;
+    if top > T::MAX_EXP as i64 {
+        return T::INFINITY;
+    }
+    // The place value of the last bit we can keep: normal numbers have `SIG_BITS` of them, while
+    // subnormals are cut off at a fixed exponent.
+    let ulp = cmp::max(top, T::MIN_EXP as i64) - (T::SIG_BITS as i64 - 1);
+    let shift = ulp - exp;
+    if shift > 64 {
+        // Less than half of the smallest subnormal.
+        return T::ZERO;
+    }
+    let (mut q, rem) =
+        if shift == 64 { (0, sig) } else { (sig >> shift, sig & ((1 << shift) - 1)) };
+    let half = 1 << (shift - 1);
+    if rem > half || (rem == half && (sticky || q % 2 == 1)) {
+        q += 1;
+    }
+    let mut k = ulp;
+    if q > T::MAX_SIG {
+        // Rounding up carried out of the significand, which leaves a power of two.
+        q >>= 1;
+        k += 1;
+    }
+
+    if q < T::MIN_SIG {
+        encode_subnormal(q)
+    } else if k > T::MAX_EXP_INT as i64 {
+        T::INFINITY
+    } else {
+        encode_normal(Unpacked::new(q, k as i16))
+    }
+}
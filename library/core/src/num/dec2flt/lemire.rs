@@ -0,0 +1,152 @@
+//! The Eisel-Lemire algorithm, which converts most decimals with a 64 bit multiplication or two.
+//!
+//! Described in "Number Parsing at a Gigabyte per Second" by Daniel Lemire,
+//! available online: <https://arxiv.org/abs/2101.11408>
+//!
+//! We multiply the (normalized) decimal significand `w` by a 128 bit approximation of `5^q` and
+//! read the float's significand off the top bits of the product. The approximation is off by less
+//! than one unit in its last place, so unless the bits we round by are right next to a halfway
+//! point, they decide the rounding correctly. When they might not, we give up and let the caller
+//! fall back to the slow but exact algorithms.
+
+use crate::num::dec2flt::num;
+use crate::num::dec2flt::rawfp::{encode_normal, encode_subnormal, RawFloat, Unpacked};
+use crate::num::dec2flt::table::{
+    LARGEST_POWER_OF_FIVE, POWERS_OF_FIVE_128, SMALLEST_POWER_OF_FIVE,
+};
+
+/// The number of decimal digits that certainly fit into a `u64`.
+const MAX_DIGITS: usize = 19;
+
+/// Tries to convert the decimal `integral.fractional * 10^e` to the closest float, where the
+/// first digit of `integral` (or `fractional`, if `integral` is empty) is not zero.
+///
+/// Returns `None` if the result can't be determined without looking at the digits more closely.
+pub fn eisel_lemire<T: RawFloat>(integral: &[u8], fractional: &[u8], e: i64) -> Option<T> {
+    let num_digits = integral.len() + fractional.len();
+    if num_digits <= MAX_DIGITS {
+        let w = num::from_str_unchecked(integral.iter().chain(fractional.iter()));
+        return compute_float(w, e);
+    }
+    // Only the first 19 digits fit, so the decimal lies somewhere between `w * 10^q` and
+    // `(w + 1) * 10^q`. If both bounds round to the same float, so does the decimal.
+    let w = num::from_str_unchecked(integral.iter().chain(fractional.iter()).take(MAX_DIGITS));
+    let q = e.saturating_add((num_digits - MAX_DIGITS) as i64);
+    let lower = compute_float::<T>(w, q)?;
+    let upper = compute_float::<T>(w + 1, q)?;
+    if lower.to_bits() == upper.to_bits() { Some(lower) } else { None }
+}
+
+/// Converts `w * 10^q` to the closest float, or returns `None` if the approximation of `5^q`
+/// isn't precise enough to tell. `w` must not be zero.
+fn compute_float<T: RawFloat>(mut w: u64, q: i64) -> Option<T> {
+    debug_assert!(w != 0);
+    // Only `w < 10^19` are handed to us, so these shortcuts don't depend on `w`.
+    if q < T::SMALLEST_POWER_OF_TEN {
+        return Some(T::ZERO);
+    } else if q > T::LARGEST_POWER_OF_TEN {
+        return Some(T::INFINITY);
+    }
+    let q = q as i32;
+
+    // Normalize the significand so that the most significant bit is set.
+    let lz = w.leading_zeros();
+    w <<= lz;
+
+    // We need the explicit significand bits, plus the hidden bit, plus one bit to round by,
+    // plus one in case the product has a leading zero.
+    let (lo, hi) = compute_product_approx(q, w, T::EXPLICIT_SIG_BITS as u32 + 3);
+    if lo == u64::MAX {
+        // The truncated bits of the product are all ones, so the error in the approximation
+        // could carry all the way into the significand. This can't happen when `5^q` is exact
+        // or its reciprocal is close enough to exact.
+        if !(-27..=55).contains(&q) {
+            return None;
+        }
+    }
+
+    // Keep one bit more than the significand needs, to round by.
+    let upper_bit = (hi >> 63) as i32;
+    let shift = upper_bit + 64 - T::EXPLICIT_SIG_BITS as i32 - 3;
+    let mut sig = hi >> shift;
+    // The biased exponent of the result, if it is normal.
+    let mut exp = power_of_two(q) + upper_bit - lz as i32 + T::MAX_EXP as i32;
+    if exp <= 0 {
+        // The result is subnormal, or rounds to zero.
+        if -exp + 1 >= 64 {
+            return Some(T::ZERO);
+        }
+        sig >>= -exp + 1;
+        // Round half up. Ties can't occur here, since `5^q` doesn't fit into the product exactly
+        // for the powers of ten that small.
+        sig += sig & 1;
+        sig >>= 1;
+        return Some(if sig < T::MIN_SIG {
+            encode_subnormal(sig)
+        } else {
+            // Rounding carried into the hidden bit, which gives the smallest normal number.
+            encode_normal(Unpacked::new(sig, T::MIN_EXP_INT))
+        });
+    }
+
+    // The product is exact when `5^q` fits into 64 bits, in which case the decimal may be exactly
+    // halfway between two floats: the bit we round by is set, and all bits below it are zero.
+    // Those ties round to even, so clear the rounding bit if the significand is even.
+    if lo <= 1
+        && q >= T::MIN_EXPONENT_ROUND_TO_EVEN
+        && q <= T::MAX_EXPONENT_ROUND_TO_EVEN
+        && sig & 3 == 1
+        && sig << shift == hi
+    {
+        sig &= !1;
+    }
+
+    sig += sig & 1;
+    sig >>= 1;
+    if sig > T::MAX_SIG {
+        // Rounding up carried out of the significand.
+        sig = T::MIN_SIG;
+        exp += 1;
+    }
+    if exp >= T::MAX_ENCODED_EXP as i32 {
+        return Some(T::INFINITY);
+    }
+    // Undo the bias and the shift into the integral representation.
+    let k = exp - T::MAX_EXP as i32 - T::EXPLICIT_SIG_BITS as i32;
+    Some(encode_normal(Unpacked::new(sig, k as i16)))
+}
+
+/// Calculates `floor(log2(10^q)) + 63`, exactly for all `q` we care about.
+#[inline]
+fn power_of_two(q: i32) -> i32 {
+    (q.wrapping_mul(152_170 + 65536) >> 16) + 63
+}
+
+/// Returns the low and the high 64 bits of `a * b`.
+#[inline]
+fn full_multiplication(a: u64, b: u64) -> (u64, u64) {
+    let r = (a as u128) * (b as u128);
+    (r as u64, (r >> 64) as u64)
+}
+
+/// Approximates the 128 most significant bits of `w * 5^q`, where only the top `precision` bits
+/// need to be right, and returns them as the low and the high 64 bits.
+fn compute_product_approx(q: i32, w: u64, precision: u32) -> (u64, u64) {
+    debug_assert!(q >= SMALLEST_POWER_OF_FIVE);
+    debug_assert!(q <= LARGEST_POWER_OF_FIVE);
+    debug_assert!(precision < 64);
+
+    let mask = u64::MAX >> precision;
+    let (hi5, lo5) = POWERS_OF_FIVE_128[(q - SMALLEST_POWER_OF_FIVE) as usize];
+    let (mut first_lo, mut first_hi) = full_multiplication(w, hi5);
+    if first_hi & mask == mask {
+        // The bits below `precision` are all ones, so the low word of `5^q` could still carry
+        // into them. Add the high half of its product, which is all that can matter.
+        let (_, second_hi) = full_multiplication(w, lo5);
+        first_lo = first_lo.wrapping_add(second_hi);
+        if second_hi > first_lo {
+            first_hi += 1;
+        }
+    }
+    (first_lo, first_hi)
+}
@@ -31,6 +31,11 @@
 //! simple but very slow algorithm that involved computing `f * 10^e` fully and doing an iterative
 //! search for the best approximation.
 //!
+//! Between the first of those special cases and the rest sits the Eisel-Lemire algorithm (see the
+//! `lemire` module), which handles almost all inputs that are left with a 128 bit approximation
+//! of `10^e`. It only passes on inputs that are very close to halfway between two floats, and
+//! inputs with more than 19 digits that it can't round without looking at all of them.
+//!
 //! Primarily, this module and its children implement the algorithms described in:
 //! "How to Read Floating Point Numbers Accurately" by William D. Clinger,
 //! available online: <http://citeseerx.ist.psu.edu/viewdoc/summary?doi=10.1.1.45.4152>
@@ -87,11 +92,14 @@
 use crate::fmt;
 use crate::str::FromStr;
 
+pub(crate) use self::hex::hex2flt;
 use self::num::digits_to_big;
 use self::parse::{parse_decimal, Decimal, ParseResult, Sign};
 use self::rawfp::RawFloat;
 
 mod algorithm;
+mod hex;
+mod lemire;
 mod num;
 mod table;
 // These two have their own tests.
@@ -266,6 +274,9 @@ fn convert<T: RawFloat>(mut decimal: Decimal<'_>) -> Result<T, ParseFloatError>
     if let Some(x) = algorithm::fast_path(decimal.integral, decimal.fractional, e) {
         return Ok(x);
     }
+    if let Some(x) = lemire::eisel_lemire(decimal.integral, decimal.fractional, e) {
+        return Ok(x);
+    }
     // Big32x40 is limited to 1280 bits, which translates to about 385 decimal digits.
     // If we exceed this, we'll crash, so we error out before getting too close (within 10^10).
     let upper_bound = bound_intermediate_digits(&decimal, e);
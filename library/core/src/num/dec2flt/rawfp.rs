@@ -52,7 +52,7 @@ pub trait RawFloat:
     const ZERO: Self;
 
     /// Type used by `to_bits` and `from_bits`.
-    type Bits: Add<Output = Self::Bits> + From<u8> + TryFrom<u64>;
+    type Bits: Add<Output = Self::Bits> + From<u8> + TryFrom<u64> + PartialEq;
 
     /// Performs a raw transmutation to an integer.
     fn to_bits(self) -> Self::Bits;
@@ -93,6 +93,18 @@ pub trait RawFloat:
     /// is certainly rounded to zero.
     const ZERO_CUTOFF: i64;
 
+    /// Decimal exponents below this always round to zero in the Eisel-Lemire algorithm, which
+    /// only deals with up to 19 decimal digits.
+    const SMALLEST_POWER_OF_TEN: i64;
+
+    /// Decimal exponents above this always round to infinity.
+    const LARGEST_POWER_OF_TEN: i64;
+
+    /// The range of decimal exponents `q` for which `w * 10^q` can be exactly halfway between two
+    /// floats for a 64 bit `w`, i.e., for which the ties in the Eisel-Lemire algorithm can be real.
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32;
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32;
+
     /// The number of bits in the exponent.
     const EXP_BITS: u8;
 
@@ -151,6 +163,10 @@ impl RawFloat for f32 {
     const MAX_NORMAL_DIGITS: usize = 35;
     const INF_CUTOFF: i64 = 40;
     const ZERO_CUTOFF: i64 = -48;
+    const SMALLEST_POWER_OF_TEN: i64 = -65;
+    const LARGEST_POWER_OF_TEN: i64 = 38;
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32 = -17;
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32 = 10;
     other_constants!(f32);
 
     /// Returns the mantissa, exponent and sign as integers.
@@ -200,6 +216,10 @@ impl RawFloat for f64 {
     const MAX_NORMAL_DIGITS: usize = 305;
     const INF_CUTOFF: i64 = 310;
     const ZERO_CUTOFF: i64 = -326;
+    const SMALLEST_POWER_OF_TEN: i64 = -342;
+    const LARGEST_POWER_OF_TEN: i64 = 308;
+    const MIN_EXPONENT_ROUND_TO_EVEN: i32 = -4;
+    const MAX_EXPONENT_ROUND_TO_EVEN: i32 = 23;
     other_constants!(f64);
 
     /// Returns the mantissa, exponent and sign as integers.
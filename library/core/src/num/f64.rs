@@ -15,6 +15,7 @@
 use crate::intrinsics;
 use crate::mem;
 use crate::num::FpCategory;
+use crate::num::ParseFloatError;
 
 /// The radix or base of the internal representation of `f64`.
 /// Use [`f64::RADIX`](../../std/primitive.f64.html#associatedconstant.RADIX) instead.
@@ -872,6 +873,75 @@ pub const fn from_bits(v: u64) -> Self {
         Self::from_bits(u64::from_ne_bytes(bytes))
     }
 
+    /// Parses a hexadecimal float such as `1.8p3`, which is `1.5 * 2^3`.
+    ///
+    /// This accepts the same strings as parsing a decimal float with [`str::parse`], except
+    /// that the digits are hexadecimal and the exponent, which is introduced by `p` or `P`
+    /// instead of `e` or `E`, stands for a power of two. Like `from_str_radix` on the integer
+    /// types, it doesn't accept a `0x` prefix. The result is always the closest `f64`, with
+    /// ties rounded to even.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(float_from_str_hex)]
+    /// assert_eq!(f64::from_str_hex("1.8p3"), Ok(12.0));
+    /// assert_eq!(f64::from_str_hex("-ff"), Ok(-255.0));
+    /// assert_eq!(f64::from_str_hex(".4"), Ok(0.25));
+    /// assert_eq!(f64::from_str_hex("1p-1074"), Ok(f64::from_bits(1)));
+    /// assert!(f64::from_str_hex("0x1p0").is_err());
+    /// ```
+    #[unstable(feature = "float_from_str_hex", issue = "none")]
+    #[inline]
+    pub fn from_str_hex(src: &str) -> Result<f64, ParseFloatError> {
+        crate::num::dec2flt::hex2flt(src)
+    }
+
+    /// Writes `self` into `buf` the way the `Display` implementation does, and returns the
+    /// written part of it.
+    ///
+    /// This produces the same shortest digits that parse back to `self`, but doesn't go through
+    /// a `Formatter`, which makes it considerably cheaper when all that's needed is the text of
+    /// the number. `buf` is long enough for any `f64`. Since this never uses exponential
+    /// notation, very large and very small numbers take many digits; see
+    /// [`to_shortest_exp_str`](#method.to_shortest_exp_str) for a more compact form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(num_format_into)]
+    /// let mut buf = [0; 327];
+    /// assert_eq!(0.3f64.to_shortest_str(&mut buf), "0.3");
+    /// assert_eq!((-1e10f64).to_shortest_str(&mut buf), "-10000000000");
+    /// assert_eq!(f64::MIN_POSITIVE.to_shortest_str(&mut buf), f64::MIN_POSITIVE.to_string());
+    /// ```
+    #[unstable(feature = "num_format_into", issue = "none")]
+    #[inline]
+    pub fn to_shortest_str(self, buf: &mut [u8; 327]) -> &str {
+        crate::fmt::float::float_to_decimal_str(self, buf)
+    }
+
+    /// Writes `self` into `buf` the way the `LowerExp` implementation does, and returns the
+    /// written part of it.
+    ///
+    /// Like [`to_shortest_str`](#method.to_shortest_str), this produces the shortest digits
+    /// that parse back to `self`, but always in exponential notation, which keeps the text
+    /// short for any `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(num_format_into)]
+    /// let mut buf = [0; 24];
+    /// assert_eq!(1234.5f64.to_shortest_exp_str(&mut buf), "1.2345e3");
+    /// assert_eq!(f64::MAX.to_shortest_exp_str(&mut buf), "1.7976931348623157e308");
+    /// ```
+    #[unstable(feature = "num_format_into", issue = "none")]
+    #[inline]
+    pub fn to_shortest_exp_str(self, buf: &mut [u8; 24]) -> &str {
+        crate::fmt::float::float_to_exponential_str(self, buf)
+    }
+
     /// Returns an ordering between self and other values.
     /// Unlike the standard partial comparison between floating point numbers,
     /// this comparison always produces an ordering in accordance to
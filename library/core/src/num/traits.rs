@@ -0,0 +1,486 @@
+//! Traits for writing code that is generic over the primitive integer types.
+//!
+//! The integer types all have the same inherent methods, generated by `int_impl!` and
+//! `uint_impl!`, but inherent methods can't be used through a type parameter. The traits in
+//! this module expose those methods, so that code like a bit packer or a checksum can be
+//! written once for every width instead of once per type or with a macro.
+//!
+//! The traits are sealed: they are only implemented by the primitive integer types, so new
+//! methods can be added to them without breaking anything.
+
+use crate::borrow::{Borrow, BorrowMut};
+use crate::convert::TryFrom;
+use crate::fmt;
+use crate::hash::Hash;
+use crate::iter::{Product, Sum};
+use crate::num::ParseIntError;
+use crate::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div,
+    DivAssign, Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub,
+    SubAssign,
+};
+use crate::str::FromStr;
+
+mod private {
+    /// Keeps the integer traits from being implemented outside of `core`.
+    pub trait Sealed {}
+}
+
+/// A primitive integer type, signed or unsigned.
+///
+/// Every method forwards to the inherent method of the same name, which documents it in detail.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(int_traits)]
+/// use core::num::Integer;
+///
+/// fn popcount<T: Integer>(values: &[T]) -> u32 {
+///     values.iter().map(|v| v.count_ones()).sum()
+/// }
+///
+/// fn parse_hex<T: Integer>(src: &str) -> Option<T> {
+///     T::from_str_radix(src, 16).ok()
+/// }
+///
+/// assert_eq!(popcount(&[0b1011u8, 0xff]), 11);
+/// assert_eq!(popcount(&[-1i64]), 64);
+/// assert_eq!(parse_hex::<u8>("ff"), Some(255));
+/// assert_eq!(parse_hex::<i8>("ff"), None);
+/// ```
+#[unstable(feature = "int_traits", issue = "none")]
+pub trait Integer:
+    private::Sealed
+    + Copy
+    + Eq
+    + Ord
+    + Hash
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + fmt::Binary
+    + fmt::Octal
+    + fmt::LowerHex
+    + fmt::UpperHex
+    + FromStr<Err = ParseIntError>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Not<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + RemAssign
+    + BitAndAssign
+    + BitOrAssign
+    + BitXorAssign
+    + ShlAssign<u32>
+    + ShrAssign<u32>
+    + Sum
+    + Product
+    + From<bool>
+    + TryFrom<u8>
+    + TryFrom<i8>
+    + TryFrom<u16>
+    + TryFrom<i16>
+    + TryFrom<u32>
+    + TryFrom<i32>
+    + TryFrom<u64>
+    + TryFrom<i64>
+    + TryFrom<u128>
+    + TryFrom<i128>
+    + TryFrom<usize>
+    + TryFrom<isize>
+{
+    /// The memory representation of the integer as a byte array, such as `[u8; 4]` for `u32`.
+    type Bytes: Copy
+        + Eq
+        + Ord
+        + Hash
+        + Default
+        + fmt::Debug
+        + AsRef<[u8]>
+        + AsMut<[u8]>
+        + Borrow<[u8]>
+        + BorrowMut<[u8]>
+        + for<'a> TryFrom<&'a [u8]>;
+
+    /// The size of this integer type in bits.
+    const BITS: u32;
+    /// The smallest value that can be represented by this integer type.
+    const MIN: Self;
+    /// The largest value that can be represented by this integer type.
+    const MAX: Self;
+    /// The number `0`.
+    const ZERO: Self;
+    /// The number `1`.
+    const ONE: Self;
+
+    /// Converts a string slice in a given base to an integer.
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+
+    /// Returns the number of ones in the binary representation of `self`.
+    fn count_ones(self) -> u32;
+    /// Returns the number of zeros in the binary representation of `self`.
+    fn count_zeros(self) -> u32;
+    /// Returns the number of leading zeros in the binary representation of `self`.
+    fn leading_zeros(self) -> u32;
+    /// Returns the number of trailing zeros in the binary representation of `self`.
+    fn trailing_zeros(self) -> u32;
+    /// Returns the number of leading ones in the binary representation of `self`.
+    fn leading_ones(self) -> u32;
+    /// Returns the number of trailing ones in the binary representation of `self`.
+    fn trailing_ones(self) -> u32;
+    /// Shifts the bits to the left by `n`, wrapping the truncated bits to the end.
+    fn rotate_left(self, n: u32) -> Self;
+    /// Shifts the bits to the right by `n`, wrapping the truncated bits to the beginning.
+    fn rotate_right(self, n: u32) -> Self;
+    /// Reverses the byte order of the integer.
+    fn swap_bytes(self) -> Self;
+    /// Reverses the order of bits in the integer.
+    fn reverse_bits(self) -> Self;
+    /// Converts an integer from big endian to the target's endianness.
+    fn from_be(x: Self) -> Self;
+    /// Converts an integer from little endian to the target's endianness.
+    fn from_le(x: Self) -> Self;
+    /// Converts `self` to big endian from the target's endianness.
+    fn to_be(self) -> Self;
+    /// Converts `self` to little endian from the target's endianness.
+    fn to_le(self) -> Self;
+
+    /// Raises `self` to the power of `exp`, using exponentiation by squaring.
+    fn pow(self, exp: u32) -> Self;
+    /// Calculates the quotient of Euclidean division of `self` by `rhs`.
+    fn div_euclid(self, rhs: Self) -> Self;
+    /// Calculates the least nonnegative remainder of `self (mod rhs)`.
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    /// Checked integer addition. Returns `None` if overflow occurred.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Checked integer subtraction. Returns `None` if overflow occurred.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// Checked integer multiplication. Returns `None` if overflow occurred.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Checked integer division. Returns `None` if `rhs == 0` or the division results in
+    /// overflow.
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    /// Checked integer remainder. Returns `None` if `rhs == 0` or the division results in
+    /// overflow.
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    /// Checked Euclidean division. Returns `None` if `rhs == 0` or the division results in
+    /// overflow.
+    fn checked_div_euclid(self, rhs: Self) -> Option<Self>;
+    /// Checked Euclidean remainder. Returns `None` if `rhs == 0` or the division results in
+    /// overflow.
+    fn checked_rem_euclid(self, rhs: Self) -> Option<Self>;
+    /// Checked negation. Returns `None` unless `self` can be negated.
+    fn checked_neg(self) -> Option<Self>;
+    /// Checked shift left. Returns `None` if `rhs` is at least the number of bits in `self`.
+    fn checked_shl(self, rhs: u32) -> Option<Self>;
+    /// Checked shift right. Returns `None` if `rhs` is at least the number of bits in `self`.
+    fn checked_shr(self, rhs: u32) -> Option<Self>;
+    /// Checked exponentiation. Returns `None` if overflow occurred.
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+
+    /// Wrapping (modular) addition.
+    fn wrapping_add(self, rhs: Self) -> Self;
+    /// Wrapping (modular) subtraction.
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    /// Wrapping (modular) multiplication.
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    /// Wrapping (modular) division.
+    fn wrapping_div(self, rhs: Self) -> Self;
+    /// Wrapping (modular) remainder.
+    fn wrapping_rem(self, rhs: Self) -> Self;
+    /// Wrapping (modular) negation.
+    fn wrapping_neg(self) -> Self;
+    /// Panic-free bitwise shift-left, which masks `rhs` to the number of bits in `self`.
+    fn wrapping_shl(self, rhs: u32) -> Self;
+    /// Panic-free bitwise shift-right, which masks `rhs` to the number of bits in `self`.
+    fn wrapping_shr(self, rhs: u32) -> Self;
+    /// Wrapping (modular) exponentiation.
+    fn wrapping_pow(self, exp: u32) -> Self;
+
+    /// Saturating integer addition, which stops at the numeric bounds instead of overflowing.
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Saturating integer subtraction, which stops at the numeric bounds instead of
+    /// overflowing.
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Saturating integer multiplication, which stops at the numeric bounds instead of
+    /// overflowing.
+    fn saturating_mul(self, rhs: Self) -> Self;
+    /// Saturating integer exponentiation, which stops at the numeric bounds instead of
+    /// overflowing.
+    fn saturating_pow(self, exp: u32) -> Self;
+
+    /// Calculates `self + rhs`, and whether an arithmetic overflow occurred.
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+    /// Calculates `self - rhs`, and whether an arithmetic overflow occurred.
+    fn overflowing_sub(self, rhs: Self) -> (Self, bool);
+    /// Calculates `self * rhs`, and whether an arithmetic overflow occurred.
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool);
+    /// Calculates `self / rhs`, and whether an arithmetic overflow occurred.
+    fn overflowing_div(self, rhs: Self) -> (Self, bool);
+    /// Calculates `self % rhs`, and whether an arithmetic overflow occurred.
+    fn overflowing_rem(self, rhs: Self) -> (Self, bool);
+    /// Negates `self`, and returns whether an overflow occurred.
+    fn overflowing_neg(self) -> (Self, bool);
+    /// Shifts `self` left by `rhs` bits, and returns whether `rhs` was too large.
+    fn overflowing_shl(self, rhs: u32) -> (Self, bool);
+    /// Shifts `self` right by `rhs` bits, and returns whether `rhs` was too large.
+    fn overflowing_shr(self, rhs: u32) -> (Self, bool);
+    /// Raises `self` to the power of `exp`, and returns whether an overflow occurred.
+    fn overflowing_pow(self, exp: u32) -> (Self, bool);
+
+    /// Returns the memory representation of the integer as a byte array in big-endian
+    /// (network) byte order.
+    fn to_be_bytes(self) -> Self::Bytes;
+    /// Returns the memory representation of the integer as a byte array in little-endian
+    /// byte order.
+    fn to_le_bytes(self) -> Self::Bytes;
+    /// Returns the memory representation of the integer as a byte array in native byte order.
+    fn to_ne_bytes(self) -> Self::Bytes;
+    /// Creates an integer from its representation as a byte array in big endian.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+    /// Creates an integer from its representation as a byte array in little endian.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+    /// Creates an integer from its memory representation as a byte array in native endianness.
+    fn from_ne_bytes(bytes: Self::Bytes) -> Self;
+}
+
+/// A primitive signed integer type.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(int_traits)]
+/// use core::num::SignedInteger;
+///
+/// fn zigzag<T: SignedInteger>(n: T) -> T::Unsigned {
+///     let sign = n >> (T::BITS - 1);
+///     ((n << 1) ^ sign).cast_unsigned()
+/// }
+///
+/// assert_eq!(zigzag(0i8), 0u8);
+/// assert_eq!(zigzag(-1i32), 1u32);
+/// assert_eq!(zigzag(1i64), 2u64);
+/// assert_eq!(zigzag(i16::MIN), u16::MAX);
+/// ```
+#[unstable(feature = "int_traits", issue = "none")]
+pub trait SignedInteger: Integer + Neg<Output = Self> {
+    /// The unsigned integer type of the same size, such as `u32` for `i32`.
+    type Unsigned: UnsignedInteger;
+
+    /// Reinterprets the bits of `self` as the unsigned integer type of the same size, like an
+    /// `as` cast.
+    fn cast_unsigned(self) -> Self::Unsigned;
+
+    /// Computes the absolute value of `self`.
+    fn abs(self) -> Self;
+    /// Returns a number representing the sign of `self`: `-1`, `0` or `1`.
+    fn signum(self) -> Self;
+    /// Returns `true` if `self` is positive and `false` if the number is zero or negative.
+    fn is_positive(self) -> bool;
+    /// Returns `true` if `self` is negative and `false` if the number is zero or positive.
+    fn is_negative(self) -> bool;
+    /// Computes the absolute value of `self` without any wrapping or panicking.
+    fn unsigned_abs(self) -> Self::Unsigned;
+    /// Checked absolute value. Returns `None` if `self == MIN`.
+    fn checked_abs(self) -> Option<Self>;
+    /// Wrapping (modular) absolute value.
+    fn wrapping_abs(self) -> Self;
+    /// Saturating absolute value, which returns `MAX` instead of overflowing for `MIN`.
+    fn saturating_abs(self) -> Self;
+    /// Saturating negation, which returns `MAX` instead of overflowing for `MIN`.
+    fn saturating_neg(self) -> Self;
+    /// Computes the absolute value of `self`, and returns whether an overflow occurred.
+    fn overflowing_abs(self) -> (Self, bool);
+}
+
+/// A primitive unsigned integer type.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(int_traits)]
+/// use core::num::UnsignedInteger;
+///
+/// fn checksum<T: UnsignedInteger>(data: &[T]) -> T {
+///     data.iter().fold(T::ZERO, |sum, &word| sum.wrapping_add(word).rotate_left(1))
+/// }
+///
+/// assert_eq!(checksum(&[0x80u8, 0x01]), 0x04);
+/// assert_eq!(checksum(&[0x8000u16, 0x0001]), 0x0004);
+/// ```
+#[unstable(feature = "int_traits", issue = "none")]
+pub trait UnsignedInteger: Integer {
+    /// The signed integer type of the same size, such as `i32` for `u32`.
+    type Signed: SignedInteger;
+
+    /// Reinterprets the bits of `self` as the signed integer type of the same size, like an
+    /// `as` cast.
+    fn cast_signed(self) -> Self::Signed;
+
+    /// Returns `true` if and only if `self == 2^k` for some `k`.
+    fn is_power_of_two(self) -> bool;
+    /// Returns the smallest power of two greater than or equal to `self`.
+    fn next_power_of_two(self) -> Self;
+    /// Returns the smallest power of two greater than or equal to `self`, or `None` if it
+    /// doesn't fit.
+    fn checked_next_power_of_two(self) -> Option<Self>;
+}
+
+// Forwards trait methods to the inherent methods of the same name.
+macro_rules! forward {
+    ($T:ty; $(fn $name:ident($($arg:ident: $arg_ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            #[inline]
+            fn $name($($arg: $arg_ty),*) -> $ret {
+                <$T>::$name($($arg),*)
+            }
+        )*
+    };
+}
+
+macro_rules! integer_impl {
+    ($($T:ident)*) => {$(
+        impl private::Sealed for $T {}
+
+        #[unstable(feature = "int_traits", issue = "none")]
+        impl Integer for $T {
+            type Bytes = [u8; crate::mem::size_of::<$T>()];
+
+            const BITS: u32 = <$T>::BITS;
+            const MIN: Self = <$T>::MIN;
+            const MAX: Self = <$T>::MAX;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline]
+            fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError> {
+                <$T>::from_str_radix(src, radix)
+            }
+
+            forward! { $T;
+                fn count_ones(self: Self) -> u32;
+                fn count_zeros(self: Self) -> u32;
+                fn leading_zeros(self: Self) -> u32;
+                fn trailing_zeros(self: Self) -> u32;
+                fn leading_ones(self: Self) -> u32;
+                fn trailing_ones(self: Self) -> u32;
+                fn rotate_left(self: Self, n: u32) -> Self;
+                fn rotate_right(self: Self, n: u32) -> Self;
+                fn swap_bytes(self: Self) -> Self;
+                fn reverse_bits(self: Self) -> Self;
+                fn from_be(x: Self) -> Self;
+                fn from_le(x: Self) -> Self;
+                fn to_be(self: Self) -> Self;
+                fn to_le(self: Self) -> Self;
+
+                fn pow(self: Self, exp: u32) -> Self;
+                fn div_euclid(self: Self, rhs: Self) -> Self;
+                fn rem_euclid(self: Self, rhs: Self) -> Self;
+
+                fn checked_add(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_sub(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_mul(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_div(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_rem(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_div_euclid(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_rem_euclid(self: Self, rhs: Self) -> Option<Self>;
+                fn checked_neg(self: Self) -> Option<Self>;
+                fn checked_shl(self: Self, rhs: u32) -> Option<Self>;
+                fn checked_shr(self: Self, rhs: u32) -> Option<Self>;
+                fn checked_pow(self: Self, exp: u32) -> Option<Self>;
+
+                fn wrapping_add(self: Self, rhs: Self) -> Self;
+                fn wrapping_sub(self: Self, rhs: Self) -> Self;
+                fn wrapping_mul(self: Self, rhs: Self) -> Self;
+                fn wrapping_div(self: Self, rhs: Self) -> Self;
+                fn wrapping_rem(self: Self, rhs: Self) -> Self;
+                fn wrapping_neg(self: Self) -> Self;
+                fn wrapping_shl(self: Self, rhs: u32) -> Self;
+                fn wrapping_shr(self: Self, rhs: u32) -> Self;
+                fn wrapping_pow(self: Self, exp: u32) -> Self;
+
+                fn saturating_add(self: Self, rhs: Self) -> Self;
+                fn saturating_sub(self: Self, rhs: Self) -> Self;
+                fn saturating_mul(self: Self, rhs: Self) -> Self;
+                fn saturating_pow(self: Self, exp: u32) -> Self;
+
+                fn overflowing_add(self: Self, rhs: Self) -> (Self, bool);
+                fn overflowing_sub(self: Self, rhs: Self) -> (Self, bool);
+                fn overflowing_mul(self: Self, rhs: Self) -> (Self, bool);
+                fn overflowing_div(self: Self, rhs: Self) -> (Self, bool);
+                fn overflowing_rem(self: Self, rhs: Self) -> (Self, bool);
+                fn overflowing_neg(self: Self) -> (Self, bool);
+                fn overflowing_shl(self: Self, rhs: u32) -> (Self, bool);
+                fn overflowing_shr(self: Self, rhs: u32) -> (Self, bool);
+                fn overflowing_pow(self: Self, exp: u32) -> (Self, bool);
+
+                fn to_be_bytes(self: Self) -> Self::Bytes;
+                fn to_le_bytes(self: Self) -> Self::Bytes;
+                fn to_ne_bytes(self: Self) -> Self::Bytes;
+                fn from_be_bytes(bytes: Self::Bytes) -> Self;
+                fn from_le_bytes(bytes: Self::Bytes) -> Self;
+                fn from_ne_bytes(bytes: Self::Bytes) -> Self;
+            }
+        }
+    )*};
+}
+
+integer_impl! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+macro_rules! signed_integer_impl {
+    ($($T:ident => $U:ident)*) => {$(
+        #[unstable(feature = "int_traits", issue = "none")]
+        impl SignedInteger for $T {
+            type Unsigned = $U;
+
+            #[inline]
+            fn cast_unsigned(self) -> $U {
+                self as $U
+            }
+
+            forward! { $T;
+                fn abs(self: Self) -> Self;
+                fn signum(self: Self) -> Self;
+                fn is_positive(self: Self) -> bool;
+                fn is_negative(self: Self) -> bool;
+                fn unsigned_abs(self: Self) -> $U;
+                fn checked_abs(self: Self) -> Option<Self>;
+                fn wrapping_abs(self: Self) -> Self;
+                fn saturating_abs(self: Self) -> Self;
+                fn saturating_neg(self: Self) -> Self;
+                fn overflowing_abs(self: Self) -> (Self, bool);
+            }
+        }
+
+        #[unstable(feature = "int_traits", issue = "none")]
+        impl UnsignedInteger for $U {
+            type Signed = $T;
+
+            #[inline]
+            fn cast_signed(self) -> $T {
+                self as $T
+            }
+
+            forward! { $U;
+                fn is_power_of_two(self: Self) -> bool;
+                fn next_power_of_two(self: Self) -> Self;
+                fn checked_next_power_of_two(self: Self) -> Option<Self>;
+            }
+        }
+    )*};
+}
+
+signed_integer_impl! { i8 => u8 i16 => u16 i32 => u32 i64 => u64 i128 => u128 isize => usize }
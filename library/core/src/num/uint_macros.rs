@@ -1,6 +1,6 @@
 macro_rules! uint_impl {
-    ($SelfT:ty, $ActualT:ty, $BITS:expr, $MaxV:expr, $Feature:expr, $EndFeature:expr,
-        $rot:expr, $rot_op:expr, $rot_result:expr, $swap_op:expr, $swapped:expr,
+    ($SelfT:ty, $ActualT:ty, $BITS:expr, $max_str_len:expr, $MaxV:expr, $Feature:expr,
+        $EndFeature:expr, $rot:expr, $rot_op:expr, $rot_result:expr, $swap_op:expr, $swapped:expr,
         $reversed:expr, $le_bytes:expr, $be_bytes:expr,
         $to_xe_bytes_doc:expr, $from_xe_bytes_doc:expr) => {
         doc_comment! {
@@ -1963,6 +1963,31 @@ fn read_ne_", stringify!($SelfT), "(input: &mut &[u8]) -> ", stringify!($SelfT),
             }
         }
 
+        doc_comment! {
+            concat!("Writes `self` in decimal into `buf`, and returns the written part of it.
+
+This produces the same digits as the `Display` implementation, but doesn't go through a
+`Formatter`, which makes it considerably cheaper when all that's needed is the text of the
+number. `buf` is long enough for any `", stringify!($SelfT), "`; the digits are written to
+its end.
+
+# Examples
+
+```
+#![feature(num_format_into)]
+let mut buf = [0; ", stringify!($max_str_len), "];
+assert_eq!(42", stringify!($SelfT), ".format_into(&mut buf), \"42\");
+assert_eq!(", stringify!($SelfT), "::MAX.format_into(&mut buf), ", stringify!($SelfT),
+"::MAX.to_string());
+```"),
+            #[unstable(feature = "num_format_into", issue = "none")]
+            #[inline]
+            pub fn format_into(self, buf: &mut [u8; $max_str_len]) -> &str {
+                // SAFETY: `buf` is long enough for `MAX`, the longest value of this type.
+                unsafe { crate::fmt::num::FormatInto::format_into(self, buf) }
+            }
+        }
+
         doc_comment! {
             concat!("**This method is soft-deprecated.**
 
@@ -1,5 +1,6 @@
 //! Operations on ASCII `[u8]`.
 
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
 use crate::mem;
 
 #[lang = "slice_u8"]
@@ -57,8 +58,16 @@ pub fn make_ascii_lowercase(&mut self) {
     }
 }
 
+/// Vectorized ASCII test, see `super::simd`.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+#[inline]
+fn is_ascii(s: &[u8]) -> bool {
+    super::simd::first_non_ascii(s) == s.len()
+}
+
 /// Returns `true` if any byte in the word `v` is nonascii (>= 128). Snarfed
 /// from `../str/mod.rs`, which does something similar for utf8 validation.
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
 #[inline]
 fn contains_nonascii(v: usize) -> bool {
     const NONASCII_MASK: usize = 0x80808080_80808080u64 as usize;
@@ -77,6 +86,7 @@ fn contains_nonascii(v: usize) -> bool {
 ///
 /// If any of these loads produces something for which `contains_nonascii`
 /// (above) returns true, then we know the answer is false.
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
 #[inline]
 fn is_ascii(s: &[u8]) -> bool {
     const USIZE_SIZE: usize = mem::size_of::<usize>();
@@ -41,6 +41,24 @@ fn repeat_byte(b: u8) -> usize {
 /// Returns the first index matching the byte `x` in `text`.
 #[inline]
 pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+    return super::simd::memchr(x, text);
+    #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+    return memchr_fallback(x, text);
+}
+
+/// Returns the last index matching the byte `x` in `text`.
+#[inline]
+pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+    return super::simd::memrchr(x, text);
+    #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+    return memrchr_fallback(x, text);
+}
+
+/// Portable version of [`memchr`], for targets without a vectorized one.
+#[inline]
+pub fn memchr_fallback(x: u8, text: &[u8]) -> Option<usize> {
     // Fast path for small slices
     if text.len() < 2 * USIZE_BYTES {
         return text.iter().position(|elt| *elt == x);
@@ -90,8 +108,8 @@ fn memchr_general_case(x: u8, text: &[u8]) -> Option<usize> {
     text[offset..].iter().position(|elt| *elt == x).map(|i| offset + i)
 }
 
-/// Returns the last index matching the byte `x` in `text`.
-pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+/// Portable version of [`memrchr`], for targets without a vectorized one.
+pub fn memrchr_fallback(x: u8, text: &[u8]) -> Option<usize> {
     // Scan for a single byte value by reading two `usize` words at a time.
     //
     // Split `text` in three parts:
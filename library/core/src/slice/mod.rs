@@ -27,6 +27,16 @@
 /// Pure rust memchr implementation, taken from rust-memchr
 pub mod memchr;
 
+#[unstable(
+    feature = "slice_internals",
+    issue = "none",
+    reason = "exposed from core to be reused in std; use the memchr crate"
+)]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[doc(hidden)]
+/// SSE2 and AVX2 versions of the byte searches in `core`
+pub mod simd;
+
 mod ascii;
 mod cmp;
 pub(crate) mod index;
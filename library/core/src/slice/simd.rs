@@ -0,0 +1,466 @@
+//! SSE2 and AVX2 versions of the byte searches behind `memchr`, `memrchr`,
+//! `is_ascii`, UTF-8 validation and substring search.
+//!
+//! Every kernel is compiled twice with `#[target_feature]`, once per
+//! instruction set. The safe functions at the bottom of this module pick one
+//! at compile time, since `core` has no way to query the CPU. This means that
+//! `is_ascii`, `str::from_utf8` and the short-needle `str::find` only use
+//! the AVX2 kernels when the target enables AVX2, for example with
+//! `-C target-feature=+avx2`.
+//!
+//! Only `memchr` and `memrchr` are chosen at runtime, by `std`. Even then,
+//! this only happens on the platforms where `std` doesn't call the C
+//! library's `memchr`, such as Windows. On Unix, libc's own vectorized
+//! version is used instead.
+
+use crate::cmp;
+
+#[cfg(target_arch = "x86")]
+use crate::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use crate::arch::x86_64::*;
+
+/// The handful of vector operations the search loops are written in terms
+/// of, so that each loop only needs to be written once.
+///
+/// All methods are unsafe because they require the CPU to support the
+/// instruction set the vector type belongs to.
+trait Vector: Copy {
+    /// The number of bytes in a vector.
+    const BYTES: usize;
+
+    /// Creates a vector with every byte set to `byte`.
+    unsafe fn splat(byte: u8) -> Self;
+
+    /// Reads `Self::BYTES` bytes from `ptr`, which need not be aligned.
+    unsafe fn load(ptr: *const u8) -> Self;
+
+    /// Sets each byte to all ones if it equals the byte in `other` and to
+    /// zero otherwise.
+    unsafe fn cmpeq(self, other: Self) -> Self;
+
+    unsafe fn and(self, other: Self) -> Self;
+
+    unsafe fn or(self, other: Self) -> Self;
+
+    /// Gathers the top bit of every byte, with the first byte ending up in
+    /// the least significant bit.
+    unsafe fn movemask(self) -> u32;
+}
+
+impl Vector for __m128i {
+    const BYTES: usize = 16;
+
+    #[inline(always)]
+    unsafe fn splat(byte: u8) -> Self {
+        unsafe { _mm_set1_epi8(byte as i8) }
+    }
+
+    #[inline(always)]
+    unsafe fn load(ptr: *const u8) -> Self {
+        unsafe { _mm_loadu_si128(ptr as *const __m128i) }
+    }
+
+    #[inline(always)]
+    unsafe fn cmpeq(self, other: Self) -> Self {
+        unsafe { _mm_cmpeq_epi8(self, other) }
+    }
+
+    #[inline(always)]
+    unsafe fn and(self, other: Self) -> Self {
+        unsafe { _mm_and_si128(self, other) }
+    }
+
+    #[inline(always)]
+    unsafe fn or(self, other: Self) -> Self {
+        unsafe { _mm_or_si128(self, other) }
+    }
+
+    #[inline(always)]
+    unsafe fn movemask(self) -> u32 {
+        unsafe { _mm_movemask_epi8(self) as u32 }
+    }
+}
+
+impl Vector for __m256i {
+    const BYTES: usize = 32;
+
+    #[inline(always)]
+    unsafe fn splat(byte: u8) -> Self {
+        unsafe { _mm256_set1_epi8(byte as i8) }
+    }
+
+    #[inline(always)]
+    unsafe fn load(ptr: *const u8) -> Self {
+        unsafe { _mm256_loadu_si256(ptr as *const __m256i) }
+    }
+
+    #[inline(always)]
+    unsafe fn cmpeq(self, other: Self) -> Self {
+        unsafe { _mm256_cmpeq_epi8(self, other) }
+    }
+
+    #[inline(always)]
+    unsafe fn and(self, other: Self) -> Self {
+        unsafe { _mm256_and_si256(self, other) }
+    }
+
+    #[inline(always)]
+    unsafe fn or(self, other: Self) -> Self {
+        unsafe { _mm256_or_si256(self, other) }
+    }
+
+    #[inline(always)]
+    unsafe fn movemask(self) -> u32 {
+        unsafe { _mm256_movemask_epi8(self) as u32 }
+    }
+}
+
+// All the loops below share the same shape: walk `text` a vector at a time,
+// and instead of finishing with a scalar loop, read one last vector that ends
+// exactly at the end of `text`. That vector overlaps bytes which have already
+// been checked, but since those bytes didn't match the first time, they don't
+// change the answer.
+
+/// Returns the first index matching the byte `x` in `text`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set `V` belongs to.
+#[inline(always)]
+unsafe fn memchr_generic<V: Vector>(x: u8, text: &[u8]) -> Option<usize> {
+    let len = text.len();
+    if len < V::BYTES {
+        return text.iter().position(|elt| *elt == x);
+    }
+
+    let ptr = text.as_ptr();
+    // SAFETY: the caller guarantees that `V` is supported, and every load
+    // starts at most `len - V::BYTES` bytes into `text`.
+    unsafe {
+        let needle = V::splat(x);
+        let mut offset = 0;
+
+        // Look at four vectors per iteration, since the haystacks this gets
+        // used on tend to be long with few matches.
+        while offset + 4 * V::BYTES <= len {
+            let a = V::load(ptr.add(offset)).cmpeq(needle);
+            let b = V::load(ptr.add(offset + V::BYTES)).cmpeq(needle);
+            let c = V::load(ptr.add(offset + 2 * V::BYTES)).cmpeq(needle);
+            let d = V::load(ptr.add(offset + 3 * V::BYTES)).cmpeq(needle);
+            if a.or(b).or(c.or(d)).movemask() != 0 {
+                break;
+            }
+            offset += 4 * V::BYTES;
+        }
+
+        offset = cmp::min(offset, len - V::BYTES);
+        loop {
+            let mask = V::load(ptr.add(offset)).cmpeq(needle).movemask();
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            if offset == len - V::BYTES {
+                return None;
+            }
+            offset = cmp::min(offset + V::BYTES, len - V::BYTES);
+        }
+    }
+}
+
+/// Returns the last index matching the byte `x` in `text`.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set `V` belongs to.
+#[inline(always)]
+unsafe fn memrchr_generic<V: Vector>(x: u8, text: &[u8]) -> Option<usize> {
+    let len = text.len();
+    if len < V::BYTES {
+        return text.iter().rposition(|elt| *elt == x);
+    }
+
+    let ptr = text.as_ptr();
+    // SAFETY: the caller guarantees that `V` is supported, and every load
+    // starts at most `len - V::BYTES` bytes into `text`.
+    unsafe {
+        let needle = V::splat(x);
+        let mut offset = len - V::BYTES;
+        loop {
+            let mask = V::load(ptr.add(offset)).cmpeq(needle).movemask();
+            if mask != 0 {
+                return Some(offset + 31 - mask.leading_zeros() as usize);
+            }
+            if offset == 0 {
+                return None;
+            }
+            offset = offset.saturating_sub(V::BYTES);
+        }
+    }
+}
+
+/// Returns the index of the first byte in `text` that is not ASCII, or
+/// `text.len()` if there is none.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set `V` belongs to.
+#[inline(always)]
+unsafe fn first_non_ascii_generic<V: Vector>(text: &[u8]) -> usize {
+    let len = text.len();
+    if len < V::BYTES {
+        return text.iter().position(|elt| !elt.is_ascii()).unwrap_or(len);
+    }
+
+    let ptr = text.as_ptr();
+    // SAFETY: the caller guarantees that `V` is supported, and every load
+    // starts at most `len - V::BYTES` bytes into `text`.
+    unsafe {
+        let mut offset = 0;
+
+        // A non-ASCII byte is one with its top bit set, which is exactly
+        // what `movemask` collects.
+        while offset + 4 * V::BYTES <= len {
+            let a = V::load(ptr.add(offset));
+            let b = V::load(ptr.add(offset + V::BYTES));
+            let c = V::load(ptr.add(offset + 2 * V::BYTES));
+            let d = V::load(ptr.add(offset + 3 * V::BYTES));
+            if a.or(b).or(c.or(d)).movemask() != 0 {
+                break;
+            }
+            offset += 4 * V::BYTES;
+        }
+
+        offset = cmp::min(offset, len - V::BYTES);
+        loop {
+            let mask = V::load(ptr.add(offset)).movemask();
+            if mask != 0 {
+                return offset + mask.trailing_zeros() as usize;
+            }
+            if offset == len - V::BYTES {
+                return len;
+            }
+            offset = cmp::min(offset + V::BYTES, len - V::BYTES);
+        }
+    }
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+///
+/// This compares the first and the last byte of the needle against two
+/// vectors of the haystack that are `needle.len() - 1` bytes apart, and
+/// only compares the whole needle at the positions where both of them
+/// match. It takes time proportional to the product of the lengths in the
+/// worst case, so it is only meant for short needles.
+///
+/// # Safety
+///
+/// The CPU must support the instruction set `V` belongs to.
+#[inline(always)]
+unsafe fn find_generic<V: Vector>(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let last = match needle.len() {
+        0 => return Some(0),
+        // SAFETY: the caller guarantees that `V` is supported.
+        1 => return unsafe { memchr_generic::<V>(needle[0], haystack) },
+        len => len - 1,
+    };
+    if haystack.len() < needle.len() {
+        return None;
+    }
+
+    // The number of positions the needle could start at.
+    let starts = haystack.len() - last;
+    if starts < V::BYTES {
+        return haystack.windows(needle.len()).position(|window| window == needle);
+    }
+
+    let ptr = haystack.as_ptr();
+    // SAFETY: the caller guarantees that `V` is supported, and every load
+    // ends at most `starts + last`, i.e. `haystack.len()`, bytes into
+    // `haystack`.
+    unsafe {
+        let first_byte = V::splat(needle[0]);
+        let last_byte = V::splat(needle[last]);
+        let mut offset = 0;
+        loop {
+            let first_eq = V::load(ptr.add(offset)).cmpeq(first_byte);
+            let last_eq = V::load(ptr.add(offset + last)).cmpeq(last_byte);
+            let mut mask = first_eq.and(last_eq).movemask();
+            while mask != 0 {
+                let start = offset + mask.trailing_zeros() as usize;
+                if haystack[start + 1..start + last] == needle[1..last] {
+                    return Some(start);
+                }
+                mask &= mask - 1;
+            }
+            if offset == starts - V::BYTES {
+                return None;
+            }
+            offset = cmp::min(offset + V::BYTES, starts - V::BYTES);
+        }
+    }
+}
+
+/// SSE2 version of [`memchr`](super::memchr::memchr).
+///
+/// # Safety
+///
+/// The CPU must support SSE2.
+#[target_feature(enable = "sse2")]
+pub unsafe fn memchr_sse2(x: u8, text: &[u8]) -> Option<usize> {
+    // SAFETY: the caller guarantees SSE2 support.
+    unsafe { memchr_generic::<__m128i>(x, text) }
+}
+
+/// AVX2 version of [`memchr`](super::memchr::memchr).
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn memchr_avx2(x: u8, text: &[u8]) -> Option<usize> {
+    // SAFETY: the caller guarantees AVX2 support, which implies SSE2.
+    unsafe {
+        if text.len() < __m256i::BYTES {
+            memchr_generic::<__m128i>(x, text)
+        } else {
+            memchr_generic::<__m256i>(x, text)
+        }
+    }
+}
+
+/// SSE2 version of [`memrchr`](super::memchr::memrchr).
+///
+/// # Safety
+///
+/// The CPU must support SSE2.
+#[target_feature(enable = "sse2")]
+pub unsafe fn memrchr_sse2(x: u8, text: &[u8]) -> Option<usize> {
+    // SAFETY: the caller guarantees SSE2 support.
+    unsafe { memrchr_generic::<__m128i>(x, text) }
+}
+
+/// AVX2 version of [`memrchr`](super::memchr::memrchr).
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn memrchr_avx2(x: u8, text: &[u8]) -> Option<usize> {
+    // SAFETY: the caller guarantees AVX2 support, which implies SSE2.
+    unsafe {
+        if text.len() < __m256i::BYTES {
+            memrchr_generic::<__m128i>(x, text)
+        } else {
+            memrchr_generic::<__m256i>(x, text)
+        }
+    }
+}
+
+/// SSE2 version of [`first_non_ascii`].
+///
+/// # Safety
+///
+/// The CPU must support SSE2.
+#[target_feature(enable = "sse2")]
+pub unsafe fn first_non_ascii_sse2(text: &[u8]) -> usize {
+    // SAFETY: the caller guarantees SSE2 support.
+    unsafe { first_non_ascii_generic::<__m128i>(text) }
+}
+
+/// AVX2 version of [`first_non_ascii`].
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn first_non_ascii_avx2(text: &[u8]) -> usize {
+    // SAFETY: the caller guarantees AVX2 support, which implies SSE2.
+    unsafe {
+        if text.len() < __m256i::BYTES {
+            first_non_ascii_generic::<__m128i>(text)
+        } else {
+            first_non_ascii_generic::<__m256i>(text)
+        }
+    }
+}
+
+/// SSE2 version of [`find`].
+///
+/// # Safety
+///
+/// The CPU must support SSE2.
+#[target_feature(enable = "sse2")]
+pub unsafe fn find_sse2(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    // SAFETY: the caller guarantees SSE2 support.
+    unsafe { find_generic::<__m128i>(haystack, needle) }
+}
+
+/// AVX2 version of [`find`].
+///
+/// # Safety
+///
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub unsafe fn find_avx2(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    // SAFETY: the caller guarantees AVX2 support, which implies SSE2.
+    unsafe {
+        if haystack.len() < needle.len() + __m256i::BYTES {
+            find_generic::<__m128i>(haystack, needle)
+        } else {
+            find_generic::<__m256i>(haystack, needle)
+        }
+    }
+}
+
+/// Returns the first index matching the byte `x` in `text`, using the
+/// widest kernel the target is compiled for.
+#[cfg(target_feature = "sse2")]
+#[inline]
+pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
+    // SAFETY: the `cfg` attrs ensure that the target supports the kernel.
+    #[cfg(target_feature = "avx2")]
+    return unsafe { memchr_avx2(x, text) };
+    #[cfg(not(target_feature = "avx2"))]
+    return unsafe { memchr_sse2(x, text) };
+}
+
+/// Returns the last index matching the byte `x` in `text`, using the widest
+/// kernel the target is compiled for.
+#[cfg(target_feature = "sse2")]
+#[inline]
+pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+    // SAFETY: the `cfg` attrs ensure that the target supports the kernel.
+    #[cfg(target_feature = "avx2")]
+    return unsafe { memrchr_avx2(x, text) };
+    #[cfg(not(target_feature = "avx2"))]
+    return unsafe { memrchr_sse2(x, text) };
+}
+
+/// Returns the index of the first byte in `text` that is not ASCII, or
+/// `text.len()` if there is none, using the widest kernel the target is
+/// compiled for.
+#[cfg(target_feature = "sse2")]
+#[inline]
+pub fn first_non_ascii(text: &[u8]) -> usize {
+    // SAFETY: the `cfg` attrs ensure that the target supports the kernel.
+    #[cfg(target_feature = "avx2")]
+    return unsafe { first_non_ascii_avx2(text) };
+    #[cfg(not(target_feature = "avx2"))]
+    return unsafe { first_non_ascii_sse2(text) };
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`,
+/// using the widest kernel the target is compiled for.
+///
+/// The running time is proportional to the product of the lengths in the
+/// worst case, so callers should keep `needle` short.
+#[cfg(target_feature = "sse2")]
+#[inline]
+pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    // SAFETY: the `cfg` attrs ensure that the target supports the kernel.
+    #[cfg(target_feature = "avx2")]
+    return unsafe { find_avx2(haystack, needle) };
+    #[cfg(not(target_feature = "avx2"))]
+    return unsafe { find_sse2(haystack, needle) };
+}
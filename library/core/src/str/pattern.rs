@@ -39,6 +39,8 @@
 use crate::cmp;
 use crate::fmt;
 use crate::slice::memchr;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+use crate::slice::simd;
 
 // Pattern
 
@@ -907,6 +909,11 @@ fn strip_suffix_of(self, haystack: &'a str) -> Option<&'a str> {
 // Two Way substring searcher
 /////////////////////////////////////////////////////////////////////////////
 
+/// The longest needle `StrSearcher::next_match` looks for with
+/// `simd::find` rather than the Two-Way algorithm.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+const SIMD_NEEDLE_MAX_LEN: usize = 32;
+
 #[derive(Clone, Debug)]
 /// Associated type for `<&str as Pattern<'a>>::Searcher`.
 pub struct StrSearcher<'a, 'b> {
@@ -1019,6 +1026,35 @@ fn next_match(&mut self) -> Option<(usize, usize)> {
                 }
             },
             StrSearcherImpl::TwoWay(ref mut searcher) => {
+                // Short needles are found faster by comparing their first and
+                // last byte against a vector's worth of positions at a time.
+                // The worst case is quadratic, which the length limit keeps
+                // in check.
+                #[cfg(all(
+                    any(target_arch = "x86", target_arch = "x86_64"),
+                    target_feature = "sse2"
+                ))]
+                if self.needle.len() <= SIMD_NEEDLE_MAX_LEN {
+                    let haystack = &self.haystack.as_bytes()[searcher.position..];
+                    let found = simd::find(haystack, self.needle.as_bytes());
+                    let start = searcher.position;
+                    return match found {
+                        Some(index) => {
+                            let (a, b) = (start + index, start + index + self.needle.len());
+                            // Leave the searcher in the same state `next` would.
+                            searcher.position = b;
+                            if searcher.memory != usize::MAX {
+                                searcher.memory = 0;
+                            }
+                            Some((a, b))
+                        }
+                        None => {
+                            searcher.position = self.haystack.len();
+                            None
+                        }
+                    };
+                }
+
                 let is_long = searcher.memory == usize::MAX;
                 // write out `true` and `false` cases to encourage the compiler
                 // to specialize the two cases separately.
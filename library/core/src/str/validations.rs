@@ -1,5 +1,8 @@
 //! Operations related to UTF-8 validation.
 
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+use crate::cmp;
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
 use crate::mem;
 
 use super::Utf8Error;
@@ -100,10 +103,17 @@ pub(super) fn next_code_point_reverse<'a, I>(bytes: &mut I) -> Option<u32>
     Some(ch)
 }
 
+/// The number of ascii bytes `run_utf8_validation` checks one by one before
+/// handing the rest of an ascii run to the vectorized search.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+const SHORT_ASCII_RUN: usize = 16;
+
 // use truncation to fit u64 into usize
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
 const NONASCII_MASK: usize = 0x80808080_80808080u64 as usize;
 
 /// Returns `true` if any byte in the word `x` is nonascii (>= 128).
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
 #[inline]
 fn contains_nonascii(x: usize) -> bool {
     (x & NONASCII_MASK) != 0
@@ -116,10 +126,15 @@ pub(super) fn run_utf8_validation(v: &[u8]) -> Result<(), Utf8Error> {
     let mut index = 0;
     let len = v.len();
 
-    let usize_bytes = mem::size_of::<usize>();
-    let ascii_block_size = 2 * usize_bytes;
-    let blocks_end = if len >= ascii_block_size { len - ascii_block_size + 1 } else { 0 };
-    let align = v.as_ptr().align_offset(usize_bytes);
+    // Only the word-at-a-time skipping of ascii needs these, the vectorized
+    // one takes care of alignment and tails by itself.
+    #[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+    let (usize_bytes, ascii_block_size, blocks_end, align) = {
+        let usize_bytes = mem::size_of::<usize>();
+        let ascii_block_size = 2 * usize_bytes;
+        let blocks_end = if len >= ascii_block_size { len - ascii_block_size + 1 } else { 0 };
+        (usize_bytes, ascii_block_size, blocks_end, v.as_ptr().align_offset(usize_bytes))
+    };
 
     while index < len {
         let old_offset = index;
@@ -195,9 +210,28 @@ macro_rules! next {
             }
             index += 1;
         } else {
+            // Ascii case, skip to the next non-ascii byte a vector at a time.
+            // Text that mixes ascii with other scripts is full of short ascii
+            // runs, like single spaces, which are quicker to step over a byte
+            // at a time, so only runs longer than that go to the vector loop.
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+            {
+                let short_run_end = cmp::min(index + SHORT_ASCII_RUN, len);
+                while index < short_run_end && v[index] < 128 {
+                    index += 1;
+                }
+                if index == short_run_end && index < len {
+                    index += crate::slice::simd::first_non_ascii(&v[index..]);
+                }
+            }
+
             // Ascii case, try to skip forward quickly.
             // When the pointer is aligned, read 2 words of data per iteration
             // until we find a word containing a non-ascii byte.
+            #[cfg(not(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                target_feature = "sse2"
+            )))]
             if align != usize::MAX && align.wrapping_sub(index) % usize_bytes == 0 {
                 let ptr = v.as_ptr();
                 while index < blocks_end {
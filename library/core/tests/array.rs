@@ -374,3 +374,123 @@ fn new() -> B<'a> {
     b3.a[0].set(Some(&b1));
     b3.a[1].set(Some(&b2));
 }
+
+#[test]
+fn array_zip() {
+    let a = [1, 2, 3];
+    let b = ["a", "b", "c"];
+    assert_eq!(a.zip(b), [(1, "a"), (2, "b"), (3, "c")]);
+    assert_eq!([(); 0].zip([0u8; 0]), []);
+}
+
+#[test]
+fn array_each_ref_and_each_mut() {
+    let strings = [String::from("a"), String::from("bc")];
+    assert_eq!(strings.each_ref().map(|s| s.len()), [1, 2]);
+
+    let mut a = [1, 2, 3];
+    for x in a.each_mut().iter_mut() {
+        **x *= 10;
+    }
+    assert_eq!(a, [10, 20, 30]);
+}
+
+#[test]
+fn array_from_fn() {
+    let a: [usize; 4] = array::from_fn(|i| i * i);
+    assert_eq!(a, [0, 1, 4, 9]);
+    let empty: [String; 0] = array::from_fn(|_| unreachable!());
+    assert_eq!(empty.len(), 0);
+}
+
+#[test]
+fn array_try_from_fn() {
+    let a: Result<[usize; 4], ()> = array::try_from_fn(|i| Ok(i + 1));
+    assert_eq!(a, Ok([1, 2, 3, 4]));
+
+    let mut calls = 0;
+    let a: Result<[usize; 4], usize> = array::try_from_fn(|i| {
+        calls += 1;
+        if i == 2 { Err(i) } else { Ok(i) }
+    });
+    assert_eq!(a, Err(2));
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn array_try_from_fn_drops_on_error() {
+    use core::cell::Cell;
+    struct DropCounter<'a>(&'a Cell<usize>);
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let a: Result<[DropCounter<'_>; 10], ()> =
+        array::try_from_fn(|i| if i < 6 { Ok(DropCounter(&dropped)) } else { Err(()) });
+    assert!(a.is_err());
+    assert_eq!(dropped.get(), 6);
+}
+
+#[test]
+fn array_try_from_iter() {
+    let a: Option<[i32; 3]> = array::try_from_iter(1..=3);
+    assert_eq!(a, Some([1, 2, 3]));
+    let a: Option<[String; 2]> = array::try_from_iter(vec![String::from("x"), String::from("y")]);
+    assert_eq!(a, Some([String::from("x"), String::from("y")]));
+    let a: Option<[u8; 0]> = array::try_from_iter(core::iter::empty());
+    assert_eq!(a, Some([]));
+    let a: Option<[u8; 0]> = array::try_from_iter(Some(1));
+    assert_eq!(a, None);
+}
+
+#[test]
+fn array_try_from_iter_drops_on_mismatch() {
+    use core::cell::Cell;
+    struct DropCounter<'a>(&'a Cell<usize>);
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let a: Option<[DropCounter<'_>; 4]> =
+        array::try_from_iter((0..3).map(|_| DropCounter(&dropped)));
+    assert!(a.is_none());
+    assert_eq!(dropped.get(), 3);
+
+    dropped.set(0);
+    let a: Option<[DropCounter<'_>; 2]> =
+        array::try_from_iter((0..3).map(|_| DropCounter(&dropped)));
+    assert!(a.is_none());
+    assert_eq!(dropped.get(), 3);
+}
+
+// See note on `array_default_impl_avoids_leaks_on_panic` for why `should_panic` is used.
+#[test]
+#[should_panic(expected = "test succeeded")]
+fn array_from_fn_drop_safety() {
+    use core::sync::atomic::AtomicUsize;
+    use core::sync::atomic::Ordering;
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+    struct DropCounter;
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let num_to_create = 5;
+    let success = std::panic::catch_unwind(|| {
+        let _: [DropCounter; 10] = array::from_fn(|i| {
+            assert!(i < num_to_create);
+            DropCounter
+        });
+    });
+    assert!(success.is_err());
+    assert_eq!(DROPPED.load(Ordering::SeqCst), num_to_create);
+    panic!("test succeeded")
+}
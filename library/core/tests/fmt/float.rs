@@ -29,3 +29,25 @@ fn test_format_f32() {
     assert_eq!("0.0", format!("{:?}", 0.0f32));
     assert_eq!("1.01", format!("{:?}", 1.01f32));
 }
+
+#[test]
+fn test_to_shortest_str() {
+    let mut buf = [0; 327];
+    let mut exp_buf = [0; 24];
+    for &x in &[0.0, -0.0, 1.0, 0.1, -1.5, 1e300, 5e-324, f64::MAX, f64::MIN, f64::MIN_POSITIVE] {
+        assert_eq!(x.to_shortest_str(&mut buf), format!("{}", x));
+        assert_eq!(x.to_shortest_exp_str(&mut exp_buf), format!("{:e}", x));
+    }
+    assert_eq!(f64::NAN.to_shortest_str(&mut buf), "NaN");
+    assert_eq!(f64::NEG_INFINITY.to_shortest_exp_str(&mut exp_buf), "-inf");
+    assert_eq!((-2.2250738585072014e-308f64).to_shortest_exp_str(&mut exp_buf).len(), 24);
+
+    let mut buf = [0; 48];
+    let mut exp_buf = [0; 15];
+    for &x in &[0.0, -0.0, 1.0, 0.1, -1.5, 1e30, 1e-45, f32::MAX, f32::MIN, -1.0244719e-36] {
+        assert_eq!(x.to_shortest_str(&mut buf), format!("{}", x));
+        assert_eq!(x.to_shortest_exp_str(&mut exp_buf), format!("{:e}", x));
+    }
+    assert_eq!(f32::INFINITY.to_shortest_str(&mut buf), "inf");
+    assert_eq!((-1.1754942e-38f32).to_shortest_str(&mut buf).len(), 48);
+}
@@ -222,3 +222,37 @@ fn test_format_debug_hex() {
     assert_eq!(format!("{:02x?}", b"Foo\0"), "[46, 6f, 6f, 00]");
     assert_eq!(format!("{:02X?}", b"Foo\0"), "[46, 6F, 6F, 00]");
 }
+
+#[test]
+fn test_format_into() {
+    macro_rules! check {
+        ($($t:ident $len:expr),*) => {$(
+            let mut buf = [0xff; $len];
+            for &n in &[0, 1, 9, 10, 99, 100, 123, <$t>::MIN, <$t>::MAX, <$t>::MAX / 7] {
+                assert_eq!(n.format_into(&mut buf), n.to_string());
+            }
+            // The buffer is exactly as long as the longest value.
+            let longest = <$t>::MIN.to_string().len().max(<$t>::MAX.to_string().len());
+            assert_eq!(longest, $len);
+        )*};
+    }
+    check!(i8 4, u8 3, i16 6, u16 5, i32 11, u32 10, i64 20, u64 20, i128 40, u128 39);
+    #[cfg(target_pointer_width = "16")]
+    check!(isize 6, usize 5);
+    #[cfg(target_pointer_width = "32")]
+    check!(isize 11, usize 10);
+    #[cfg(target_pointer_width = "64")]
+    check!(isize 20, usize 20);
+
+    let mut buf = [0; 40];
+    for &n in &[-1, -9, -10, -10000, -123456789, -(10i128.pow(19)), -(10i128.pow(38))] {
+        assert_eq!(n.format_into(&mut buf), n.to_string());
+    }
+    let mut buf = [0; 39];
+    for k in 0..39 {
+        let n = 10u128.pow(k);
+        assert_eq!(n.format_into(&mut buf), n.to_string());
+        assert_eq!((n - 1).format_into(&mut buf), (n - 1).to_string());
+        assert_eq!((n + 1).format_into(&mut buf), (n + 1).to_string());
+    }
+}
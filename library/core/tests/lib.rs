@@ -3,6 +3,8 @@
 #![feature(array_from_ref)]
 #![feature(array_methods)]
 #![feature(array_map)]
+#![feature(array_from_fn)]
+#![feature(array_zip)]
 #![feature(array_windows)]
 #![feature(bool_to_option)]
 #![feature(bound_cloned)]
@@ -23,6 +25,8 @@
 #![feature(exact_size_is_empty)]
 #![feature(fixed_size_array)]
 #![feature(flt2dec)]
+#![feature(float_from_str_hex)]
+#![feature(num_format_into)]
 #![feature(fmt_internals)]
 #![feature(hashmap_internals)]
 #![feature(try_find)]
@@ -41,6 +45,7 @@
 #![feature(slice_internals)]
 #![feature(slice_partition_dedup)]
 #![feature(int_error_matching)]
+#![feature(int_traits)]
 #![feature(array_value_iter)]
 #![feature(iter_advance_by)]
 #![feature(iter_partition_in_place)]
@@ -91,6 +96,7 @@
 mod pin;
 mod ptr;
 mod result;
+mod simd;
 mod slice;
 mod str;
 mod str_lossy;
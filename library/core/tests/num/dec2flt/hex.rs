@@ -0,0 +1,74 @@
+#[test]
+fn ordinary() {
+    assert_eq!(f64::from_str_hex("0"), Ok(0.0));
+    assert_eq!(f64::from_str_hex("1"), Ok(1.0));
+    assert_eq!(f64::from_str_hex("A"), Ok(10.0));
+    assert_eq!(f64::from_str_hex("ff"), Ok(255.0));
+    assert_eq!(f64::from_str_hex("1.8"), Ok(1.5));
+    assert_eq!(f64::from_str_hex(".8"), Ok(0.5));
+    assert_eq!(f64::from_str_hex("1."), Ok(1.0));
+    assert_eq!(f64::from_str_hex("1p4"), Ok(16.0));
+    assert_eq!(f64::from_str_hex("1P+4"), Ok(16.0));
+    assert_eq!(f64::from_str_hex("1p-4"), Ok(0.0625));
+    assert_eq!(f64::from_str_hex("-1.8p1"), Ok(-3.0));
+    assert_eq!(f64::from_str_hex("+0.1p4"), Ok(1.0));
+    assert_eq!(f64::from_str_hex("1.921fb54442d18p1"), Ok(core::f64::consts::PI));
+    assert_eq!(f32::from_str_hex("1.921fb6p1"), Ok(core::f32::consts::PI));
+    assert_eq!(f64::from_str_hex("000000000000000000000001"), Ok(1.0));
+}
+
+#[test]
+fn special() {
+    assert_eq!(f64::from_str_hex("inf"), Ok(f64::INFINITY));
+    assert_eq!(f64::from_str_hex("-inf"), Ok(f64::NEG_INFINITY));
+    assert!(f32::from_str_hex("NaN").unwrap().is_nan());
+    assert!(f64::from_str_hex("-0").unwrap().is_sign_negative());
+}
+
+#[test]
+fn invalid() {
+    for s in &["", "+", "-", ".", "p0", ".p0", "1p", "1p+", "1pa", "0x1", "1.2.3", " 1", "g"] {
+        assert!(f64::from_str_hex(s).is_err(), "{:?}", s);
+        assert!(f32::from_str_hex(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn rounding() {
+    // `1 + 2^-53` is exactly halfway between 1 and the next `f64`, and rounds to even.
+    assert_eq!(f64::from_str_hex("1.00000000000008"), Ok(1.0));
+    assert_eq!(f64::from_str_hex("1.00000000000018"), Ok(1.0 + 2.0 * f64::EPSILON));
+    // Anything above halfway rounds up, even when it's only in the digits that don't fit.
+    assert_eq!(f64::from_str_hex("1.0000000000000800000000001"), Ok(1.0 + f64::EPSILON));
+    assert_eq!(f32::from_str_hex("1.000001"), Ok(1.0));
+    assert_eq!(f32::from_str_hex("1.0000010000000000000000001"), Ok(1.0 + f32::EPSILON));
+    // Rounding up carries into the next power of two.
+    assert_eq!(f64::from_str_hex("1.fffffffffffff8"), Ok(2.0));
+}
+
+#[test]
+fn extremes() {
+    assert_eq!(f64::from_str_hex("1.fffffffffffffp1023"), Ok(f64::MAX));
+    assert_eq!(f64::from_str_hex("1.fffffffffffff8p1023"), Ok(f64::INFINITY));
+    assert_eq!(f64::from_str_hex("1p1024"), Ok(f64::INFINITY));
+    assert_eq!(f64::from_str_hex("1p-1022"), Ok(f64::MIN_POSITIVE));
+    assert_eq!(f64::from_str_hex("0.fffffffffffffp-1022"), Ok(f64::MIN_POSITIVE - 5e-324));
+    assert_eq!(f64::from_str_hex("1p-1074"), Ok(5e-324));
+    assert_eq!(f64::from_str_hex("1p-1075"), Ok(0.0));
+    assert_eq!(f64::from_str_hex("1.00001p-1075"), Ok(5e-324));
+    assert_eq!(f32::from_str_hex("1.fffffep127"), Ok(f32::MAX));
+    assert_eq!(f32::from_str_hex("1p-149"), Ok(f32::from_bits(1)));
+    assert_eq!(f32::from_str_hex("1p-150"), Ok(0.0));
+}
+
+#[test]
+fn massive_exponent() {
+    let max = i64::MAX;
+    assert_eq!(f64::from_str_hex(&format!("1p{}000", max)), Ok(f64::INFINITY));
+    assert_eq!(f64::from_str_hex(&format!("1p-{}000", max)), Ok(0.0));
+    assert_eq!(f64::from_str_hex(&format!("0p{}000", max)), Ok(0.0));
+    // The digits that don't fit count towards the exponent, as do the ones after the point.
+    let zeros = "0".repeat(300);
+    assert_eq!(f64::from_str_hex(&format!("1{}p-1200", zeros)), Ok(1.0));
+    assert_eq!(f64::from_str_hex(&format!("0.{}1p1204", zeros)), Ok(1.0));
+}
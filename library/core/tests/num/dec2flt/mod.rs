@@ -1,5 +1,8 @@
 #![allow(overflowing_literals)]
 
+use core::cmp::Ordering;
+
+mod hex;
 mod parse;
 mod rawfp;
 
@@ -149,7 +152,177 @@ fn borderline_overflow() {
     for _ in 0..375 {
         s.push('3');
     }
-    // At the time of this writing, this returns Err(..), but this is a bug that should be fixed.
-    // It makes no sense to enshrine that in a test, the important part is that it doesn't panic.
-    let _ = s.parse::<f64>();
+    assert_eq!(s.parse(), Ok(1.0f64 / 3.0));
+    assert_eq!(s.parse(), Ok(1.0f32 / 3.0));
+}
+
+#[test]
+fn long_mantissas() {
+    // More digits than fit into a `u64`, but far from any halfway point.
+    test_literal!(0.1000000000000000000000000000001);
+    test_literal!(123456789012345678901234567890.0);
+    test_literal!(1.00000000000000000000000000000000000000000000000000000000000000001e300);
+    test_literal!(9.999999999999999999999999999999e-300);
+    // Right next to the halfway point between 1 and the next `f64`, `1 + 2^-53`, on either side.
+    assert_eq!("1.00000000000000011102230246251565404".parse(), Ok(1.0f64));
+    assert_eq!("1.00000000000000011102230246251565405".parse(), Ok(1.0000000000000002f64));
+    assert_eq!("1.000000000000000111022302462515654042363166809082031250".parse(), Ok(1.0f64));
+    assert_eq!(
+        "1.000000000000000111022302462515654042363166809082031251".parse(),
+        Ok(1.0000000000000002f64)
+    );
+}
+
+fn assert_f32_roundtrips(bits: u32) {
+    let x = f32::from_bits(bits);
+    if x.is_nan() {
+        return;
+    }
+    // The shortest representation, and one with more digits than that.
+    for s in &[format!("{:e}", x), format!("{:.11e}", x), format!("{:?}", x)] {
+        assert_eq!(s.parse::<f32>().map(f32::to_bits), Ok(bits), "{}", s);
+    }
+    // Hexadecimal, with the hidden bit written out.
+    let (sig, exp) = match (bits >> 23) & 0xff {
+        0 => (bits & 0x7f_ffff, -126),
+        0xff => return,
+        e => (bits & 0x7f_ffff | 0x80_0000, e as i32 - 127),
+    };
+    let sign = if x.is_sign_negative() { "-" } else { "" };
+    let s = format!("{}{:x}.{:06x}p{}", sign, sig >> 23, (sig << 1) & 0xff_ffff, exp);
+    assert_eq!(f32::from_str_hex(&s).map(f32::to_bits), Ok(bits), "{}", s);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn f32_roundtrip_sampled() {
+    for bits in (0..=u32::MAX).step_by(65_537) {
+        assert_f32_roundtrips(bits);
+    }
+    for &bits in &[1, 0x7f_ffff, 0x80_0000, 0x7f7f_ffff, 0x3f80_0000, 0x3f80_0001] {
+        assert_f32_roundtrips(bits);
+        assert_f32_roundtrips(bits | 0x8000_0000);
+    }
+}
+
+#[test]
+#[ignore] // Takes over an hour even in release mode.
+fn f32_roundtrip_exhaustive() {
+    for bits in 0..=u32::MAX {
+        assert_f32_roundtrips(bits);
+    }
+}
+
+/// Splits a positive decimal string like `12.34e56` into its digits without leading and trailing
+/// zeros, and the decimal exponent of the first of them. Zero has no digits.
+fn significant_digits(s: &str) -> (String, i64) {
+    let (mantissa, exp) = match s.find('e') {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i64>().unwrap()),
+        None => (s, 0),
+    };
+    let point = mantissa.find('.').unwrap_or(mantissa.len());
+    let digits: String = mantissa.chars().filter(|&c| c != '.').collect();
+    let leading_zeros = digits.len() - digits.trim_start_matches('0').len();
+    let digits = digits.trim_matches('0').to_string();
+    (digits, point as i64 - 1 - leading_zeros as i64 + exp)
+}
+
+/// Compares the exact values of two positive decimal strings.
+fn cmp_decimal(a: &str, b: &str) -> Ordering {
+    let (a_digits, a_exp) = significant_digits(a);
+    let (b_digits, b_exp) = significant_digits(b);
+    match (a_digits.is_empty(), b_digits.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a_exp.cmp(&b_exp).then_with(|| a_digits.cmp(&b_digits)),
+    }
+}
+
+/// Returns the point halfway between the positive `f32` with the given bits and the next larger
+/// one, or the point where rounding overflows to infinity for `f32::MAX`. It is exact in `f64`,
+/// and `{:.250e}` prints it without rounding.
+fn f32_halfway(bits: u32) -> f64 {
+    let lo = f32::from_bits(bits) as f64;
+    let hi = match bits {
+        0x7f7f_ffff => 2f64.powi(128),
+        _ => f32::from_bits(bits + 1) as f64,
+    };
+    (lo + hi) / 2.0
+}
+
+/// Asserts that the positive decimal string `s` is parsed to the `f32` closest to it, with ties
+/// going to the one with the even significand.
+fn assert_f32_correctly_rounded(s: &str) {
+    let bits = s.parse::<f32>().unwrap().to_bits();
+    if bits > 0 {
+        match cmp_decimal(s, &format!("{:.250e}", f32_halfway(bits - 1))) {
+            Ordering::Less => panic!("{} is rounded up to {:#x}", s, bits),
+            Ordering::Equal => assert_eq!(bits % 2, 0, "{} is a tie, rounded to odd", s),
+            Ordering::Greater => {}
+        }
+    }
+    if bits < f32::INFINITY.to_bits() {
+        match cmp_decimal(s, &format!("{:.250e}", f32_halfway(bits))) {
+            Ordering::Less => {}
+            Ordering::Equal => assert_eq!(bits % 2, 0, "{} is a tie, rounded to odd", s),
+            Ordering::Greater => panic!("{} is rounded down to {:#x}", s, bits),
+        }
+    }
+}
+
+/// Asserts that the exact decimal representations of the point halfway between the positive `f32`
+/// with the given bits and the next larger one, and of two points just below and above it, are
+/// parsed correctly. These are the inputs that the Eisel-Lemire algorithm has to give up on or
+/// round just right.
+fn assert_f32_halfway_cases(bits: u32) {
+    let mid = f32_halfway(bits);
+    let delta = (f32::from_bits(bits + 1) as f64 - f32::from_bits(bits) as f64) / (1 << 20) as f64;
+    let even = bits + bits % 2;
+    for &(x, expected) in &[(mid, even), (mid - delta, bits), (mid + delta, bits + 1)] {
+        // All of these are exact in `f64`.
+        let s = format!("{:.250e}", x);
+        assert_eq!(s.parse::<f32>().map(f32::to_bits), Ok(expected), "f32: {}", s);
+        assert_eq!(s.parse::<f64>().map(f64::to_bits), Ok(x.to_bits()), "f64: {}", s);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn correctly_rounded_sampled() {
+    use rand::distributions::{Distribution, Uniform};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let lens = Uniform::new_inclusive(1, 25);
+    let digits = Uniform::new_inclusive(b'0', b'9');
+    let exps = Uniform::new_inclusive(-70, 50);
+    for _ in 0..20_000 {
+        let len = lens.sample(&mut rng);
+        let mut s: String = (0..len).map(|_| digits.sample(&mut rng) as char).collect();
+        s.push_str(&format!("e{}", exps.sample(&mut rng)));
+        assert_f32_correctly_rounded(&s);
+    }
+
+    for _ in 0..20_000 {
+        let x = f64::from_bits(rng.gen::<u64>() >> 1);
+        if x.is_finite() {
+            for s in &[format!("{:e}", x), format!("{:.16e}", x), format!("{:.20e}", x)] {
+                assert_eq!(s.parse::<f64>().map(f64::to_bits), Ok(x.to_bits()), "{}", s);
+            }
+        }
+    }
+
+    for bits in (0..0x7f7f_ffff).step_by(65_537) {
+        assert_f32_halfway_cases(bits);
+    }
+}
+
+#[test]
+#[ignore] // Takes hours even in release mode.
+fn halfway_cases_exhaustive() {
+    for bits in 0..0x7f7f_ffff {
+        assert_f32_halfway_cases(bits);
+    }
 }
@@ -29,6 +29,7 @@
 mod flt2dec;
 
 mod nan;
+mod traits;
 
 /// Adds the attribute to all items in the block.
 macro_rules! cfg_block {
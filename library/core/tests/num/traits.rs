@@ -0,0 +1,89 @@
+use core::num::{Integer, SignedInteger, UnsignedInteger};
+
+fn check_integer<T: Integer>() {
+    assert_eq!(T::BITS as usize, core::mem::size_of::<T>() * 8);
+    assert!(T::MIN <= T::ZERO);
+    assert_eq!(T::ZERO + T::ONE, T::ONE);
+    assert_eq!(T::MAX.checked_add(T::ONE), None);
+    assert_eq!(T::MAX.wrapping_add(T::ONE), T::MIN);
+    assert_eq!(T::MAX.saturating_add(T::ONE), T::MAX);
+    assert_eq!(T::MAX.overflowing_add(T::ONE), (T::MIN, true));
+    assert_eq!(T::MIN.checked_sub(T::ONE), None);
+    assert_eq!(T::MIN.saturating_sub(T::ONE), T::MIN);
+    assert_eq!(T::ONE.checked_div(T::ZERO), None);
+    assert_eq!(T::ONE.checked_shl(T::BITS), None);
+    assert_eq!(T::ONE.wrapping_shl(T::BITS), T::ONE);
+    assert_eq!(T::ONE.rotate_right(1).rotate_left(1), T::ONE);
+    assert_eq!(T::ONE.leading_zeros(), T::BITS - 1);
+    assert_eq!(T::ONE.trailing_zeros(), 0);
+    assert_eq!(T::MAX.count_ones() + T::MAX.count_zeros(), T::BITS);
+    assert_eq!(T::ONE.reverse_bits().leading_zeros(), 0);
+    assert_eq!(T::ONE.swap_bytes().swap_bytes(), T::ONE);
+    assert_eq!(T::from_be(T::ONE.to_be()), T::ONE);
+    assert_eq!(T::from_le(T::ONE.to_le()), T::ONE);
+
+    let two = T::ONE + T::ONE;
+    assert_eq!(two.pow(3), T::try_from(8u8).ok().unwrap());
+    assert_eq!(two.checked_pow(T::BITS), None);
+    assert_eq!(two.wrapping_pow(T::BITS), T::ZERO);
+    assert_eq!(two.saturating_pow(T::BITS), T::MAX);
+    assert_eq!(T::from(true), T::ONE);
+    assert_eq!(T::from_str_radix("101", 2), Ok(T::try_from(5u8).ok().unwrap()));
+    assert!(T::from_str_radix("z", 10).is_err());
+
+    let bytes = T::MAX.to_le_bytes();
+    assert_eq!(bytes.as_ref().len(), core::mem::size_of::<T>());
+    assert_eq!(T::from_le_bytes(bytes), T::MAX);
+    assert_eq!(T::from_be_bytes(T::ONE.to_be_bytes()), T::ONE);
+    assert_eq!(T::from_ne_bytes(T::MIN.to_ne_bytes()), T::MIN);
+    assert_eq!(T::ONE.to_be_bytes().as_ref().last(), Some(&1));
+}
+
+fn check_signed<T: SignedInteger>() {
+    check_integer::<T>();
+    let minus_one = -T::ONE;
+    assert_eq!(minus_one.abs(), T::ONE);
+    assert_eq!(minus_one.signum(), minus_one);
+    assert!(minus_one.is_negative() && !minus_one.is_positive());
+    assert_eq!(T::MIN.checked_abs(), None);
+    assert_eq!(T::MIN.wrapping_abs(), T::MIN);
+    assert_eq!(T::MIN.saturating_abs(), T::MAX);
+    assert_eq!(T::MIN.saturating_neg(), T::MAX);
+    assert_eq!(T::MIN.overflowing_abs(), (T::MIN, true));
+    assert_eq!(T::MIN.checked_neg(), None);
+    assert_eq!(minus_one.cast_unsigned(), <T::Unsigned as Integer>::MAX);
+    assert_eq!(T::MIN.unsigned_abs().count_ones(), 1);
+    assert_eq!(minus_one.rem_euclid(T::MAX), T::MAX - T::ONE);
+}
+
+fn check_unsigned<T: UnsignedInteger>() {
+    check_integer::<T>();
+    assert_eq!(T::MIN, T::ZERO);
+    assert_eq!(T::MAX.cast_signed(), -<T::Signed as Integer>::ONE);
+    assert!(T::ONE.is_power_of_two());
+    assert!(!T::MAX.is_power_of_two());
+    assert_eq!((T::ONE + T::ONE + T::ONE).next_power_of_two(), T::try_from(4u8).ok().unwrap());
+    assert_eq!(T::MAX.checked_next_power_of_two(), None);
+    assert_eq!(T::ZERO.checked_neg(), Some(T::ZERO));
+    assert_eq!(T::ONE.wrapping_neg(), T::MAX);
+}
+
+#[test]
+fn signed_integers() {
+    check_signed::<i8>();
+    check_signed::<i16>();
+    check_signed::<i32>();
+    check_signed::<i64>();
+    check_signed::<i128>();
+    check_signed::<isize>();
+}
+
+#[test]
+fn unsigned_integers() {
+    check_unsigned::<u8>();
+    check_unsigned::<u16>();
+    check_unsigned::<u32>();
+    check_unsigned::<u64>();
+    check_unsigned::<u128>();
+    check_unsigned::<usize>();
+}
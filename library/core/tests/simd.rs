@@ -0,0 +1,205 @@
+//! Differential tests for the vectorized byte searches, checking them against
+//! the portable versions on random inputs of many lengths and alignments.
+
+use core::slice::memchr::{memchr_fallback, memrchr_fallback};
+use core::str::lossy::Utf8Lossy;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Returns `len` random bytes below `alphabet`, starting at a random offset
+/// into a larger buffer so that every alignment gets exercised.
+fn random_bytes(rng: &mut StdRng, len: usize, alphabet: u8) -> Vec<u8> {
+    let offset = rng.gen_range(0, 32);
+    let buf: Vec<u8> = (0..offset + len).map(|_| rng.gen_range(0, alphabet)).collect();
+    buf[offset..].to_vec()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn memchr_matches_fallback() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for len in 0..300 {
+        for &alphabet in &[2, 20, 255] {
+            let text = random_bytes(&mut rng, len, alphabet);
+            for needle in 0..4 {
+                let expected = memchr_fallback(needle, &text);
+                assert_eq!(core::slice::memchr::memchr(needle, &text), expected);
+                let expected = memrchr_fallback(needle, &text);
+                assert_eq!(core::slice::memchr::memrchr(needle, &text), expected);
+            }
+        }
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::*;
+    use core::slice::simd;
+
+    fn has_sse2() -> bool {
+        is_x86_feature_detected!("sse2")
+    }
+
+    fn has_avx2() -> bool {
+        is_x86_feature_detected!("avx2")
+    }
+
+    fn naive_find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+        haystack.windows(needle.len()).position(|window| window == needle)
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn memchr_kernels_match_fallback() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for len in 0..300 {
+            for &alphabet in &[2, 20, 255] {
+                let text = random_bytes(&mut rng, len, alphabet);
+                for needle in 0..4 {
+                    let forward = memchr_fallback(needle, &text);
+                    let backward = memrchr_fallback(needle, &text);
+                    if has_sse2() {
+                        unsafe {
+                            assert_eq!(simd::memchr_sse2(needle, &text), forward);
+                            assert_eq!(simd::memrchr_sse2(needle, &text), backward);
+                        }
+                    }
+                    if has_avx2() {
+                        unsafe {
+                            assert_eq!(simd::memchr_avx2(needle, &text), forward);
+                            assert_eq!(simd::memrchr_avx2(needle, &text), backward);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn first_non_ascii_kernels_match_fallback() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for len in 0..300 {
+            let mut text = random_bytes(&mut rng, len, 128);
+            // Plant a non-ascii byte at a random position half of the time.
+            if len > 0 && rng.gen() {
+                let index = rng.gen_range(0, len);
+                text[index] = rng.gen_range(128, 255);
+            }
+            let expected = text.iter().position(|b| !b.is_ascii()).unwrap_or(len);
+            if has_sse2() {
+                assert_eq!(unsafe { simd::first_non_ascii_sse2(&text) }, expected);
+            }
+            if has_avx2() {
+                assert_eq!(unsafe { simd::first_non_ascii_avx2(&text) }, expected);
+            }
+            assert_eq!(text.is_ascii(), expected == len);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn find_kernels_match_naive() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for len in 0..200 {
+            let haystack = random_bytes(&mut rng, len, 3);
+            for needle_len in 0..40 {
+                // Take the needle from the haystack half of the time, so
+                // that there is always at least one match.
+                let needle = if needle_len <= len && rng.gen() {
+                    let start = rng.gen_range(0, len - needle_len + 1);
+                    haystack[start..start + needle_len].to_vec()
+                } else {
+                    random_bytes(&mut rng, needle_len, 3)
+                };
+                let expected = naive_find(&haystack, &needle);
+                if has_sse2() {
+                    assert_eq!(unsafe { simd::find_sse2(&haystack, &needle) }, expected);
+                }
+                if has_avx2() {
+                    assert_eq!(unsafe { simd::find_avx2(&haystack, &needle) }, expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn str_find_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(4);
+    for len in 0..200 {
+        let haystack = String::from_utf8(random_bytes(&mut rng, len, 3))
+            .unwrap()
+            .replace('\u{0}', "a")
+            .replace('\u{1}', "b")
+            .replace('\u{2}', "é");
+        for needle_len in 1..40 {
+            let needle = haystack.get(..needle_len).unwrap_or("ab");
+            let expected: Vec<_> = {
+                let mut matches = Vec::new();
+                let mut start = 0;
+                while let Some(index) = haystack.as_bytes()[start..]
+                    .windows(needle.len())
+                    .position(|window| window == needle.as_bytes())
+                {
+                    matches.push(start + index);
+                    start += index + needle.len();
+                }
+                matches
+            };
+            let found: Vec<_> = haystack.match_indices(needle).map(|(index, _)| index).collect();
+            assert_eq!(found, expected);
+            assert_eq!(haystack.find(needle), expected.first().copied());
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Miri is too slow
+fn from_utf8_matches_lossy() {
+    let pieces: [&[u8]; 12] = [
+        b"a",
+        b"log line ",
+        b"0123456789abcdefghijklmnopqrstuvwxyz0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        "é".as_bytes(),
+        "€".as_bytes(),
+        "𝄞".as_bytes(),
+        b"\x80",
+        b"\xC0\x80",
+        b"\xE0\x80",
+        b"\xED\xA0\x80",
+        b"\xF4\x90\x80\x80",
+        b"\xF0\x9D",
+    ];
+    let mut rng = StdRng::seed_from_u64(5);
+    for _ in 0..3000 {
+        let count = rng.gen_range(0, 12);
+        let mut bytes = Vec::new();
+        for _ in 0..count {
+            // Mostly valid pieces, with the occasional invalid one.
+            let valid = rng.gen_range(0, 8) != 0;
+            let index = if valid { rng.gen_range(0, 6) } else { rng.gen_range(0, 12) };
+            bytes.extend_from_slice(pieces[index]);
+        }
+
+        let chunk = Utf8Lossy::from_bytes(&bytes).chunks().next();
+        match core::str::from_utf8(&bytes) {
+            Ok(s) => {
+                assert_eq!(s.as_bytes(), &bytes[..]);
+                assert!(chunk.map_or(true, |chunk| chunk.broken.is_empty()));
+            }
+            Err(error) => {
+                let chunk = chunk.unwrap();
+                assert_eq!(error.valid_up_to(), chunk.valid.len());
+                match error.error_len() {
+                    Some(len) => assert_eq!(len, chunk.broken.len()),
+                    None => assert_eq!(error.valid_up_to() + chunk.broken.len(), bytes.len()),
+                }
+            }
+        }
+    }
+}
@@ -412,6 +412,10 @@
 pub use alloc_crate::collections::{BTreeMap, BTreeSet, BinaryHeap};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::collections::{LinkedList, VecDeque};
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub use alloc_crate::collections::{sorted_vec_map, sorted_vec_set};
+#[unstable(feature = "sorted_vec", issue = "none")]
+pub use alloc_crate::collections::{SortedVecMap, SortedVecSet};
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::hash_map::HashMap;
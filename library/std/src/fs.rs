@@ -10,6 +10,7 @@
 
 #[cfg(all(test, not(any(target_os = "cloudabi", target_os = "emscripten", target_env = "sgx"))))]
 mod tests;
+mod walk;
 
 use crate::ffi::OsString;
 use crate::fmt;
@@ -19,6 +20,9 @@
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 use crate::time::SystemTime;
 
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub use self::walk::WalkDir;
+
 /// A reference to an open file on the filesystem.
 ///
 /// An instance of a `File` can be read and/or written depending on what options
@@ -311,6 +315,58 @@ fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
     inner(path.as_ref(), contents.as_ref())
 }
 
+/// Write a slice as the entire contents of a file, replacing the file
+/// atomically.
+///
+/// Unlike [`write`], which truncates the file and then writes to it, this
+/// writes the contents to a new file in the same directory, syncs it to disk
+/// like [`File::sync_all`] and then renames it over `path`. Other processes
+/// and a system crash see either the old or the new contents, never a mix of
+/// the two or a partially written file. If `path` exists, its permissions are
+/// kept.
+///
+/// The new file is created with a temporary name starting with a `.`. It is
+/// removed again if writing fails, but may be left behind if the process is
+/// killed while writing.
+///
+/// Since `path` is replaced by a new file, a symbolic link at `path` is
+/// replaced rather than followed, and hard links to the old file keep the old
+/// contents. Besides the permissions, nothing about the old file is kept: in
+/// particular its owner and group on Unix and its ACLs on Windows are those of
+/// a newly created file.
+///
+/// # Platform-specific behavior
+///
+/// On Unix the directory is synced after the rename so that the change itself
+/// is durable when this function returns. On Linux the new file is created
+/// with `O_TMPFILE` where supported, so that it only gets a name once it is
+/// complete. On Windows the rename corresponds to `MoveFileExW` with
+/// `MOVEFILE_WRITE_THROUGH`, which only returns once the rename is on disk.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// Besides the errors of writing and renaming files, this function returns
+/// an error if `path` doesn't end in a file name.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_write_atomic)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::write_atomic("state.json", br#"{"generation": 2}"#)?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    fs_imp::write_atomic(path.as_ref(), contents.as_ref())
+}
+
 impl File {
     /// Attempts to open a file in read-only mode.
     ///
@@ -446,6 +502,157 @@ pub fn sync_data(&self) -> io::Result<()> {
         self.inner.datasync()
     }
 
+    /// Acquires an exclusive advisory lock on the file, blocking until it can
+    /// be acquired.
+    ///
+    /// At most one exclusive lock may be held on a file at a time, and not
+    /// while any shared lock is held. The lock is advisory: it only excludes
+    /// other lockers and doesn't prevent anybody from reading or writing the
+    /// file.
+    ///
+    /// A lock belongs to the open file, not to this `File` or the process.
+    /// Handles created with [`try_clone`], duplicated descriptors and the
+    /// descriptors a child process inherits share the lock, so it is
+    /// released when [`unlock`] is called through any of them or once all of
+    /// them are closed. Opening the same path again yields a separate open
+    /// file that competes for the lock, even within the same process.
+    ///
+    /// If this file already holds a lock, the behavior is unspecified and
+    /// platform-specific, and it may deadlock. To switch between a shared and
+    /// an exclusive lock, call [`unlock`] first.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock` with `LOCK_EX` on
+    /// Unix, which has the semantics described above, and to `LockFileEx`
+    /// with `LOCKFILE_EXCLUSIVE_LOCK` on Windows, where the lock is
+    /// mandatory: other open files can't read or write the file while it is
+    /// held. On Unix a lock already held by this file is converted, though
+    /// not atomically. On Windows it is not converted: locking a file that
+    /// already holds a shared lock blocks forever, and a file that holds two
+    /// locks must be unlocked twice.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`try_clone`]: File::try_clone
+    /// [`unlock`]: File::unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("cache.lock")?;
+    ///     f.lock()?;
+    ///     // ... modify the cache ...
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be
+    /// acquired.
+    ///
+    /// Any number of shared locks may be held on a file at a time, but not
+    /// while an exclusive lock is held. See [`lock`] for how locks are shared
+    /// between handles.
+    ///
+    /// If this file already holds a lock, the behavior is unspecified and
+    /// platform-specific, and it may deadlock. To switch between an exclusive
+    /// and a shared lock, call [`unlock`] first.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock` with `LOCK_SH` on Unix
+    /// and to `LockFileEx` on Windows.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    /// [`unlock`]: File::unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("cache.lock")?;
+    ///     f.lock_shared()?;
+    ///     // ... read the cache ...
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file without
+    /// blocking.
+    ///
+    /// Returns `Ok(false)` if another lock is held on the file. Otherwise
+    /// this behaves like [`lock`].
+    ///
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("cache.lock")?;
+    ///     if !f.try_lock()? {
+    ///         println!("waiting for another process to release the cache");
+    ///         f.lock()?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file without blocking.
+    ///
+    /// Returns `Ok(false)` if an exclusive lock is held on the file.
+    /// Otherwise this behaves like [`lock_shared`].
+    ///
+    /// [`lock_shared`]: File::lock_shared
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases the lock held on the file.
+    ///
+    /// Locks are also released when the last handle to the open file is
+    /// closed, so this is only needed to release a lock early.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock` with `LOCK_UN` on Unix
+    /// and to `UnlockFile` on Windows, which returns an error if the file
+    /// isn't locked.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -1663,6 +1870,9 @@ pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()>
 /// This function currently corresponds to the `open` function in Unix
 /// with `O_RDONLY` for `from` and `O_WRONLY`, `O_CREAT`, and `O_TRUNC` for `to`.
 /// `O_CLOEXEC` is set for returned file descriptors.
+/// On Linux the destination is first reflinked to the source with the
+/// `FICLONE` ioctl, falling back to `copy_file_range` and then to a plain
+/// read/write loop.
 /// On Windows, this function currently corresponds to `CopyFileEx`. Alternate
 /// NTFS streams are copied but only the size of the main stream is returned by
 /// this function. On MacOS, this function corresponds to `fclonefileat` and
@@ -2073,6 +2283,39 @@ pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     fs_imp::readdir(path.as_ref()).map(ReadDir)
 }
 
+/// Returns an iterator over the entries within a directory and, recursively,
+/// within all directories below it.
+///
+/// The iterator yields [`io::Result`]`<`[`DirEntry`]`>` like [`read_dir`],
+/// without the starting directory itself. If the starting directory can't be
+/// read the first item is the error. The walk can be limited in depth, follow
+/// symbolic links, yield directories after their contents, sort and filter
+/// entries through the methods on [`WalkDir`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     let walk = fs::walk_dir("src")
+///         .max_depth(3)
+///         .sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+///     for entry in walk {
+///         let entry = entry?;
+///         if entry.file_type()?.is_file() {
+///             println!("{}", entry.path().display());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    walk::new(path.as_ref().to_path_buf())
+}
+
 /// Changes the permissions found on a file or a directory.
 ///
 /// # Platform-specific behavior
@@ -552,6 +552,77 @@ fn recursive_mkdir_empty() {
     check!(fs::create_dir_all(Path::new("")));
 }
 
+// Relative paths of the entries yielded by `walk`, panicking on errors.
+fn walked(root: &Path, walk: fs::WalkDir) -> Vec<String> {
+    walk.map(|entry| {
+        let path = check!(entry).path();
+        path.strip_prefix(root).unwrap().to_str().unwrap().replace('\\', "/")
+    })
+    .collect()
+}
+
+fn by_name(a: &fs::DirEntry, b: &fs::DirEntry) -> crate::cmp::Ordering {
+    a.file_name().cmp(&b.file_name())
+}
+
+#[test]
+fn walk_dir() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.path();
+    check!(fs::create_dir_all(root.join("b/d")));
+    check!(fs::create_dir_all(root.join("c")));
+    check!(File::create(root.join("a")));
+    check!(File::create(root.join("b/d/e")));
+    check!(File::create(root.join("b/f")));
+
+    let all = walked(root, fs::walk_dir(root).sort_by(by_name));
+    assert_eq!(all, ["a", "b", "b/d", "b/d/e", "b/f", "c"]);
+
+    let mut unsorted = walked(root, fs::walk_dir(root));
+    unsorted.sort();
+    assert_eq!(unsorted, all);
+
+    let shallow = walked(root, fs::walk_dir(root).max_depth(2).sort_by(by_name));
+    assert_eq!(shallow, ["a", "b", "b/d", "b/f", "c"]);
+    assert!(walked(root, fs::walk_dir(root).max_depth(0)).is_empty());
+
+    let contents_first = walked(root, fs::walk_dir(root).contents_first(true).sort_by(by_name));
+    assert_eq!(contents_first, ["a", "b/d/e", "b/d", "b/f", "b", "c"]);
+
+    let filtered = walked(root, fs::walk_dir(root).filter_entry(|e| e.file_name() != "d"));
+    assert_eq!(filtered.len(), 4);
+    assert!(!filtered.iter().any(|p| p.contains('d')));
+
+    let mut missing = fs::walk_dir(root.join("missing"));
+    assert!(missing.next().unwrap().is_err());
+    assert!(missing.next().is_none());
+}
+
+#[test]
+fn walk_dir_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let root = tmpdir.path();
+    check!(fs::create_dir_all(root.join("a/b")));
+    check!(File::create(root.join("a/b/c")));
+    check!(symlink_dir(root.join("a/b"), root.join("link")));
+    check!(symlink_dir(root.join("a"), root.join("a/b/up")));
+
+    // links are yielded but not followed by default
+    let walk = fs::walk_dir(root).sort_by(by_name);
+    assert_eq!(walked(root, walk), ["a", "a/b", "a/b/c", "a/b/up", "link"]);
+
+    // following them reports the loop instead of descending forever
+    let walk = fs::walk_dir(root).follow_links(true).sort_by(by_name);
+    let (ok, err): (Vec<_>, Vec<_>) = walk.partition(|e| e.is_ok());
+    let ok = ok.into_iter().map(|e| e.unwrap().path().strip_prefix(root).unwrap().to_owned());
+    let ok = ok.map(|p| p.to_str().unwrap().replace('\\', "/")).collect::<Vec<_>>();
+    assert_eq!(ok, ["a", "a/b", "a/b/c", "link", "link/c", "link/up"]);
+    assert_eq!(err.len(), 2);
+}
+
 #[test]
 fn recursive_rmdir() {
     let tmpdir = tmpdir();
@@ -1131,6 +1202,48 @@ fn write_then_read() {
     assert_eq!(string, s);
 }
 
+#[test]
+fn write_atomic() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("state");
+
+    check!(fs::write_atomic(&path, b"first"));
+    assert_eq!(check!(fs::read(&path)), b"first");
+
+    // an open handle keeps seeing the replaced file
+    #[cfg(unix)]
+    let mut old = check!(File::open(&path));
+    check!(fs::write_atomic(&path, b"second"));
+    assert_eq!(check!(fs::read(&path)), b"second");
+    #[cfg(unix)]
+    {
+        let mut v = Vec::new();
+        check!(old.read_to_end(&mut v));
+        assert_eq!(v, b"first");
+    }
+
+    // no temporary files are left behind
+    let names: Vec<_> =
+        check!(fs::read_dir(tmpdir.path())).map(|e| check!(e).file_name()).collect();
+    assert_eq!(names, ["state"]);
+
+    assert!(fs::write_atomic(tmpdir.join("missing/state"), b"").is_err());
+    assert!(fs::write_atomic(tmpdir.join(".."), b"").is_err());
+}
+
+#[test]
+#[cfg(unix)]
+fn write_atomic_keeps_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("script");
+    check!(fs::write(&path, b"#!/bin/sh"));
+    check!(fs::set_permissions(&path, fs::Permissions::from_mode(0o750)));
+    check!(fs::write_atomic(&path, b"#!/bin/sh\nexit 0"));
+    assert_eq!(check!(fs::metadata(&path)).permissions().mode() & 0o777, 0o750);
+}
+
 #[test]
 fn file_try_clone() {
     let tmpdir = tmpdir();
@@ -1150,6 +1263,48 @@ fn file_try_clone() {
     check!(f1.write_all(b"!"));
 }
 
+#[test]
+fn file_lock() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("lock");
+    let f1 = check!(File::create(&path));
+    let f2 = check!(File::open(&path));
+
+    check!(f1.lock());
+    assert!(!check!(f2.try_lock()));
+    assert!(!check!(f2.try_lock_shared()));
+    check!(f1.unlock());
+
+    check!(f1.lock_shared());
+    assert!(check!(f2.try_lock_shared()));
+    check!(f2.unlock());
+    assert!(!check!(f2.try_lock()));
+    check!(f1.unlock());
+
+    // closing the file releases the lock
+    assert!(check!(f1.try_lock()));
+    drop(f1);
+    assert!(check!(f2.try_lock()));
+    check!(f2.unlock());
+}
+
+#[test]
+#[cfg(unix)]
+fn file_lock_shared_by_clones() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("lock");
+    let f1 = check!(File::create(&path));
+    let f2 = check!(f1.try_clone());
+    let other = check!(File::open(&path));
+
+    check!(f1.lock());
+    // the clone refers to the same open file, so it already holds the lock
+    assert!(check!(f2.try_lock()));
+    assert!(!check!(other.try_lock_shared()));
+    check!(f2.unlock());
+    assert!(check!(other.try_lock_shared()));
+}
+
 #[test]
 #[cfg(not(windows))]
 fn unlink_readonly() {
@@ -0,0 +1,310 @@
+use crate::cmp::Ordering;
+use crate::fmt;
+use crate::io::{self, ErrorKind};
+use crate::path::{Path, PathBuf};
+use crate::sys_common::AsInner;
+use crate::vec;
+
+use super::{metadata, read_dir, DirEntry, ReadDir};
+
+/// An iterator over the entries of a directory tree, created by [`walk_dir`].
+///
+/// The iterator yields the entries below the directory it was created for,
+/// but not the directory itself. Each directory's entries are read with the
+/// same platform implementation as [`read_dir`], so an entry that can't be
+/// read is yielded as an error and iteration continues with the next one.
+///
+/// The walk is configured by calling the builder methods before iterating.
+/// By default it descends into every directory, doesn't follow symbolic
+/// links, yields a directory before its contents and yields the entries of
+/// a directory in the order the platform returns them.
+///
+/// [`walk_dir`]: super::walk_dir
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct WalkDir {
+    root: PathBuf,
+    max_depth: usize,
+    follow_links: bool,
+    contents_first: bool,
+    sorter: Option<Box<dyn FnMut(&DirEntry, &DirEntry) -> Ordering + Send>>,
+    filter: Option<Box<dyn FnMut(&DirEntry) -> bool + Send>>,
+    started: bool,
+    // One level per directory being read, the innermost one last.
+    stack: Vec<Level>,
+    // Result to yield before continuing the walk, for a directory that could
+    // not be read.
+    pending: Option<io::Result<DirEntry>>,
+}
+
+struct Level {
+    entries: Entries,
+    // Identity of the directory, only tracked when following links.
+    id: Option<(u64, u64)>,
+    // The entry for this directory if it should be yielded after its contents.
+    dir: Option<DirEntry>,
+}
+
+enum Entries {
+    Unsorted(ReadDir),
+    Sorted(vec::IntoIter<io::Result<DirEntry>>),
+}
+
+impl Iterator for Entries {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        match self {
+            Entries::Unsorted(read_dir) => read_dir.next(),
+            Entries::Sorted(entries) => entries.next(),
+        }
+    }
+}
+
+pub(super) fn new(root: PathBuf) -> WalkDir {
+    WalkDir {
+        root,
+        max_depth: usize::MAX,
+        follow_links: false,
+        contents_first: false,
+        sorter: None,
+        filter: None,
+        started: false,
+        stack: Vec::new(),
+        pending: None,
+    }
+}
+
+impl WalkDir {
+    /// Limits how deep the walk descends.
+    ///
+    /// The entries of the starting directory are at depth 1, so a depth of 1
+    /// yields the same entries as [`read_dir`] and a depth of 0 yields
+    /// nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     for entry in fs::walk_dir("src").max_depth(2) {
+    ///         println!("{}", entry?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Descends into symbolic links that point to directories.
+    ///
+    /// Entries are still reported as symbolic links by
+    /// [`DirEntry::file_type`]. A link that points to one of the directories
+    /// it is contained in would make the walk endless, it is yielded as an
+    /// error instead. Directories are identified by device and inode number
+    /// on Unix and by volume serial number and file index on Windows.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn follow_links(mut self, follow: bool) -> WalkDir {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Yields the entries within a directory before the directory itself.
+    ///
+    /// This is the order needed to remove a tree, for example.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn contents_first(mut self, contents_first: bool) -> WalkDir {
+        self.contents_first = contents_first;
+        self
+    }
+
+    /// Yields the entries of each directory in the order given by `compare`.
+    ///
+    /// All entries of a directory are read before the first of them is
+    /// yielded. Entries that can't be read are yielded first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let walk = fs::walk_dir("src").sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    ///     for entry in walk {
+    ///         println!("{}", entry?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by<F>(mut self, compare: F) -> WalkDir
+    where
+        F: FnMut(&DirEntry, &DirEntry) -> Ordering + Send + 'static,
+    {
+        self.sorter = Some(Box::new(compare));
+        self
+    }
+
+    /// Only yields the entries for which `predicate` returns `true`.
+    ///
+    /// The walk doesn't descend into directories that are filtered out, so
+    /// this is cheaper than filtering the iterator afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let walk = fs::walk_dir(".").filter_entry(|entry| entry.file_name() != "target");
+    ///     for entry in walk {
+    ///         println!("{}", entry?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn filter_entry<P>(mut self, predicate: P) -> WalkDir
+    where
+        P: FnMut(&DirEntry) -> bool + Send + 'static,
+    {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    fn read_dir(&mut self, path: &Path) -> io::Result<Entries> {
+        let read_dir = read_dir(path)?;
+        Ok(match self.sorter {
+            Some(ref mut compare) => {
+                let mut entries = read_dir.collect::<Vec<_>>();
+                entries.sort_by(|a, b| match (a, b) {
+                    (Ok(a), Ok(b)) => compare(a, b),
+                    (Err(_), Ok(_)) => Ordering::Less,
+                    (Ok(_), Err(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => Ordering::Equal,
+                });
+                Entries::Sorted(entries.into_iter())
+            }
+            None => Entries::Unsorted(read_dir),
+        })
+    }
+
+    fn dir_id(&self, path: &Path) -> io::Result<Option<(u64, u64)>> {
+        if self.follow_links { Ok(metadata(path)?.as_inner().file_id()) } else { Ok(None) }
+    }
+
+    fn push_root(&mut self) -> io::Result<()> {
+        let root = self.root.clone();
+        let id = self.dir_id(&root)?;
+        let entries = self.read_dir(&root)?;
+        self.stack.push(Level { entries, id, dir: None });
+        Ok(())
+    }
+
+    // Whether the walk should descend into `entry`.
+    fn is_dir(&self, entry: &DirEntry) -> bool {
+        match entry.file_type() {
+            Ok(ty) if ty.is_dir() => true,
+            // a dangling link is yielded like any other link
+            Ok(ty) if ty.is_symlink() && self.follow_links => {
+                metadata(entry.path()).map(|m| m.is_dir()).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    // Starts reading the directory `entry`. Returns the result to yield for it.
+    fn push_dir(&mut self, entry: DirEntry) -> Option<io::Result<DirEntry>> {
+        let path = entry.path();
+        let id = match self.dir_id(&path) {
+            Ok(Some(id)) if self.stack.iter().any(|level| level.id == Some(id)) => {
+                return Some(Err(io::Error::new(
+                    ErrorKind::Other,
+                    format!("filesystem loop: {} points to an ancestor", path.display()),
+                )));
+            }
+            Ok(id) => id,
+            Err(e) => return Some(Err(e)),
+        };
+        // A directory that can't be read is still yielded, along with the error.
+        match self.read_dir(&path) {
+            Ok(entries) if self.contents_first => {
+                self.stack.push(Level { entries, id, dir: Some(entry) });
+                None
+            }
+            Ok(entries) => {
+                self.stack.push(Level { entries, id, dir: None });
+                Some(Ok(entry))
+            }
+            Err(e) if self.contents_first => {
+                self.pending = Some(Ok(entry));
+                Some(Err(e))
+            }
+            Err(e) => {
+                self.pending = Some(Err(e));
+                Some(Ok(entry))
+            }
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for WalkDir {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        if !self.started {
+            self.started = true;
+            if self.max_depth == 0 {
+                return None;
+            }
+            if let Err(e) = self.push_root() {
+                return Some(Err(e));
+            }
+        }
+        if let Some(pending) = self.pending.take() {
+            return Some(pending);
+        }
+        loop {
+            let depth = self.stack.len();
+            let entry = match self.stack.last_mut()?.entries.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(e)) => return Some(Err(e)),
+                None => match self.stack.pop().unwrap().dir {
+                    Some(dir) => return Some(Ok(dir)),
+                    None => continue,
+                },
+            };
+            if let Some(ref mut filter) = self.filter {
+                if !filter(&entry) {
+                    continue;
+                }
+            }
+            if depth < self.max_depth && self.is_dir(&entry) {
+                match self.push_dir(entry) {
+                    Some(result) => return Some(result),
+                    None => continue,
+                }
+            }
+            return Some(Ok(entry));
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDir")
+            .field("root", &self.root)
+            .field("max_depth", &self.max_depth)
+            .field("follow_links", &self.follow_links)
+            .field("contents_first", &self.contents_first)
+            .finish()
+    }
+}
@@ -4,6 +4,8 @@
 
 use crate::fs::Metadata;
 use crate::sys_common::AsInner;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::{fs::File, io, path::Path, sys};
 
 #[allow(deprecated)]
 use crate::os::linux::raw;
@@ -378,3 +380,76 @@ fn st_blocks(&self) -> u64 {
         self.as_inner().as_inner().st_blocks as u64
     }
 }
+
+/// Copies many files, or many pairs of open files, sharing what was learned
+/// about the filesystems involved between the copies.
+///
+/// Each copy first tries to reflink the destination to the source, then to
+/// use `copy_file_range`, and finally falls back to reading and writing.
+/// Whether the first two work depends mostly on the source and destination
+/// filesystems and the running kernel, so once one of them fails between a
+/// pair of devices the remaining copies between those devices skip it. This
+/// saves a few failing syscalls per file when copying large directory trees.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_copy_batch)]
+/// use std::os::linux::fs::CopyBatch;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut batch = CopyBatch::new();
+///     for name in &["a.rlib", "b.rlib", "c.rlib"] {
+///         batch.copy_file(format!("cache/{}", name), format!("target/{}", name))?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[doc(cfg(target_os = "linux"))]
+#[unstable(feature = "linux_copy_batch", issue = "none")]
+#[derive(Debug)]
+pub struct CopyBatch {
+    inner: sys::fs::CopyBatch,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[doc(cfg(target_os = "linux"))]
+impl CopyBatch {
+    /// Creates a batch that hasn't seen any copies yet.
+    #[unstable(feature = "linux_copy_batch", issue = "none")]
+    pub fn new() -> CopyBatch {
+        CopyBatch { inner: sys::fs::CopyBatch::new() }
+    }
+
+    /// Copies the contents and permission bits of one file to another, like
+    /// [`fs::copy`].
+    ///
+    /// Unlike [`fs::copy`], when the destination can be reflinked the source
+    /// is not read at all and the returned length is the one the source had
+    /// when it was opened.
+    ///
+    /// [`fs::copy`]: crate::fs::copy
+    #[unstable(feature = "linux_copy_batch", issue = "none")]
+    pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(&mut self, from: P, to: Q) -> io::Result<u64> {
+        self.inner.copy_file(from.as_ref(), to.as_ref())
+    }
+
+    /// Copies the rest of `reader` into `writer`, like [`io::copy`].
+    ///
+    /// Both cursors advance by the number of bytes copied. The copy is never
+    /// done by reflinking since that would ignore the cursors.
+    #[unstable(feature = "linux_copy_batch", issue = "none")]
+    pub fn copy(&mut self, reader: &mut File, writer: &mut File) -> io::Result<u64> {
+        self.inner.copy(reader, writer)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[doc(cfg(target_os = "linux"))]
+#[unstable(feature = "linux_copy_batch", issue = "none")]
+impl Default for CopyBatch {
+    fn default() -> CopyBatch {
+        CopyBatch::new()
+    }
+}
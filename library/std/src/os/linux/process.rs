@@ -0,0 +1,162 @@
+//! Linux-specific extensions to primitives in the `std::process` module.
+
+#![unstable(feature = "linux_pidfd", issue = "none")]
+
+use crate::io::{self, Error, ErrorKind};
+use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::process;
+use crate::sys::fd::FileDesc;
+use crate::sys::process::pidfd_send_signal;
+use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+
+/// A file descriptor referring to a process.
+///
+/// Unlike a pid, a pidfd keeps referring to the same process for as long as
+/// it is open, even after the process has exited and its pid has been
+/// reused. It becomes readable when the process exits, so it can be passed
+/// to `poll`, `select` or `epoll` to wait for the process without blocking
+/// in [`Child::wait`].
+///
+/// A `PidFd` is obtained from a child spawned by a [`Command`] on which
+/// [`create_pidfd`] was enabled, and is closed when dropped.
+///
+/// [`Child::wait`]: crate::process::Child::wait
+/// [`Command`]: crate::process::Command
+/// [`create_pidfd`]: CommandExt::create_pidfd
+#[unstable(feature = "linux_pidfd", issue = "none")]
+#[derive(Debug)]
+pub struct PidFd {
+    inner: FileDesc,
+}
+
+impl PidFd {
+    /// Sends `signal` to the process this pidfd refers to.
+    ///
+    /// This translates to a `pidfd_send_signal` call, and fails with
+    /// `ESRCH` rather than signalling an unrelated process if the process
+    /// has already been reaped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_pidfd)]
+    /// use std::os::linux::process::{ChildExt, CommandExt};
+    /// use std::process::Command;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut child = Command::new("sleep").arg("10").create_pidfd(true).spawn()?;
+    ///     // SIGTERM
+    ///     child.pidfd()?.send_signal(15)?;
+    ///     child.wait()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "linux_pidfd", issue = "none")]
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
+        pidfd_send_signal(self.inner.raw(), signal)
+    }
+}
+
+impl AsInner<FileDesc> for PidFd {
+    fn as_inner(&self) -> &FileDesc {
+        &self.inner
+    }
+}
+
+impl FromInner<FileDesc> for PidFd {
+    fn from_inner(inner: FileDesc) -> PidFd {
+        PidFd { inner }
+    }
+}
+
+impl IntoInner<FileDesc> for PidFd {
+    fn into_inner(self) -> FileDesc {
+        self.inner
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.raw()
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl FromRawFd for PidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> PidFd {
+        PidFd { inner: FileDesc::new(fd) }
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl IntoRawFd for PidFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw()
+    }
+}
+
+/// Linux-specific extensions to [`process::Child`].
+#[unstable(feature = "linux_pidfd", issue = "none")]
+pub trait ChildExt {
+    /// Returns the pidfd created for this child.
+    ///
+    /// An error is returned if [`create_pidfd`] wasn't enabled when the
+    /// child was spawned, if the kernel doesn't support pidfds (they were
+    /// added in Linux 5.3), or if the pidfd has already been taken with
+    /// [`take_pidfd`].
+    ///
+    /// [`create_pidfd`]: CommandExt::create_pidfd
+    /// [`take_pidfd`]: ChildExt::take_pidfd
+    #[unstable(feature = "linux_pidfd", issue = "none")]
+    fn pidfd(&self) -> io::Result<&PidFd>;
+
+    /// Takes ownership of the pidfd created for this child.
+    ///
+    /// Errors are returned in the same cases as for [`pidfd`]. Once the
+    /// pidfd has been taken, [`Child::kill`] falls back to signalling the
+    /// child by its pid.
+    ///
+    /// [`pidfd`]: ChildExt::pidfd
+    /// [`Child::kill`]: crate::process::Child::kill
+    #[unstable(feature = "linux_pidfd", issue = "none")]
+    fn take_pidfd(&mut self) -> io::Result<PidFd>;
+}
+
+/// Linux-specific extensions to the [`process::Command`] builder.
+#[unstable(feature = "linux_pidfd", issue = "none")]
+pub trait CommandExt {
+    /// Sets whether a pidfd should be created for the child process spawned
+    /// by this command. It can then be retrieved with [`ChildExt::pidfd`].
+    ///
+    /// When a pidfd is available, [`Child::kill`] uses it to deliver the
+    /// signal, so the signal can never reach an unrelated process that
+    /// happened to reuse the child's pid.
+    ///
+    /// [`Child::kill`]: crate::process::Child::kill
+    #[unstable(feature = "linux_pidfd", issue = "none")]
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+}
+
+fn no_pidfd() -> Error {
+    Error::new(ErrorKind::Other, "no pidfd was created for this process")
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl ChildExt for process::Child {
+    fn pidfd(&self) -> io::Result<&PidFd> {
+        self.as_inner().pidfd().ok_or_else(no_pidfd)
+    }
+
+    fn take_pidfd(&mut self) -> io::Result<PidFd> {
+        self.as_inner_mut().take_pidfd().ok_or_else(no_pidfd)
+    }
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl CommandExt for process::Command {
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
+        self.as_inner_mut().create_pidfd(val);
+        self
+    }
+}
@@ -78,7 +78,8 @@ pub fn timed_out(&self) -> bool {
 ///
 /// Functions in this module will block the current **thread** of execution.
 /// Note that any attempt to use multiple mutexes on the same condition
-/// variable may result in a runtime panic.
+/// variable is a logic error. On some platforms it results in a runtime
+/// panic, on others it goes undetected.
 ///
 /// # Examples
 ///
@@ -149,7 +150,9 @@ pub fn new() -> Condvar {
     /// # Panics
     ///
     /// This function may [`panic!`] if it is used with more than one mutex
-    /// over time.
+    /// over time. This is not guaranteed: on platforms where a [`Mutex`] may
+    /// move between uses, such as Linux and Android, the condition variable
+    /// can't tell mutexes apart and the misuse goes undetected.
     ///
     /// [`notify_one`]: Self::notify_one
     /// [`notify_all`]: Self::notify_all
@@ -0,0 +1,394 @@
+use super::*;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::Arc;
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn smoke() {
+    let (tx, rx) = channel();
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv(), Ok(1));
+
+    for cap in 0..3 {
+        let (tx, rx) = sync_channel(cap);
+        let t = thread::spawn(move || tx.send(2).unwrap());
+        assert_eq!(rx.recv(), Ok(2));
+        t.join().unwrap();
+    }
+}
+
+#[test]
+fn capacity_and_len() {
+    let (tx, rx) = channel::<i32>();
+    assert_eq!(tx.capacity(), None);
+    assert!(!tx.is_full());
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(rx.len(), 2);
+    assert!(!rx.is_empty());
+
+    let (tx, rx) = sync_channel::<i32>(2);
+    assert_eq!(rx.capacity(), Some(2));
+    assert!(tx.is_empty());
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert!(tx.is_full());
+    assert_eq!(tx.len(), 2);
+    assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+
+    let (tx, rx) = sync_channel::<i32>(0);
+    assert_eq!(tx.capacity(), Some(0));
+    assert!(rx.is_empty());
+    assert!(rx.is_full());
+    assert_eq!(rx.len(), 0);
+}
+
+#[test]
+fn same_channel() {
+    let (tx, rx) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    assert!(tx.same_channel(&tx.clone()));
+    assert!(rx.same_channel(&rx.clone()));
+    assert!(!tx.same_channel(&tx2));
+    assert!(!rx.same_channel(&rx2));
+}
+
+#[test]
+fn disconnect_senders() {
+    for cap in &[None, Some(0), Some(1)] {
+        let (tx, rx) = match *cap {
+            None => channel::<i32>(),
+            Some(cap) => sync_channel(cap),
+        };
+        let tx2 = tx.clone();
+        drop(tx);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        drop(tx2);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+}
+
+#[test]
+fn disconnect_receivers() {
+    for cap in &[None, Some(0), Some(1)] {
+        let (tx, rx) = match *cap {
+            None => channel::<i32>(),
+            Some(cap) => sync_channel(cap),
+        };
+        let rx2 = rx.clone();
+        drop(rx);
+        drop(rx2);
+        assert_eq!(tx.send(1), Err(SendError(1)));
+        assert_eq!(tx.try_send(2), Err(TrySendError::Disconnected(2)));
+    }
+}
+
+#[test]
+fn messages_received_after_disconnect() {
+    let (tx, rx) = sync_channel(3);
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    drop(tx);
+    assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+fn wake_blocked_receiver_on_disconnect() {
+    for cap in &[None, Some(0), Some(1)] {
+        let (tx, rx) = match *cap {
+            None => channel::<i32>(),
+            Some(cap) => sync_channel(cap),
+        };
+        let t = thread::spawn(move || rx.recv());
+        thread::sleep(ms(50));
+        drop(tx);
+        assert_eq!(t.join().unwrap(), Err(RecvError));
+    }
+}
+
+#[test]
+fn wake_blocked_sender_on_disconnect() {
+    for &cap in &[0, 1] {
+        let (tx, rx) = sync_channel(cap);
+        if cap == 1 {
+            tx.send(0).unwrap();
+        }
+        let t = thread::spawn(move || tx.send(1));
+        thread::sleep(ms(50));
+        drop(rx);
+        assert_eq!(t.join().unwrap(), Err(SendError(1)));
+    }
+}
+
+#[test]
+fn drop_unreceived_messages() {
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // Enough messages to span several blocks of the unbounded channel.
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = channel();
+    for _ in 0..100 {
+        tx.send(DropCounter(drops.clone())).unwrap();
+    }
+    drop(rx.recv().unwrap());
+    drop(rx);
+    assert_eq!(drops.load(Ordering::SeqCst), 100);
+    drop(tx);
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = sync_channel(10);
+    for _ in 0..10 {
+        tx.send(DropCounter(drops.clone())).unwrap();
+    }
+    drop(tx);
+    drop(rx);
+    assert_eq!(drops.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+fn recv_timeout() {
+    for cap in &[None, Some(0), Some(1)] {
+        let (tx, rx) = match *cap {
+            None => channel::<i32>(),
+            Some(cap) => sync_channel(cap),
+        };
+        assert_eq!(rx.recv_timeout(ms(10)), Err(RecvTimeoutError::Timeout));
+
+        let t = thread::spawn(move || {
+            thread::sleep(ms(50));
+            tx.send(1).unwrap();
+        });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(1));
+        t.join().unwrap();
+        assert_eq!(rx.recv_timeout(ms(10)), Err(RecvTimeoutError::Disconnected));
+    }
+}
+
+#[test]
+fn recv_deadline() {
+    let (_tx, rx) = channel::<i32>();
+    let start = Instant::now();
+    let deadline = start + ms(50);
+    assert_eq!(rx.recv_deadline(deadline), Err(RecvTimeoutError::Timeout));
+    assert!(Instant::now() >= deadline);
+}
+
+#[test]
+fn send_timeout() {
+    let (tx, rx) = sync_channel(1);
+    tx.send(1).unwrap();
+    assert_eq!(tx.send_timeout(2, ms(10)), Err(SendTimeoutError::Timeout(2)));
+
+    let t = thread::spawn(move || {
+        thread::sleep(ms(50));
+        rx.recv().unwrap()
+    });
+    assert_eq!(tx.send_timeout(3, Duration::from_secs(10)), Ok(()));
+    assert_eq!(t.join().unwrap(), 1);
+    assert_eq!(tx.send_timeout(4, ms(10)), Err(SendTimeoutError::Disconnected(4)));
+}
+
+#[test]
+fn zero_capacity_rendezvous() {
+    let (tx, rx) = sync_channel(0);
+    assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+    assert_eq!(tx.send_timeout(2, ms(10)), Err(SendTimeoutError::Timeout(2)));
+
+    let t = thread::spawn(move || {
+        for i in 0..10 {
+            tx.send(i).unwrap();
+        }
+    });
+    for i in 0..10 {
+        assert_eq!(rx.recv(), Ok(i));
+    }
+    t.join().unwrap();
+}
+
+#[test]
+fn multiple_receivers() {
+    const THREADS: usize = 4;
+    const MESSAGES: usize = 1_000;
+
+    for cap in &[None, Some(0), Some(1), Some(16)] {
+        let (tx, rx) = match *cap {
+            None => channel(),
+            Some(cap) => sync_channel(cap),
+        };
+        let (done_tx, done_rx) = channel();
+
+        let threads = (0..THREADS)
+            .map(|_| {
+                let rx = rx.clone();
+                let done_tx = done_tx.clone();
+                thread::spawn(move || {
+                    let sum: usize = rx.iter().sum();
+                    done_tx.send(sum).unwrap();
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(rx);
+        drop(done_tx);
+
+        for i in 0..MESSAGES {
+            tx.send(i).unwrap();
+        }
+        drop(tx);
+
+        for t in threads {
+            t.join().unwrap();
+        }
+        assert_eq!(done_rx.iter().sum::<usize>(), MESSAGES * (MESSAGES - 1) / 2);
+    }
+}
+
+#[test]
+fn select_recv() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<&str>(0);
+
+    let t = thread::spawn(move || {
+        thread::sleep(ms(50));
+        tx2.send("hello").unwrap();
+    });
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_ne!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx2), Ok("hello"));
+    t.join().unwrap();
+    drop(tx1);
+}
+
+#[test]
+fn select_send() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, rx2) = sync_channel::<i32>(0);
+    tx1.send(0).unwrap();
+
+    let t = thread::spawn(move || {
+        thread::sleep(ms(50));
+        rx2.recv().unwrap()
+    });
+
+    // `tx1` is full, so only the rendezvous with the spawned thread can complete.
+    let mut sel = Select::new();
+    let oper1 = sel.send(&tx1);
+    let oper2 = sel.send(&tx2);
+    let oper = sel.select();
+    match oper.index() {
+        i if i == oper1 => panic!("sent into a full channel"),
+        i if i == oper2 => oper.send(&tx2, 7).unwrap(),
+        _ => unreachable!(),
+    }
+    assert_eq!(t.join().unwrap(), 7);
+    assert_eq!(rx1.recv(), Ok(0));
+}
+
+#[test]
+fn select_disconnected() {
+    let (tx, rx) = channel::<i32>();
+    drop(tx);
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx);
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx), Err(RecvError));
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    assert!(sel.try_select().is_err());
+    assert_eq!(sel.select_timeout(ms(50)).err(), Some(SelectTimeoutError));
+}
+
+#[test]
+fn select_remove() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    sel.remove(oper1);
+    assert!(sel.try_select().is_err());
+
+    let oper3 = sel.recv(&rx1);
+    assert_ne!(oper3, oper2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper3);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+}
+
+#[test]
+#[should_panic(expected = "passed a receiver that wasn't selected")]
+fn select_wrong_receiver() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    let oper = sel.select();
+    let _ = oper.recv(&rx2);
+}
+
+#[test]
+fn select_stress() {
+    const MESSAGES: usize = 1_000;
+
+    for cap in &[None, Some(0), Some(1)] {
+        let ((tx1, rx1), (tx2, rx2)) = match *cap {
+            None => (channel::<usize>(), channel::<usize>()),
+            Some(cap) => (sync_channel(cap), sync_channel(cap)),
+        };
+
+        let t = thread::spawn(move || {
+            for i in 0..MESSAGES {
+                if i % 2 == 0 {
+                    tx1.send(i).unwrap();
+                } else {
+                    tx2.send(i).unwrap();
+                }
+            }
+        });
+
+        let mut sum = 0;
+        for _ in 0..MESSAGES {
+            let mut sel = Select::new();
+            let oper1 = sel.recv(&rx1);
+            let oper2 = sel.recv(&rx2);
+            let oper = sel.select();
+            sum += match oper.index() {
+                i if i == oper1 => oper.recv(&rx1).unwrap(),
+                i if i == oper2 => oper.recv(&rx2).unwrap(),
+                _ => unreachable!(),
+            };
+        }
+        assert_eq!(sum, MESSAGES * (MESSAGES - 1) / 2);
+        t.join().unwrap();
+    }
+}
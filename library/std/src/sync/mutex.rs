@@ -8,6 +8,7 @@
 use crate::ptr;
 use crate::sys_common::mutex as sys;
 use crate::sys_common::poison::{self, LockResult, TryLockError, TryLockResult};
+use crate::time::{Duration, Instant};
 
 /// A mutual exclusion primitive useful for protecting shared data
 ///
@@ -212,7 +213,8 @@ impl<T> Mutex<T> {
     /// let mutex = Mutex::new(0);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn new(t: T) -> Mutex<T> {
+    #[rustc_const_unstable(feature = "const_locks", issue = "none")]
+    pub const fn new(t: T) -> Mutex<T> {
         Mutex {
             inner: sys::MovableMutex::new(),
             poison: poison::Flag::new(),
@@ -309,6 +311,84 @@ pub fn try_lock(&self) -> TryLockResult<MutexGuard<'_, T>> {
         }
     }
 
+    /// Attempts to acquire this lock, blocking the current thread for at most
+    /// `timeout`.
+    ///
+    /// If the lock could not be acquired before the timeout expired, then
+    /// [`Err`] is returned. Otherwise, an RAII guard is returned. The lock
+    /// will be unlocked when the guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// If another user of this mutex panicked while holding the mutex, then
+    /// this call will return an error if the mutex would otherwise be
+    /// acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(lock_timeout)]
+    /// use std::sync::{Arc, Mutex, TryLockError};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Arc::new(Mutex::new(0));
+    /// let c_mutex = Arc::clone(&mutex);
+    ///
+    /// let guard = mutex.lock().unwrap();
+    /// thread::spawn(move || {
+    ///     match c_mutex.try_lock_for(Duration::from_millis(10)) {
+    ///         Err(TryLockError::WouldBlock) => {}
+    ///         _ => unreachable!(),
+    ///     }
+    /// }).join().expect("thread::spawn failed");
+    /// drop(guard);
+    /// ```
+    #[unstable(feature = "lock_timeout", issue = "none")]
+    pub fn try_lock_for(&self, timeout: Duration) -> TryLockResult<MutexGuard<'_, T>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.try_lock_until(deadline),
+            // A deadline this far away is never reached.
+            None => Ok(self.lock()?),
+        }
+    }
+
+    /// Attempts to acquire this lock, blocking the current thread until
+    /// `deadline` at most.
+    ///
+    /// If the lock could not be acquired before the deadline, then [`Err`] is
+    /// returned. Otherwise, an RAII guard is returned. The lock will be
+    /// unlocked when the guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// If another user of this mutex panicked while holding the mutex, then
+    /// this call will return an error if the mutex would otherwise be
+    /// acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(lock_timeout)]
+    /// use std::sync::Mutex;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// *mutex.try_lock_until(deadline).unwrap() += 1;
+    /// assert_eq!(*mutex.lock().unwrap(), 1);
+    /// ```
+    #[unstable(feature = "lock_timeout", issue = "none")]
+    pub fn try_lock_until(&self, deadline: Instant) -> TryLockResult<MutexGuard<'_, T>> {
+        unsafe {
+            if self.inner.try_lock_until(deadline) {
+                Ok(MutexGuard::new(self)?)
+            } else {
+                Err(TryLockError::WouldBlock)
+            }
+        }
+    }
+
     /// Determines whether the mutex is poisoned.
     ///
     /// If another thread is active, the mutex can still become poisoned at any
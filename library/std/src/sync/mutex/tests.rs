@@ -2,6 +2,7 @@
 use crate::sync::mpsc::channel;
 use crate::sync::{Arc, Condvar, Mutex};
 use crate::thread;
+use crate::time::{Duration, Instant};
 
 struct Packet<T>(Arc<(Mutex<T>, Condvar)>);
 
@@ -15,6 +16,19 @@ fn smoke() {
     drop(m.lock().unwrap());
 }
 
+#[test]
+fn const_new() {
+    static M: Mutex<i32> = Mutex::new(0);
+    *M.lock().unwrap() += 1;
+    let m = Mutex::new(0);
+    *m.lock().unwrap() += 1;
+    // A used mutex may still be moved.
+    let m = Box::new(m);
+    *m.lock().unwrap() += 1;
+    assert_eq!(*M.lock().unwrap(), 1);
+    assert_eq!(m.into_inner().unwrap(), 2);
+}
+
 #[test]
 fn lots_and_lots() {
     const J: u32 = 1000;
@@ -57,6 +71,32 @@ fn try_lock() {
     *m.try_lock().unwrap() = ();
 }
 
+#[test]
+fn try_lock_for() {
+    let m = Arc::new(Mutex::new(0));
+    *m.try_lock_for(Duration::from_millis(10)).unwrap() += 1;
+
+    let guard = m.lock().unwrap();
+    let m2 = m.clone();
+    let start = Instant::now();
+    thread::spawn(move || {
+        assert!(m2.try_lock_for(Duration::from_millis(50)).is_err());
+    })
+    .join()
+    .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    // A waiting thread gets the lock once it's released.
+    let m2 = m.clone();
+    let waiter = thread::spawn(move || {
+        *m2.try_lock_for(Duration::from_secs(60)).unwrap() += 1;
+    });
+    thread::sleep(Duration::from_millis(10));
+    drop(guard);
+    waiter.join().unwrap();
+    assert_eq!(*m.lock().unwrap(), 2);
+}
+
 #[test]
 fn test_into_inner() {
     let m = Mutex::new(NonCopy(10));
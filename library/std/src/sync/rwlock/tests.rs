@@ -1,7 +1,8 @@
 use crate::sync::atomic::{AtomicUsize, Ordering};
 use crate::sync::mpsc::channel;
-use crate::sync::{Arc, RwLock, TryLockError};
+use crate::sync::{Arc, RwLock, RwLockUpgradableReadGuard, TryLockError};
 use crate::thread;
+use crate::time::Duration;
 use rand::{self, Rng};
 
 #[derive(Eq, PartialEq, Debug)]
@@ -16,6 +17,16 @@ fn smoke() {
     drop(l.write().unwrap());
 }
 
+#[test]
+fn const_new() {
+    static L: RwLock<i32> = RwLock::new(0);
+    *L.write().unwrap() += 1;
+    assert_eq!(*L.read().unwrap(), 1);
+    let l = RwLock::new(0);
+    drop(RwLock::new(0));
+    assert_eq!(l.into_inner().unwrap(), 0);
+}
+
 #[test]
 fn frob() {
     const N: u32 = 10;
@@ -181,6 +192,90 @@ fn test_rwlock_try_write() {
     drop(read_guard);
 }
 
+#[test]
+fn test_rwlock_try_for() {
+    let lock = Arc::new(RwLock::new(0));
+
+    let write_guard = lock.write().unwrap();
+    let lock2 = lock.clone();
+    thread::spawn(move || {
+        assert!(lock2.try_read_for(Duration::from_millis(10)).is_err());
+        assert!(lock2.try_write_for(Duration::from_millis(10)).is_err());
+    })
+    .join()
+    .unwrap();
+
+    // A waiting reader gets the lock once the writer is done.
+    let lock2 = lock.clone();
+    let reader = thread::spawn(move || *lock2.try_read_for(Duration::from_secs(60)).unwrap());
+    thread::sleep(Duration::from_millis(10));
+    drop(write_guard);
+    assert_eq!(reader.join().unwrap(), 0);
+
+    let read_guard = lock.read().unwrap();
+    assert!(lock.try_read_for(Duration::from_millis(10)).is_ok());
+    assert!(lock.try_write_for(Duration::from_millis(10)).is_err());
+    drop(read_guard);
+    *lock.try_write_for(Duration::from_millis(10)).unwrap() = 1;
+}
+
+#[test]
+fn test_rwlock_upgradable_read() {
+    let lock = Arc::new(RwLock::new(0));
+
+    let upgradable = lock.upgradable_read().unwrap();
+    assert_eq!(*lock.try_read().unwrap(), 0);
+    assert!(matches!(lock.try_upgradable_read(), Err(TryLockError::WouldBlock)));
+    assert!(matches!(lock.try_write(), Err(TryLockError::WouldBlock)));
+    drop(upgradable);
+
+    // Upgrading waits for the other readers.
+    let read_guard = lock.read().unwrap();
+    let lock2 = lock.clone();
+    let upgrader = thread::spawn(move || {
+        let upgradable = lock2.upgradable_read().unwrap();
+        let mut write_guard = RwLockUpgradableReadGuard::upgrade(upgradable).unwrap();
+        *write_guard += 1;
+    });
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(*read_guard, 0);
+    drop(read_guard);
+    upgrader.join().unwrap();
+    assert_eq!(*lock.read().unwrap(), 1);
+    assert!(lock.try_upgradable_read().is_ok());
+}
+
+#[test]
+fn test_rwlock_upgradable_frob() {
+    const N: usize = 10;
+    const M: usize = 100;
+
+    let lock = Arc::new(RwLock::new(0));
+    let threads: Vec<_> = (0..N)
+        .map(|i| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..M {
+                    if i % 2 == 0 {
+                        drop(lock.read().unwrap());
+                    } else {
+                        // Nothing can change the value between reading and writing it.
+                        let upgradable = lock.upgradable_read().unwrap();
+                        let value = *upgradable;
+                        let mut write_guard =
+                            RwLockUpgradableReadGuard::upgrade(upgradable).unwrap();
+                        *write_guard = value + 1;
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(*lock.read().unwrap(), N / 2 * M);
+}
+
 #[test]
 fn test_into_inner() {
     let m = RwLock::new(NonCopy(10));
@@ -26,12 +26,6 @@ pub const fn new() -> Mutex {
         Mutex(RWLock::new())
     }
 
-    pub unsafe fn init(&mut self) {
-        // This function should normally reinitialize the mutex after
-        // moving it to a different memory address. This implementation
-        // does not require adjustments after moving.
-    }
-
     pub unsafe fn try_lock(&self) -> bool {
         self.0.try_write()
     }
@@ -6,6 +6,8 @@
 use crate::sys::time::SystemTime;
 use crate::sys::{unsupported, Void};
 
+pub use crate::sys_common::fs::write_atomic;
+
 pub struct File(Void);
 
 pub struct FileAttr(Void);
@@ -37,6 +39,10 @@ pub fn file_type(&self) -> FileType {
         match self.0 {}
     }
 
+    pub fn file_id(&self) -> Option<(u64, u64)> {
+        match self.0 {}
+    }
+
     pub fn modified(&self) -> io::Result<SystemTime> {
         match self.0 {}
     }
@@ -190,6 +196,26 @@ pub fn datasync(&self) -> io::Result<()> {
         match self.0 {}
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        match self.0 {}
+    }
+
     pub fn truncate(&self, _size: u64) -> io::Result<()> {
         match self.0 {}
     }
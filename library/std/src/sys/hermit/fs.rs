@@ -13,6 +13,7 @@
 use crate::sys_common::os_str_bytes::OsStrExt;
 
 pub use crate::sys_common::fs::copy;
+pub use crate::sys_common::fs::write_atomic;
 //pub use crate::sys_common::fs::remove_dir_all;
 
 fn cstr(path: &Path) -> io::Result<CString> {
@@ -61,6 +62,10 @@ pub fn file_type(&self) -> FileType {
         match self.0 {}
     }
 
+    pub fn file_id(&self) -> Option<(u64, u64)> {
+        match self.0 {}
+    }
+
     pub fn modified(&self) -> io::Result<SystemTime> {
         match self.0 {}
     }
@@ -289,6 +294,26 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn truncate(&self, _size: u64) -> io::Result<()> {
         Err(Error::from_raw_os_error(22))
     }
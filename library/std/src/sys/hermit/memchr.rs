@@ -1 +1 @@
-pub use core::slice::memchr::{memchr, memrchr};
+pub use crate::sys_common::memchr::{memchr, memrchr};
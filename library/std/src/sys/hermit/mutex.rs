@@ -5,6 +5,7 @@
 use crate::ptr;
 use crate::sync::atomic::{spin_loop_hint, AtomicUsize, Ordering};
 use crate::sys::hermit::abi;
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
 
 /// This type provides a lock based on busy waiting to realize mutual exclusion
 ///
@@ -155,11 +156,23 @@ pub struct Mutex {
     inner: Spinlock<MutexInner>,
 }
 
-pub type MovableMutex = Box<Mutex>;
+pub type MovableMutex = LazyBox<Mutex>;
 
 unsafe impl Send for Mutex {}
 unsafe impl Sync for Mutex {}
 
+impl LazyInit for Mutex {
+    fn init() -> Box<Self> {
+        let mut mutex = box Mutex::new();
+        unsafe { mutex.init() };
+        mutex
+    }
+
+    unsafe fn destroy(&self) {
+        Mutex::destroy(self)
+    }
+}
+
 impl Mutex {
     pub const fn new() -> Mutex {
         Mutex { inner: Spinlock::new(MutexInner::new()) }
@@ -1,5 +1,7 @@
 use fortanix_sgx_abi::Tcs;
 
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
+
 use super::abi::thread;
 
 use super::waitqueue::{try_lock_or_false, NotifiedTcs, SpinMutex, WaitQueue, WaitVariable};
@@ -8,7 +10,19 @@ pub struct Mutex {
     inner: SpinMutex<WaitVariable<bool>>,
 }
 
-pub type MovableMutex = Box<Mutex>;
+pub type MovableMutex = LazyBox<Mutex>;
+
+impl LazyInit for Mutex {
+    fn init() -> Box<Self> {
+        let mut mutex = box Mutex::new();
+        unsafe { mutex.init() };
+        mutex
+    }
+
+    unsafe fn destroy(&self) {
+        Mutex::destroy(self)
+    }
+}
 
 // Implementation according to “Operating Systems: Three Easy Pieces”, chapter 28
 impl Mutex {
@@ -452,3 +452,310 @@ fn test_unix_datagram_peek_from() {
     assert_eq!(size, 11);
     assert_eq!(msg, &buf[..]);
 }
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_send_vectored_fds_unix_stream() {
+    let (s1, s2) = or_panic!(UnixStream::pair());
+
+    let buf1 = [1; 8];
+    let bufs_send = &[IoSlice::new(&buf1[..])][..];
+
+    let mut ancillary1_buffer = [0; 128];
+    let mut ancillary1 = SocketAncillary::new(&mut ancillary1_buffer[..]);
+    assert!(ancillary1.add_fds(&[s1.as_raw_fd()][..]));
+
+    let usize = or_panic!(s1.send_vectored_with_ancillary(&bufs_send, &mut ancillary1));
+    assert_eq!(usize, 8);
+
+    let mut buf2 = [0; 8];
+    let mut bufs_recv = &mut [IoSliceMut::new(&mut buf2[..])][..];
+
+    let mut ancillary2_buffer = [0; 128];
+    let mut ancillary2 = SocketAncillary::new(&mut ancillary2_buffer[..]);
+
+    let usize = or_panic!(s2.recv_vectored_with_ancillary(&mut bufs_recv, &mut ancillary2));
+    assert_eq!(usize, 8);
+    assert_eq!(buf1, buf2);
+    assert!(!ancillary2.truncated());
+
+    let mut ancillary_data_vec = ancillary2.messages().collect::<Vec<_>>();
+    assert_eq!(ancillary_data_vec.len(), 1);
+    if let AncillaryData::ScmRights(scm_rights) = ancillary_data_vec.pop().unwrap().unwrap() {
+        let fd_vec = scm_rights.collect::<Vec<_>>();
+        assert_eq!(fd_vec.len(), 1);
+        unsafe {
+            libc::close(fd_vec[0]);
+        }
+    } else {
+        unreachable!("must be ScmRights");
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_send_vectored_fds_usable() {
+    use crate::fs::File;
+
+    let dir = tmpdir();
+    let (s1, s2) = or_panic!(UnixStream::pair());
+    let mut file = or_panic!(File::create(dir.path().join("passed")));
+
+    let buf1 = [1; 4];
+    let bufs_send = &[IoSlice::new(&buf1[..])][..];
+
+    let mut ancillary1_buffer = [0; 128];
+    let mut ancillary1 = SocketAncillary::new(&mut ancillary1_buffer[..]);
+    assert!(ancillary1.add_fds(&[file.as_raw_fd()][..]));
+    or_panic!(s1.send_vectored_with_ancillary(&bufs_send, &mut ancillary1));
+
+    let mut buf2 = [0; 4];
+    let mut bufs_recv = &mut [IoSliceMut::new(&mut buf2[..])][..];
+    let mut ancillary2_buffer = [0; 128];
+    let mut ancillary2 = SocketAncillary::new(&mut ancillary2_buffer[..]);
+    or_panic!(s2.recv_vectored_with_ancillary(&mut bufs_recv, &mut ancillary2));
+
+    let fd = match ancillary2.messages().next() {
+        Some(Ok(AncillaryData::ScmRights(mut scm_rights))) => scm_rights.next().unwrap(),
+        _ => unreachable!("must be ScmRights"),
+    };
+    assert_ne!(fd, file.as_raw_fd());
+
+    // Both descriptors refer to the same open file description, so writes through the
+    // received descriptor are visible through the original file.
+    let mut received = unsafe { File::from_raw_fd(fd) };
+    or_panic!(received.write_all(b"passed"));
+    drop(received);
+
+    or_panic!(file.flush());
+    let contents = or_panic!(crate::fs::read(dir.path().join("passed")));
+    assert_eq!(contents, b"passed");
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_send_vectored_with_ancillary_unix_datagram() {
+    let (bsock1, bsock2) = or_panic!(UnixDatagram::pair());
+
+    unsafe {
+        let optval: libc::c_int = 1;
+        or_panic!(cvt(libc::setsockopt(
+            bsock2.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PASSCRED,
+            &optval as *const _ as *const _,
+            mem::size_of_val(&optval) as libc::socklen_t,
+        )));
+    }
+
+    let buf1 = [1; 8];
+    let bufs_send = &[IoSlice::new(&buf1[..])][..];
+
+    let mut ancillary1_buffer = [0; 128];
+    let mut ancillary1 = SocketAncillary::new(&mut ancillary1_buffer[..]);
+    let mut cred1 = SocketCred::new();
+    cred1.set_pid(unsafe { libc::getpid() });
+    cred1.set_uid(unsafe { libc::getuid() });
+    cred1.set_gid(unsafe { libc::getgid() });
+    assert!(ancillary1.add_creds(&[cred1.clone()][..]));
+
+    let usize = or_panic!(bsock1.send_vectored_with_ancillary(&bufs_send, &mut ancillary1));
+    assert_eq!(usize, 8);
+
+    let mut buf2 = [0; 8];
+    let mut bufs_recv = &mut [IoSliceMut::new(&mut buf2[..])][..];
+
+    let mut ancillary2_buffer = [0; 128];
+    let mut ancillary2 = SocketAncillary::new(&mut ancillary2_buffer[..]);
+
+    let (usize, truncated) =
+        or_panic!(bsock2.recv_vectored_with_ancillary(&mut bufs_recv, &mut ancillary2));
+    assert_eq!(ancillary2.truncated(), false);
+    assert_eq!(usize, 8);
+    assert_eq!(truncated, false);
+    assert_eq!(buf1, buf2);
+
+    let mut ancillary_data_vec = ancillary2.messages().collect::<Vec<_>>();
+    assert_eq!(ancillary_data_vec.len(), 1);
+    if let AncillaryData::ScmCredentials(scm_credentials) =
+        ancillary_data_vec.pop().unwrap().unwrap()
+    {
+        let cred_vec = scm_credentials.collect::<Vec<_>>();
+        assert_eq!(cred_vec.len(), 1);
+        assert_eq!(cred1.get_pid(), cred_vec[0].get_pid());
+        assert_eq!(cred1.get_uid(), cred_vec[0].get_uid());
+        assert_eq!(cred1.get_gid(), cred_vec[0].get_gid());
+    } else {
+        unreachable!("must be ScmCredentials");
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_socket_ancillary_capacity() {
+    let mut buffer = [0; 4];
+    let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+    assert_eq!(ancillary.capacity(), 4);
+    assert!(!ancillary.add_fds(&[0][..]));
+    assert!(ancillary.is_empty());
+
+    let mut buffer = [0; 128];
+    let mut ancillary = SocketAncillary::new(&mut buffer[..]);
+    assert!(ancillary.add_fds(&[0, 1, 2][..]));
+    assert!(!ancillary.is_empty());
+    assert_eq!(ancillary.messages().count(), 1);
+    ancillary.clear();
+    assert!(ancillary.is_empty());
+    assert_eq!(ancillary.messages().count(), 0);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_stream_connect() {
+    let msg1 = b"hello";
+    let msg2 = b"world";
+
+    let socket_addr = or_panic!(SocketAddr::from_abstract_namespace(b"namespace"));
+    let listener = or_panic!(UnixListener::bind_addr(&socket_addr));
+
+    let thread = thread::spawn(move || {
+        let mut stream = or_panic!(listener.accept()).0;
+        let mut buf = [0; 5];
+        or_panic!(stream.read(&mut buf));
+        assert_eq!(&msg1[..], &buf[..]);
+        or_panic!(stream.write_all(msg2));
+    });
+
+    let mut stream = or_panic!(UnixStream::connect_addr(&socket_addr));
+
+    let peer = or_panic!(stream.peer_addr());
+    assert_eq!(peer.as_abstract_namespace().unwrap(), b"namespace");
+
+    or_panic!(stream.write_all(msg1));
+    let mut buf = vec![];
+    or_panic!(stream.read_to_end(&mut buf));
+    assert_eq!(&msg2[..], &buf[..]);
+    drop(stream);
+
+    thread.join().unwrap();
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_datagram_bind_send_to_addr() {
+    let addr1 = or_panic!(SocketAddr::from_abstract_namespace(b"ns1"));
+    let sock1 = or_panic!(UnixDatagram::bind_addr(&addr1));
+
+    let local = or_panic!(sock1.local_addr());
+    assert_eq!(local.as_abstract_namespace().unwrap(), b"ns1");
+    assert_eq!(local.as_pathname(), None);
+
+    let addr2 = or_panic!(SocketAddr::from_abstract_namespace(b"ns2"));
+    let sock2 = or_panic!(UnixDatagram::bind_addr(&addr2));
+
+    let msg = b"hello world";
+    or_panic!(sock1.send_to_addr(msg, &addr2));
+    let mut buf = [0; 11];
+    let (len, addr) = or_panic!(sock2.recv_from(&mut buf));
+    assert_eq!(msg, &buf[..]);
+    assert_eq!(len, 11);
+    assert_eq!(addr.as_abstract_namespace().unwrap(), b"ns1");
+
+    or_panic!(sock2.connect_addr(&addr1));
+    or_panic!(sock2.send(msg));
+    let len = or_panic!(sock1.recv(&mut buf));
+    assert_eq!(len, 11);
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_namespace_too_long() {
+    match SocketAddr::from_abstract_namespace(
+        b"abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz\
+        opqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz",
+    ) {
+        Err(ref e) if e.kind() == io::ErrorKind::InvalidInput => {}
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(addr) => panic!("unexpected success {:?}", addr),
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[test]
+fn test_abstract_namespace_debug() {
+    let addr = or_panic!(SocketAddr::from_abstract_namespace(b"a\0b"));
+    assert_eq!(addr.as_abstract_namespace(), Some(&b"a\0b"[..]));
+    assert!(!addr.is_unnamed());
+    assert_eq!(format!("{:?}", addr), "\"a\\x00b\" (abstract)");
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_seqpacket_message_boundaries() {
+    let (s1, s2) = or_panic!(UnixSeqpacket::pair());
+
+    or_panic!(s1.send(b"hello"));
+    or_panic!(s1.send(b"world!"));
+    or_panic!(s1.send(b"truncated message"));
+
+    let mut buf = [0; 16];
+    assert_eq!(or_panic!(s2.recv(&mut buf)), (5, false));
+    assert_eq!(&buf[..5], b"hello");
+    assert_eq!(or_panic!(s2.recv(&mut buf)), (6, false));
+    assert_eq!(&buf[..6], b"world!");
+
+    let mut small = [0; 9];
+    assert_eq!(or_panic!(s2.recv(&mut small)), (9, true));
+    assert_eq!(&small, b"truncated");
+
+    // The rest of the truncated message is discarded.
+    or_panic!(s2.set_nonblocking(true));
+    match s2.recv(&mut buf) {
+        Err(ref e) if e.kind() == ErrorKind::WouldBlock => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[test]
+fn test_seqpacket_listener() {
+    let dir = tmpdir();
+    let socket_path = dir.path().join("sock");
+
+    let listener = or_panic!(UnixSeqpacketListener::bind(&socket_path));
+    let thread = thread::spawn(move || {
+        let conn = or_panic!(listener.accept()).0;
+        let mut buf = [0; 16];
+        let (len, truncated) = or_panic!(conn.recv(&mut buf));
+        assert!(!truncated);
+        or_panic!(conn.send(&buf[..len]));
+        or_panic!(conn.send(&buf[..len]));
+    });
+
+    let client = or_panic!(UnixSeqpacket::connect(&socket_path));
+    assert_eq!(or_panic!(client.peer_addr()).as_pathname(), Some(&*socket_path));
+    or_panic!(client.send(b"echo"));
+
+    let mut buf = [0; 16];
+    assert_eq!(or_panic!(client.recv(&mut buf)), (4, false));
+    assert_eq!(&buf[..4], b"echo");
+    assert_eq!(or_panic!(client.recv(&mut buf)), (4, false));
+
+    thread.join().unwrap();
+    // The peer closed the connection.
+    assert_eq!(or_panic!(client.recv(&mut buf)), (0, false));
+}
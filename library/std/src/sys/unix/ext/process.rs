@@ -119,6 +119,49 @@ fn before_exec<F>(&mut self, f: F) -> &mut process::Command
     fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     where
         S: AsRef<OsStr>;
+
+    /// Sets the process group ID of the child process. This translates to a
+    /// `setpgid` call in the child process, or to `POSIX_SPAWN_SETPGROUP`
+    /// when the child is started with `posix_spawn`.
+    ///
+    /// If `pgroup` is 0, the child is placed in a new process group whose ID
+    /// is the child's process ID. Otherwise it joins the existing process
+    /// group `pgroup`. This makes it possible to signal a whole tree of
+    /// processes at once with `kill(-pgid, signal)`.
+    ///
+    /// This has no effect if [`setsid`] is also enabled, since a new session
+    /// always starts a new process group.
+    ///
+    /// Unlike using [`pre_exec`], setting this option doesn't prevent the
+    /// standard library from spawning the child with `posix_spawn`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_set_process_group)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::CommandExt;
+    ///
+    /// Command::new("sleep")
+    ///     .arg("10")
+    ///     .process_group(0)
+    ///     .spawn()
+    ///     .expect("sleep failed to start");
+    /// ```
+    ///
+    /// [`setsid`]: CommandExt::setsid
+    /// [`pre_exec`]: CommandExt::pre_exec
+    #[unstable(feature = "process_set_process_group", issue = "none")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Runs the child process in a new session. This translates to a
+    /// `setsid` call in the child process, or to `POSIX_SPAWN_SETSID` when
+    /// the child is started with `posix_spawn`.
+    ///
+    /// The child becomes the leader of both a new session and a new process
+    /// group, and has no controlling terminal.
+    #[unstable(feature = "process_set_process_group", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -160,6 +203,16 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
         self.as_inner_mut().set_arg_0(arg.as_ref());
         self
     }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`].
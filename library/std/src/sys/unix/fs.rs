@@ -269,6 +269,11 @@ pub fn perm(&self) -> FilePermissions {
     pub fn file_type(&self) -> FileType {
         FileType { mode: self.stat.st_mode as mode_t }
     }
+
+    /// The device and inode number, which together identify the file.
+    pub fn file_id(&self) -> Option<(u64, u64)> {
+        Some((self.stat.st_dev as u64, self.stat.st_ino as u64))
+    }
 }
 
 #[cfg(target_os = "netbsd")]
@@ -811,6 +816,61 @@ unsafe fn os_datasync(fd: c_int) -> c_int {
         }
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_EX).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_SH).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.flock(libc::LOCK_EX | libc::LOCK_NB)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.flock(libc::LOCK_SH | libc::LOCK_NB)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_UN).map(drop)
+    }
+
+    // `flock` locks belong to the open file description rather than the process
+    // like `fcntl` locks, the same semantics as Linux OFD locks, and work on NFS
+    // on Linux since they are emulated with byte range locks there.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn flock(&self, operation: c_int) -> io::Result<bool> {
+        match cvt_r(|| unsafe { libc::flock(self.0.raw(), operation) }) {
+            Ok(_) => Ok(true),
+            Err(ref e) if e.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    fn flock(&self, _operation: c_int) -> io::Result<bool> {
+        Err(io::Error::new(io::ErrorKind::Other, "file locking is not supported on this platform"))
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         #[cfg(target_os = "android")]
         return crate::sys::android::ftruncate64(self.0.raw(), size);
@@ -1204,18 +1264,173 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
-    let (mut reader, reader_metadata) = open_from(from)?;
-    let max_len = u64::MAX;
-    let (mut writer, _) = open_to_and_set_permissions(to, reader_metadata)?;
+    CopyBatch::new().copy_file(from, to)
+}
 
-    use super::kernel_copy::{copy_regular_files, CopyResult};
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    use crate::sys_common::fs::parent_dir;
 
-    match copy_regular_files(reader.as_raw_fd(), writer.as_raw_fd(), max_len) {
-        CopyResult::Ended(result) => result,
-        CopyResult::Fallback(written) => match io::copy::generic_copy(&mut reader, &mut writer) {
-            Ok(bytes) => Ok(bytes + written),
-            Err(e) => Err(e),
-        },
+    #[cfg(target_os = "linux")]
+    let written = write_atomic_tmpfile(path, contents);
+    #[cfg(not(target_os = "linux"))]
+    let written = None;
+
+    match written {
+        Some(result) => result?,
+        None => crate::sys_common::fs::write_atomic(path, contents)?,
+    }
+    // make the rename itself durable
+    crate::fs::File::open(parent_dir(path))?.sync_all()
+}
+
+/// Writes the contents to an unnamed file with `O_TMPFILE`, so no partially
+/// written file is left behind if the process dies, and only links it into
+/// the directory to rename it over `path` once it is complete.
+///
+/// Returns `None` if the kernel or filesystem doesn't support unnamed files.
+#[cfg(target_os = "linux")]
+fn write_atomic_tmpfile(path: &Path, contents: &[u8]) -> Option<io::Result<()>> {
+    use crate::io::Write;
+    use crate::sys_common::fs::{keep_permissions, parent_dir, temp_sibling};
+
+    let mut file = match crate::fs::OpenOptions::new()
+        .write(true)
+        .mode(0o666)
+        .custom_flags(libc::O_TMPFILE)
+        .open(parent_dir(path))
+    {
+        Ok(file) => file,
+        // Kernels prior to 3.11 ignore the flag and fail to open the directory
+        // for writing, or the filesystem doesn't support it.
+        Err(e) if matches!(e.raw_os_error(), Some(libc::EISDIR | libc::EOPNOTSUPP)) => {
+            return None;
+        }
+        Err(e) => return Some(Err(e)),
+    };
+    let result: io::Result<()> = try {
+        file.write_all(contents)?;
+        keep_permissions(path, &file)?;
+        file.sync_all()?;
+    };
+    if let Err(e) = result {
+        return Some(Err(e));
+    }
+
+    // Linking the file from /proc doesn't need `CAP_DAC_READ_SEARCH` like `AT_EMPTY_PATH` does.
+    let proc_path = cstr(Path::new(&format!("/proc/self/fd/{}", file.as_raw_fd()))).unwrap();
+    let temp = loop {
+        let temp = match temp_sibling(path) {
+            Ok(temp) => temp,
+            Err(e) => return Some(Err(e)),
+        };
+        let c_temp = match cstr(&temp) {
+            Ok(c_temp) => c_temp,
+            Err(e) => return Some(Err(e)),
+        };
+        let linked = cvt(unsafe {
+            libc::linkat(
+                libc::AT_FDCWD,
+                proc_path.as_ptr(),
+                libc::AT_FDCWD,
+                c_temp.as_ptr(),
+                libc::AT_SYMLINK_FOLLOW,
+            )
+        });
+        match linked {
+            Ok(_) => break temp,
+            Err(ref e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            // /proc isn't mounted
+            Err(ref e) if e.kind() == ErrorKind::NotFound => return None,
+            Err(e) => return Some(Err(e)),
+        }
+    };
+    let result = rename(&temp, path);
+    if result.is_err() {
+        let _ = unlink(&temp);
+    }
+    Some(result)
+}
+
+/// State shared by a series of copies on Linux.
+///
+/// Whether a reflink or `copy_file_range` works is mostly a property of the source and
+/// destination filesystems, so after the first failure between a pair of devices the
+/// remaining copies between them skip straight to the next strategy.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug)]
+pub struct CopyBatch {
+    // (source, destination) device pairs on which `FICLONE` failed
+    no_reflink: Vec<(u64, u64)>,
+    // (source, destination) device pairs on which `copy_file_range` fell back
+    no_copy_file_range: Vec<(u64, u64)>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl CopyBatch {
+    pub fn new() -> CopyBatch {
+        CopyBatch { no_reflink: Vec::new(), no_copy_file_range: Vec::new() }
+    }
+
+    pub fn copy_file(&mut self, from: &Path, to: &Path) -> io::Result<u64> {
+        use super::kernel_copy::reflink;
+
+        let (mut reader, reader_metadata) = open_from(from)?;
+        let len = reader_metadata.len();
+        let reader_dev = reader_metadata.dev();
+        let (mut writer, writer_metadata) = open_to_and_set_permissions(to, reader_metadata)?;
+        if !writer_metadata.is_file() {
+            return io::copy(&mut reader, &mut writer);
+        }
+        let devs = (reader_dev, writer_metadata.dev());
+
+        // The destination was just truncated, so cloning the whole source is exactly a copy.
+        if !self.no_reflink.contains(&devs) {
+            match reflink(reader.as_raw_fd(), writer.as_raw_fd()) {
+                Ok(()) => return Ok(len),
+                Err(_) => self.no_reflink.push(devs),
+            }
+        }
+        self.copy_regular_files(&mut reader, &mut writer, devs)
+    }
+
+    pub fn copy(
+        &mut self,
+        reader: &mut crate::fs::File,
+        writer: &mut crate::fs::File,
+    ) -> io::Result<u64> {
+        let (reader_metadata, writer_metadata) = match (reader.metadata(), writer.metadata()) {
+            (Ok(r), Ok(w)) if r.is_file() && w.is_file() => (r, w),
+            // sendfile and splice are handled by the io::copy specialization
+            _ => return io::copy(reader, writer),
+        };
+        let devs = (reader_metadata.dev(), writer_metadata.dev());
+        self.copy_regular_files(reader, writer, devs)
+    }
+
+    fn copy_regular_files(
+        &mut self,
+        reader: &mut crate::fs::File,
+        writer: &mut crate::fs::File,
+        devs: (u64, u64),
+    ) -> io::Result<u64> {
+        use super::kernel_copy::{copy_regular_files, CopyResult};
+
+        let mut written = 0;
+        let mut probed = false;
+        if !self.no_copy_file_range.contains(&devs) {
+            match copy_regular_files(reader.as_raw_fd(), writer.as_raw_fd(), u64::MAX) {
+                CopyResult::Ended(result) => return result,
+                CopyResult::Fallback(0) => probed = true,
+                CopyResult::Fallback(bytes) => written = bytes,
+            }
+        }
+        let bytes = io::copy::generic_copy(reader, writer)?;
+        // copy_file_range also falls back when there is nothing left to copy,
+        // only remember the failure if there actually was data it refused to copy.
+        if probed && bytes > 0 {
+            self.no_copy_file_range.push(devs);
+        }
+        Ok(bytes + written)
     }
 }
 
@@ -10,9 +10,15 @@
 use crate::ptr::null;
 use crate::sync::atomic::AtomicI32;
 use crate::time::Duration;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::time::Instant;
 
+/// Waits for a `futex_wake` on `futex`, as long as its value is `expected`.
+///
+/// Returns `false` if the timeout elapsed, and `true` otherwise, including
+/// for spurious wake ups.
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) {
+pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) -> bool {
     let timespec = timeout.and_then(|d| {
         Some(libc::timespec {
             // Sleep forever if the timeout is longer than fits in a timespec.
@@ -21,19 +27,34 @@ pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) {
             tv_nsec: d.subsec_nanos() as _,
         })
     });
-    unsafe {
+    let r = unsafe {
         libc::syscall(
             libc::SYS_futex,
             futex as *const AtomicI32,
             libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
             expected,
             timespec.as_ref().map_or(null(), |d| d as *const libc::timespec),
-        );
+        )
+    };
+    r == 0 || crate::sys::os::errno() != libc::ETIMEDOUT
+}
+
+/// Like `futex_wait`, but waits until `deadline` instead of for a duration.
+///
+/// Returns `false` without waiting if the deadline has already passed.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn futex_wait_until(futex: &AtomicI32, expected: i32, deadline: Option<Instant>) -> bool {
+    match deadline {
+        None => futex_wait(futex, expected, None),
+        Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+            Some(timeout) => futex_wait(futex, expected, Some(timeout)),
+            None => false,
+        },
     }
 }
 
 #[cfg(target_os = "emscripten")]
-pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) {
+pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) -> bool {
     extern "C" {
         fn emscripten_futex_wait(
             addr: *const AtomicI32,
@@ -42,36 +63,49 @@ fn emscripten_futex_wait(
         ) -> libc::c_int;
     }
 
-    unsafe {
+    let r = unsafe {
         emscripten_futex_wait(
             futex as *const AtomicI32,
             // `val` is declared unsigned to match the Emscripten headers, but since it's used as
             // an opaque value, we can ignore the meaning of signed vs. unsigned and cast here.
             expected as libc::c_uint,
             timeout.map_or(crate::f64::INFINITY, |d| d.as_secs_f64() * 1000.0),
-        );
-    }
+        )
+    };
+    r != -libc::ETIMEDOUT
 }
 
+/// Wakes up one thread waiting on `futex`, returning whether there was one.
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub fn futex_wake(futex: &AtomicI32) {
+pub fn futex_wake(futex: &AtomicI32) -> bool {
     unsafe {
         libc::syscall(
             libc::SYS_futex,
             futex as *const AtomicI32,
             libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
             1,
+        ) > 0
+    }
+}
+
+/// Wakes up all threads waiting on `futex`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn futex_wake_all(futex: &AtomicI32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex as *const AtomicI32,
+            libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+            i32::MAX,
         );
     }
 }
 
 #[cfg(target_os = "emscripten")]
-pub fn futex_wake(futex: &AtomicI32) {
+pub fn futex_wake(futex: &AtomicI32) -> bool {
     extern "C" {
         fn emscripten_futex_wake(addr: *const AtomicI32, count: libc::c_int) -> libc::c_int;
     }
 
-    unsafe {
-        emscripten_futex_wake(futex as *const AtomicI32, 1);
-    }
+    unsafe { emscripten_futex_wake(futex as *const AtomicI32, 1) > 0 }
 }
@@ -0,0 +1,497 @@
+use crate::sync::atomic::{
+    AtomicI32,
+    Ordering::{Acquire, Relaxed, Release},
+};
+use crate::sys::futex::{futex_wait_until, futex_wake, futex_wake_all};
+use crate::time::Instant;
+
+/// A reader-writer lock built directly on futexes, which supports timeouts
+/// and upgradable reads.
+///
+/// An upgradable reader is a reader that excludes writers and other
+/// upgradable readers, but not plain readers. It can become a writer
+/// atomically, once the plain readers are gone.
+pub struct RWLock {
+    // The state consists of a 26-bit reader counter and a number of flags.
+    //
+    // Bits 0..26:
+    //   0: Unlocked
+    //   1..=0x3FF_FFFE: Locked by N readers, possibly one of them upgradable
+    //   0x3FF_FFFF: Write locked
+    // Bit 26: The upgradable reader waits to become a writer.
+    // Bit 27: Upgradable readers are waiting on `upgradable_notify`.
+    // Bit 28: One of the readers is upgradable.
+    // Bit 29: Readers are waiting on this futex.
+    // Bit 30: Writers are waiting on the writer_notify futex.
+    // Bit 31 is unused, to keep the state positive.
+    state: AtomicI32,
+    // The 'condition variable' to notify writers through.
+    // Incremented on every signal.
+    writer_notify: AtomicI32,
+    // The 'condition variable' to notify upgradable readers through, and the
+    // upgradable reader waiting to become a writer.
+    // Incremented on every signal.
+    upgradable_notify: AtomicI32,
+}
+
+const READ_LOCKED: i32 = 1;
+const MASK: i32 = (1 << 26) - 1;
+const WRITE_LOCKED: i32 = MASK;
+const MAX_READERS: i32 = MASK - 1;
+const UPGRADING: i32 = 1 << 26;
+const UPGRADABLE_WAITING: i32 = 1 << 27;
+const UPGRADABLE: i32 = 1 << 28;
+const READERS_WAITING: i32 = 1 << 29;
+const WRITERS_WAITING: i32 = 1 << 30;
+
+#[inline]
+fn is_unlocked(state: i32) -> bool {
+    state & MASK == 0
+}
+
+#[inline]
+fn is_write_locked(state: i32) -> bool {
+    state & MASK == WRITE_LOCKED
+}
+
+#[inline]
+fn has_readers_waiting(state: i32) -> bool {
+    state & READERS_WAITING != 0
+}
+
+#[inline]
+fn has_writers_waiting(state: i32) -> bool {
+    state & WRITERS_WAITING != 0
+}
+
+#[inline]
+fn is_read_lockable(state: i32) -> bool {
+    // This also returns false if the counter could overflow if we tried to read lock it.
+    //
+    // We don't allow read-locking if there's readers waiting, even if the lock is unlocked
+    // and there's no writers waiting. The only situation when this happens is after unlocking,
+    // at which point the unlocking thread might be waking up writers, which have priority over
+    // readers. The unlocking thread will clear the readers waiting bit and wake up readers, if
+    // necessary. New readers also wait for an upgrade, so it can't be starved.
+    state & MASK < MAX_READERS && state & (READERS_WAITING | WRITERS_WAITING | UPGRADING) == 0
+}
+
+#[inline]
+fn is_upgradable_lockable(state: i32) -> bool {
+    is_read_lockable(state) && state & UPGRADABLE == 0
+}
+
+#[inline]
+fn has_reached_max_readers(state: i32) -> bool {
+    state & MASK == MAX_READERS
+}
+
+impl RWLock {
+    #[inline]
+    pub const fn new() -> RWLock {
+        RWLock {
+            state: AtomicI32::new(0),
+            writer_notify: AtomicI32::new(0),
+            upgradable_notify: AtomicI32::new(0),
+        }
+    }
+
+    #[inline]
+    pub unsafe fn destroy(&self) {}
+
+    #[inline]
+    pub unsafe fn try_read(&self) -> bool {
+        self.state
+            .fetch_update(Acquire, Relaxed, |s| {
+                if is_read_lockable(s) { Some(s + READ_LOCKED) } else { None }
+            })
+            .is_ok()
+    }
+
+    #[inline]
+    pub unsafe fn read(&self) {
+        if !self.try_read_fast() {
+            self.read_contended(0, None);
+        }
+    }
+
+    #[inline]
+    pub unsafe fn try_read_until(&self, deadline: Instant) -> bool {
+        self.try_read_fast() || self.read_contended(0, Some(deadline))
+    }
+
+    #[inline]
+    pub unsafe fn try_upgradable_read(&self) -> bool {
+        self.state
+            .fetch_update(Acquire, Relaxed, |s| {
+                if is_upgradable_lockable(s) { Some(s + READ_LOCKED + UPGRADABLE) } else { None }
+            })
+            .is_ok()
+    }
+
+    #[inline]
+    pub unsafe fn upgradable_read(&self) {
+        if !self.try_upgradable_read() {
+            self.read_contended(UPGRADABLE, None);
+        }
+    }
+
+    #[inline]
+    pub unsafe fn read_unlock(&self) {
+        let state = self.state.fetch_sub(READ_LOCKED, Release) - READ_LOCKED;
+
+        // It's impossible for a reader to be waiting on a read-locked RwLock,
+        // except if there is also a writer or an upgrade waiting.
+        debug_assert!(
+            !has_readers_waiting(state) || has_writers_waiting(state) || state & UPGRADING != 0
+        );
+
+        if is_unlocked(state) && has_writers_waiting(state) {
+            // Wake up a writer if we were the last reader and there's a writer waiting.
+            self.wake_writer_or_readers(state);
+        } else if state & (MASK | UPGRADING) == READ_LOCKED | UPGRADING {
+            // Only the upgradable reader is left, and it waits to become a writer.
+            self.wake_upgradable();
+        }
+    }
+
+    #[inline]
+    pub unsafe fn upgradable_unlock(&self) {
+        let old = self
+            .state
+            .fetch_update(Release, Relaxed, |s| {
+                Some((s - READ_LOCKED) & !(UPGRADABLE | UPGRADABLE_WAITING))
+            })
+            .unwrap();
+        let state = (old - READ_LOCKED) & !(UPGRADABLE | UPGRADABLE_WAITING);
+
+        if old & UPGRADABLE_WAITING != 0 {
+            self.wake_upgradable();
+        }
+        if is_unlocked(state) && (has_writers_waiting(state) || has_readers_waiting(state)) {
+            self.wake_writer_or_readers(state);
+        }
+    }
+
+    #[inline]
+    fn try_read_fast(&self) -> bool {
+        let state = self.state.load(Relaxed);
+        let new = state + READ_LOCKED;
+        is_read_lockable(state)
+            && self.state.compare_exchange_weak(state, new, Acquire, Relaxed).is_ok()
+    }
+
+    /// Takes a read lock, which is upgradable if `upgradable` is
+    /// `UPGRADABLE`. Returns false if `deadline` passed first.
+    #[cold]
+    fn read_contended(&self, upgradable: i32, deadline: Option<Instant>) -> bool {
+        let lockable: fn(i32) -> bool =
+            if upgradable != 0 { is_upgradable_lockable } else { is_read_lockable };
+        let mut state = self.spin_read();
+
+        loop {
+            // If we can lock it, lock it.
+            if lockable(state) {
+                let new = state + READ_LOCKED + upgradable;
+                match self.state.compare_exchange_weak(state, new, Acquire, Relaxed) {
+                    Ok(_) => return true, // Locked!
+                    Err(s) => {
+                        state = s;
+                        continue;
+                    }
+                }
+            }
+
+            // Check for overflow.
+            if has_reached_max_readers(state) {
+                panic!("too many active read locks on RwLock");
+            }
+
+            if upgradable != 0 && state & UPGRADABLE != 0 {
+                // Wait for the other upgradable reader to unlock (or upgrade)
+                // on the separate futex, as the state keeps changing while
+                // plain readers come and go.
+                if state & UPGRADABLE_WAITING == 0 {
+                    let new = state | UPGRADABLE_WAITING;
+                    if let Err(s) = self.state.compare_exchange(state, new, Relaxed, Relaxed) {
+                        state = s;
+                        continue;
+                    }
+                }
+
+                let seq = self.upgradable_notify.load(Acquire);
+
+                // Don't go to sleep if the other upgradable reader is gone, or
+                // if it has already cleared the waiting bit.
+                state = self.state.load(Relaxed);
+                if state & UPGRADABLE == 0 || state & UPGRADABLE_WAITING == 0 {
+                    continue;
+                }
+
+                if !futex_wait_until(&self.upgradable_notify, seq, deadline) {
+                    return false;
+                }
+                state = self.state.load(Relaxed);
+                continue;
+            }
+
+            // Make sure the readers waiting bit is set before we go to sleep.
+            if !has_readers_waiting(state) {
+                if let Err(s) =
+                    self.state.compare_exchange(state, state | READERS_WAITING, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+            }
+
+            // Wait for the state to change.
+            if !futex_wait_until(&self.state, state | READERS_WAITING, deadline) {
+                return false;
+            }
+
+            // Spin again after waking up.
+            state = self.spin_read();
+        }
+    }
+
+    #[inline]
+    pub unsafe fn try_write(&self) -> bool {
+        self.state
+            .fetch_update(Acquire, Relaxed, |s| {
+                if is_unlocked(s) { Some(s + WRITE_LOCKED) } else { None }
+            })
+            .is_ok()
+    }
+
+    #[inline]
+    pub unsafe fn write(&self) {
+        if self.state.compare_exchange_weak(0, WRITE_LOCKED, Acquire, Relaxed).is_err() {
+            self.write_contended(None);
+        }
+    }
+
+    #[inline]
+    pub unsafe fn try_write_until(&self, deadline: Instant) -> bool {
+        self.state.compare_exchange_weak(0, WRITE_LOCKED, Acquire, Relaxed).is_ok()
+            || self.write_contended(Some(deadline))
+    }
+
+    #[inline]
+    pub unsafe fn write_unlock(&self) {
+        let state = self.state.fetch_sub(WRITE_LOCKED, Release) - WRITE_LOCKED;
+
+        debug_assert!(is_unlocked(state));
+
+        if has_writers_waiting(state) || has_readers_waiting(state) {
+            self.wake_writer_or_readers(state);
+        }
+    }
+
+    #[cold]
+    fn write_contended(&self, deadline: Option<Instant>) -> bool {
+        let mut state = self.spin_write();
+
+        let mut other_writers_waiting = 0;
+
+        loop {
+            // If it's unlocked, we try to lock it.
+            if is_unlocked(state) {
+                match self.state.compare_exchange_weak(
+                    state,
+                    state | WRITE_LOCKED | other_writers_waiting,
+                    Acquire,
+                    Relaxed,
+                ) {
+                    Ok(_) => return true, // Locked!
+                    Err(s) => {
+                        state = s;
+                        continue;
+                    }
+                }
+            }
+
+            // Set the waiting bit indicating that we're waiting on it.
+            if !has_writers_waiting(state) {
+                if let Err(s) =
+                    self.state.compare_exchange(state, state | WRITERS_WAITING, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+            }
+
+            // Other writers might be waiting now too, so we should make sure
+            // we keep that bit on once we manage lock it.
+            other_writers_waiting = WRITERS_WAITING;
+
+            // Examine the notification counter before we check if `state` has changed,
+            // to make sure we don't miss any notifications.
+            let seq = self.writer_notify.load(Acquire);
+
+            // Don't go to sleep if the lock has become available,
+            // or if the writers waiting bit is no longer set.
+            state = self.state.load(Relaxed);
+            if is_unlocked(state) || !has_writers_waiting(state) {
+                continue;
+            }
+
+            // Wait for the state to change. A writer that gives up leaves the
+            // waiting bit set, which only costs a spurious wake up later on.
+            if !futex_wait_until(&self.writer_notify, seq, deadline) {
+                return false;
+            }
+
+            // Spin again after waking up.
+            state = self.spin_write();
+        }
+    }
+
+    /// Turns the upgradable read lock held by the caller into a write lock,
+    /// waiting for the other readers to unlock first.
+    pub unsafe fn upgrade(&self) {
+        let mut state = self.state.load(Relaxed);
+
+        loop {
+            // If we're the only reader left, become the writer. Upgradable
+            // readers that are waiting are woken up to wait for the write
+            // lock like any other reader.
+            if state & MASK == READ_LOCKED {
+                let new = (state & !(MASK | UPGRADABLE | UPGRADABLE_WAITING | UPGRADING))
+                    | WRITE_LOCKED;
+                match self.state.compare_exchange_weak(state, new, Acquire, Relaxed) {
+                    Ok(_) => {
+                        if state & UPGRADABLE_WAITING != 0 {
+                            self.wake_upgradable();
+                        }
+                        return;
+                    }
+                    Err(s) => {
+                        state = s;
+                        continue;
+                    }
+                }
+            }
+
+            // Keep new readers out, and have the last other reader wake us up.
+            if state & UPGRADING == 0 {
+                if let Err(s) =
+                    self.state.compare_exchange(state, state | UPGRADING, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+            }
+
+            let seq = self.upgradable_notify.load(Acquire);
+
+            state = self.state.load(Relaxed);
+            if state & MASK == READ_LOCKED {
+                continue;
+            }
+
+            futex_wait_until(&self.upgradable_notify, seq, None);
+            state = self.state.load(Relaxed);
+        }
+    }
+
+    /// Wakes up waiting threads after unlocking.
+    ///
+    /// If both are waiting, this will wake up only one writer, but will fall
+    /// back to waking up readers if there was no writer to wake up.
+    #[cold]
+    fn wake_writer_or_readers(&self, mut state: i32) {
+        assert!(is_unlocked(state));
+
+        // The readers waiting bit might be turned on at any point now,
+        // since readers will block when there's anything waiting.
+        // Writers will just lock the lock though, regardless of the waiting bits,
+        // so we don't have to worry about the writer waiting bit.
+        //
+        // If the lock gets locked in the meantime, we don't have to do
+        // anything, because then the thread that locked the lock will take
+        // care of waking up waiters when it unlocks.
+
+        // If only writers are waiting, wake one of them up.
+        if state == WRITERS_WAITING {
+            match self.state.compare_exchange(state, 0, Relaxed, Relaxed) {
+                Ok(_) => {
+                    self.wake_writer();
+                    return;
+                }
+                Err(s) => {
+                    // Maybe some readers are now waiting too. So, continue to the next `if`.
+                    state = s;
+                }
+            }
+        }
+
+        // If both writers and readers are waiting, leave the readers waiting
+        // and only wake up one writer.
+        if state == READERS_WAITING + WRITERS_WAITING {
+            if self.state.compare_exchange(state, READERS_WAITING, Relaxed, Relaxed).is_err() {
+                // The lock got locked. Not our problem anymore.
+                return;
+            }
+            if self.wake_writer() {
+                return;
+            }
+            // No writers were actually blocked on futex_wait, so we continue
+            // to wake up readers instead, since we can't be sure if we notified a writer.
+            state = READERS_WAITING;
+        }
+
+        // If readers are waiting, wake them all up.
+        if state == READERS_WAITING {
+            if self.state.compare_exchange(state, 0, Relaxed, Relaxed).is_ok() {
+                futex_wake_all(&self.state);
+            }
+        }
+    }
+
+    /// This wakes one writer and returns true if we woke up a writer that was
+    /// blocked on futex_wait.
+    ///
+    /// If this returns false, it might still be the case that we notified a
+    /// writer that was about to go to sleep.
+    fn wake_writer(&self) -> bool {
+        self.writer_notify.fetch_add(1, Release);
+        futex_wake(&self.writer_notify)
+    }
+
+    /// Wakes up everything waiting on `upgradable_notify`: the upgradable
+    /// readers waiting for the current one to go, and the upgrading one.
+    fn wake_upgradable(&self) {
+        self.upgradable_notify.fetch_add(1, Release);
+        futex_wake_all(&self.upgradable_notify);
+    }
+
+    /// Spin for a while, but stop directly at the given condition.
+    #[inline]
+    fn spin_until(&self, f: impl Fn(i32) -> bool) -> i32 {
+        let mut spin = 100; // Chosen by fair dice roll.
+        loop {
+            let state = self.state.load(Relaxed);
+            if f(state) || spin == 0 {
+                return state;
+            }
+            crate::hint::spin_loop();
+            spin -= 1;
+        }
+    }
+
+    #[inline]
+    fn spin_write(&self) -> i32 {
+        // Stop spinning when it's unlocked or when there's waiting writers,
+        // to keep things somewhat fair.
+        self.spin_until(|state| is_unlocked(state) || has_writers_waiting(state))
+    }
+
+    #[inline]
+    fn spin_read(&self) -> i32 {
+        // Stop spinning when it's unlocked or read locked, or when there's waiting threads.
+        self.spin_until(|state| {
+            !is_write_locked(state) || has_readers_waiting(state) || has_writers_waiting(state)
+        })
+    }
+}
@@ -43,6 +43,9 @@
 //! * optimizations only apply to std types. If a user adds a custom wrapper type, e.g. to report
 //!   progress, they can hit a performance cliff.
 //! * complexity
+//!
+//! `fs::copy` additionally tries to reflink the whole file with the `FICLONE` ioctl before any of
+//! the above, see `reflink`. That is not done for `io::copy` since it ignores the file cursors.
 
 use crate::cmp::min;
 use crate::convert::TryInto;
@@ -524,6 +527,48 @@ fn copy_file_range(
     CopyResult::Ended(Ok(written))
 }
 
+/// linux-specific attempt to make `writer` share the extents of `reader` via the `FICLONE` ioctl
+///
+/// This replaces the whole contents of `writer` regardless of either file's cursor, so it is only
+/// suitable when `writer` is an empty regular file that should become a copy of all of `reader`,
+/// which is what `fs::copy` does. Reflinks are only possible within a single filesystem that
+/// supports them (btrfs, xfs, ...), any error means the caller must copy the data instead.
+pub(super) fn reflink(reader: RawFd, writer: RawFd) -> Result<()> {
+    // FICLONE is _IOW(0x94, 9, int), the direction bits are encoded differently on some targets
+    #[cfg(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc64"
+    ))]
+    const FICLONE: libc::c_ulong = 0x80049409;
+    #[cfg(not(any(
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc64"
+    )))]
+    const FICLONE: libc::c_ulong = 0x40049409;
+
+    // The ioctl may be disallowed altogether, e.g. by seccomp.
+    // Kernels prior to 4.5 report ENOTTY instead, but still support the same ioctl on btrfs
+    // so that only rules out the filesystem at hand and not reflinking in general.
+    static HAS_FICLONE: AtomicBool = AtomicBool::new(true);
+
+    if !HAS_FICLONE.load(Ordering::Relaxed) {
+        return Err(Error::from_raw_os_error(libc::ENOSYS));
+    }
+    let result = cvt(unsafe { libc::ioctl(writer, FICLONE as _, reader) });
+    if let Err(ref err) = result {
+        if let Some(libc::ENOSYS | libc::EPERM) = err.raw_os_error() {
+            HAS_FICLONE.store(false, Ordering::Relaxed);
+        }
+    }
+    result.map(drop)
+}
+
 #[derive(PartialEq)]
 enum SpliceMode {
     Sendfile,
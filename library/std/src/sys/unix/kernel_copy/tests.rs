@@ -58,6 +58,44 @@ fn copy_specialization() -> Result<()> {
     result.and(rm1).and(rm2)
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn copy_batch() -> Result<()> {
+    use crate::sys::fs::CopyBatch;
+
+    let path = crate::env::temp_dir();
+    let names = ["copy-batch.a", "copy-batch.b", "copy-batch.c"];
+    let sources: Vec<_> = names.iter().map(|n| path.join(n)).collect();
+    let sinks: Vec<_> = names.iter().map(|n| path.join(format!("{}.sink", n))).collect();
+
+    let result: Result<()> = try {
+        let mut batch = CopyBatch::new();
+        for (i, (source, sink)) in sources.iter().zip(&sinks).enumerate() {
+            let content = vec![b'a' + i as u8; 1000 * i];
+            crate::fs::write(source, &content)?;
+            assert_eq!(batch.copy_file(source, sink)?, content.len() as u64);
+            assert_eq!(crate::fs::read(sink)?, content);
+        }
+
+        // copying between open files honors both cursors
+        let mut source = crate::fs::File::open(&sources[1])?;
+        source.seek(SeekFrom::Start(990))?;
+        let mut sink = OpenOptions::new().read(true).write(true).open(&sinks[2])?;
+        sink.seek(SeekFrom::Start(1995))?;
+        assert_eq!(batch.copy(&mut source, &mut sink)?, 10);
+        sink.seek(SeekFrom::Start(1990))?;
+        let mut tail = Vec::new();
+        sink.read_to_end(&mut tail)?;
+        assert_eq!(tail, b"cccccbbbbbbbbbb");
+    };
+
+    let mut result = result;
+    for path in sources.iter().chain(&sinks) {
+        result = result.and(crate::fs::remove_file(path));
+    }
+    result
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
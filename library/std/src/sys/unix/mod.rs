@@ -44,12 +44,15 @@
 pub mod android;
 pub mod args;
 pub mod cmath;
+#[cfg_attr(any(target_os = "linux", target_os = "android"), path = "futex_condvar.rs")]
 pub mod condvar;
 pub mod env;
 pub mod ext;
 pub mod fd;
 pub mod fs;
 pub mod futex;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod futex_mutex;
 pub mod io;
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub mod kernel_copy;
@@ -66,6 +69,7 @@
 pub mod pipe;
 pub mod process;
 pub mod rand;
+#[cfg_attr(any(target_os = "linux", target_os = "android"), path = "futex_rwlock.rs")]
 pub mod rwlock;
 pub mod stack_overflow;
 pub mod stdio;
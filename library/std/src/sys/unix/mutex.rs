@@ -1,21 +1,45 @@
 use crate::cell::UnsafeCell;
 use crate::mem::MaybeUninit;
 use crate::sys::cvt_nz;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use super::futex_mutex::{MovableMutex, Mutex};
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 pub struct Mutex {
     inner: UnsafeCell<libc::pthread_mutex_t>,
 }
 
-pub type MovableMutex = Box<Mutex>;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub type MovableMutex = LazyBox<Mutex>;
 
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 #[inline]
 pub unsafe fn raw(m: &Mutex) -> *mut libc::pthread_mutex_t {
     m.inner.get()
 }
 
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 unsafe impl Send for Mutex {}
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 unsafe impl Sync for Mutex {}
 
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+impl LazyInit for Mutex {
+    fn init() -> Box<Self> {
+        let mut mutex = box Mutex::new();
+        unsafe { mutex.init() };
+        mutex
+    }
+
+    unsafe fn destroy(&self) {
+        Mutex::destroy(self)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
 #[allow(dead_code)] // sys isn't exported yet
 impl Mutex {
     pub const fn new() -> Mutex {
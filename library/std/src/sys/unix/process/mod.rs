@@ -1,5 +1,7 @@
 pub use self::process_common::{Command, CommandArgs, ExitCode, Stdio, StdioPipes};
 pub use self::process_inner::{ExitStatus, Process};
+#[cfg(target_os = "linux")]
+pub use self::process_inner::pidfd_send_signal;
 pub use crate::ffi::OsString as EnvKey;
 pub use crate::sys_common::process::CommandEnvs;
 
@@ -67,3 +67,72 @@ fn test_process_mask() {
         t!(cat.wait());
     }
 }
+
+#[test]
+fn test_process_group() {
+    unsafe {
+        let mut cmd = Command::new(OsStr::new("cat"));
+        cmd.pgroup(0);
+        cmd.stdin(Stdio::MakePipe);
+        cmd.stdout(Stdio::Null);
+
+        let (mut cat, mut pipes) = t!(cmd.spawn(Stdio::Null, true));
+        let pid = cat.id() as libc::pid_t;
+        // The child leads a new process group of its own.
+        assert_eq!(t!(cvt(libc::getpgid(pid))), pid);
+        assert_ne!(pid, libc::getpgrp());
+
+        drop(pipes.stdin.take());
+        t!(cat.wait());
+    }
+}
+
+#[test]
+fn test_setsid() {
+    unsafe {
+        let mut cmd = Command::new(OsStr::new("cat"));
+        cmd.setsid(true);
+        // Ignored, because a new session also starts a new process group.
+        cmd.pgroup(libc::getpgrp());
+        cmd.stdin(Stdio::MakePipe);
+        cmd.stdout(Stdio::Null);
+
+        let (mut cat, mut pipes) = t!(cmd.spawn(Stdio::Null, true));
+        let pid = cat.id() as libc::pid_t;
+        assert_eq!(t!(cvt(libc::getsid(pid))), pid);
+        assert_eq!(t!(cvt(libc::getpgid(pid))), pid);
+
+        drop(pipes.stdin.take());
+        t!(cat.wait());
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pidfd() {
+    use crate::sys::process::pidfd_send_signal;
+
+    let mut cmd = Command::new(OsStr::new("cat"));
+    cmd.create_pidfd(true);
+    cmd.stdin(Stdio::MakePipe);
+    cmd.stdout(Stdio::Null);
+
+    let (mut cat, _pipes) = t!(cmd.spawn(Stdio::Null, true));
+    if cat.pidfd().is_none() {
+        // The kernel doesn't support pidfds, make sure killing still works.
+        t!(cat.kill());
+        t!(cat.wait());
+        return;
+    }
+
+    // Killing goes through the pidfd.
+    t!(cat.kill());
+    let status = t!(cat.wait());
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+
+    // Once reaped, the pidfd can't be used to signal anything else.
+    let pidfd = cat.take_pidfd().unwrap();
+    let err = pidfd_send_signal(pidfd.as_raw_fd(), libc::SIGKILL).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ESRCH));
+    assert!(cat.pidfd().is_none());
+}
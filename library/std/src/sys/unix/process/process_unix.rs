@@ -1,10 +1,16 @@
 use crate::convert::TryInto;
 use crate::fmt;
 use crate::io::{self, Error, ErrorKind};
+#[cfg(target_os = "linux")]
+use crate::os::linux::process::PidFd;
 use crate::ptr;
 use crate::sys;
 use crate::sys::cvt;
+#[cfg(target_os = "linux")]
+use crate::sys::fd::FileDesc;
 use crate::sys::process::process_common::*;
+#[cfg(target_os = "linux")]
+use crate::sys_common::{AsInner, FromInner};
 
 #[cfg(target_os = "vxworks")]
 use libc::RTP_ID as pid_t;
@@ -32,7 +38,8 @@ pub fn spawn(
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
-        if let Some(ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+        if let Some(mut ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+            self.open_pidfd(&mut ret);
             return Ok((ret, ours));
         }
 
@@ -78,14 +85,17 @@ pub fn spawn(
             }
         };
 
-        let mut p = Process { pid, status: None };
+        let mut p = Process::new(pid);
         drop(output);
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
         loop {
             match input.read(&mut bytes) {
-                Ok(0) => return Ok((p, ours)),
+                Ok(0) => {
+                    self.open_pidfd(&mut p);
+                    return Ok((p, ours));
+                }
                 Ok(8) => {
                     let (errno, footer) = bytes.split_at(4);
                     assert_eq!(
@@ -204,6 +214,14 @@ unsafe fn do_exec(
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
 
+        // A new session also starts a new process group, led by the child,
+        // so there is nothing left to do for `pgroup` in that case.
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        } else if let Some(pgroup) = self.get_pgroup() {
+            cvt(libc::setpgid(0, pgroup))?;
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -267,6 +285,20 @@ fn posix_spawn(
         Ok(None)
     }
 
+    #[cfg(target_os = "linux")]
+    fn open_pidfd(&self, p: &mut Process) {
+        // The child has not been reaped yet, so its pid can't have been
+        // reused and the pidfd is guaranteed to refer to it. Kernels older
+        // than 5.3 don't support pidfds, in which case the child simply
+        // doesn't get one.
+        if self.get_create_pidfd() {
+            p.pidfd = pidfd_open(p.pid).ok().map(PidFd::from_inner);
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn open_pidfd(&self, _: &mut Process) {}
+
     // Only support platforms for which posix_spawn() can return ENOENT
     // directly.
     #[cfg(any(
@@ -291,17 +323,24 @@ fn posix_spawn(
             return Ok(None);
         }
 
-        // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly.
+        // Only glibc 2.24+ posix_spawn() supports returning ENOENT directly,
+        // and only glibc 2.26+ supports POSIX_SPAWN_SETSID.
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
         {
             if let Some(version) = sys::os::glibc_version() {
-                if version < (2, 24) {
+                if version < (2, 24) || (self.get_setsid() && version < (2, 26)) {
                     return Ok(None);
                 }
             } else {
                 return Ok(None);
             }
         }
+        #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+        {
+            if self.get_setsid() {
+                return Ok(None);
+            }
+        }
 
         // Solaris, glibc 2.29+, and musl 1.24+ can set a new working directory,
         // and maybe others will gain this non-POSIX function too. We'll check
@@ -321,7 +360,7 @@ fn posix_spawn_file_actions_addchdir_np(
             None => None,
         };
 
-        let mut p = Process { pid: 0, status: None };
+        let mut p = Process::new(0);
 
         struct PosixSpawnFileActions<'a>(&'a mut MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -383,7 +422,20 @@ fn drop(&mut self) {
             cvt(sigaddset(set.as_mut_ptr(), libc::SIGPIPE))?;
             cvt_nz(libc::posix_spawnattr_setsigdefault(attrs.0.as_mut_ptr(), set.as_ptr()))?;
 
-            let flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            let mut flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            if self.get_setsid() {
+                // Only reachable on glibc, see above. The flag isn't exposed
+                // by the libc crate yet; its value is the same on every
+                // architecture glibc supports.
+                #[cfg(all(target_os = "linux", target_env = "gnu"))]
+                {
+                    const POSIX_SPAWN_SETSID: c_int = 0x80;
+                    flags |= POSIX_SPAWN_SETSID;
+                }
+            } else if let Some(pgroup) = self.get_pgroup() {
+                cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
+                flags |= libc::POSIX_SPAWN_SETPGROUP;
+            }
             cvt_nz(libc::posix_spawnattr_setflags(attrs.0.as_mut_ptr(), flags as _))?;
 
             // Make sure we synchronize access to the global `environ` resource
@@ -410,13 +462,36 @@ fn drop(&mut self) {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    // A pidfd refers to the process itself rather than to its pid, so it
+    // can't be confused with an unrelated process once the pid is reused.
+    #[cfg(target_os = "linux")]
+    pidfd: Option<PidFd>,
 }
 
 impl Process {
+    fn new(pid: pid_t) -> Process {
+        Process {
+            pid,
+            status: None,
+            #[cfg(target_os = "linux")]
+            pidfd: None,
+        }
+    }
+
     pub fn id(&self) -> u32 {
         self.pid as u32
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn pidfd(&self) -> Option<&PidFd> {
+        self.pidfd.as_ref()
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn take_pidfd(&mut self) -> Option<PidFd> {
+        self.pidfd.take()
+    }
+
     pub fn kill(&mut self) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
@@ -427,6 +502,12 @@ pub fn kill(&mut self) -> io::Result<()> {
                 "invalid argument: can't kill an exited process",
             ))
         } else {
+            #[cfg(target_os = "linux")]
+            {
+                if let Some(ref pidfd) = self.pidfd {
+                    return pidfd_send_signal(pidfd.as_inner().raw(), libc::SIGKILL);
+                }
+            }
             cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
         }
     }
@@ -457,6 +538,30 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
     }
 }
 
+#[cfg(target_os = "linux")]
+fn pidfd_open(pid: pid_t) -> io::Result<FileDesc> {
+    syscall! {
+        fn pidfd_open(pid: pid_t, flags: libc::c_uint) -> c_int
+    }
+    // pidfds are always created with `O_CLOEXEC` set.
+    let fd = cvt(unsafe { pidfd_open(pid, 0) })?;
+    Ok(FileDesc::new(fd))
+}
+
+/// Sends `signal` to the process referred to by `pidfd`.
+#[cfg(target_os = "linux")]
+pub fn pidfd_send_signal(pidfd: c_int, signal: c_int) -> io::Result<()> {
+    syscall! {
+        fn pidfd_send_signal(
+            pidfd: c_int,
+            sig: c_int,
+            info: *mut libc::siginfo_t,
+            flags: libc::c_uint
+        ) -> c_int
+    }
+    cvt(unsafe { pidfd_send_signal(pidfd, signal, ptr::null_mut(), 0) }).map(drop)
+}
+
 /// Unix exit statuses
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub struct ExitStatus(c_int);
@@ -14,7 +14,7 @@
 use crate::sys::unsupported;
 use crate::sys_common::FromInner;
 
-pub use crate::sys_common::fs::remove_dir_all;
+pub use crate::sys_common::fs::{remove_dir_all, write_atomic};
 
 pub struct File {
     fd: WasiFd,
@@ -83,6 +83,10 @@ pub fn file_type(&self) -> FileType {
         FileType { bits: self.meta.filetype }
     }
 
+    pub fn file_id(&self) -> Option<(u64, u64)> {
+        Some((self.meta.dev, self.meta.ino))
+    }
+
     pub fn modified(&self) -> io::Result<SystemTime> {
         Ok(SystemTime::from_wasi_timestamp(self.meta.mtim))
     }
@@ -392,6 +396,26 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fd.datasync()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         self.fd.filestat_set_size(size)
     }
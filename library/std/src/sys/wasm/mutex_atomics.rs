@@ -21,11 +21,6 @@ pub const fn new() -> Mutex {
         Mutex { locked: AtomicUsize::new(0) }
     }
 
-    #[inline]
-    pub unsafe fn init(&mut self) {
-        // nothing to do
-    }
-
     pub unsafe fn lock(&self) {
         while !self.try_lock() {
             // SAFETY: the caller must uphold the safety contract for `memory_atomic_wait32`.
@@ -308,6 +308,40 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_range(c::LOCKFILE_EXCLUSIVE_LOCK).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_range(0).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.lock_range(c::LOCKFILE_EXCLUSIVE_LOCK | c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.lock_range(c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    // Locks the largest possible range, which covers the file regardless of its size.
+    fn lock_range(&self, flags: c::DWORD) -> io::Result<bool> {
+        let mut overlapped: c::OVERLAPPED = unsafe { mem::zeroed() };
+        let result = cvt(unsafe {
+            c::LockFileEx(self.handle.raw(), flags, 0, !0, !0, &mut overlapped)
+        });
+        match result {
+            Ok(_) => Ok(true),
+            Err(ref e) if e.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        cvt(unsafe { c::UnlockFile(self.handle.raw(), 0, 0, !0, !0) })?;
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let mut info = c::FILE_END_OF_FILE_INFO { EndOfFile: size as c::LARGE_INTEGER };
         let size = mem::size_of_val(&info);
@@ -582,6 +616,12 @@ pub fn attrs(&self) -> u32 {
         self.attributes
     }
 
+    /// The volume serial number and file index, which together identify the
+    /// file. Only known for attributes obtained from an open handle.
+    pub fn file_id(&self) -> Option<(u64, u64)> {
+        Some((self.volume_serial_number? as u64, self.file_index?))
+    }
+
     pub fn file_type(&self) -> FileType {
         FileType::new(self.attributes, self.reparse_tag)
     }
@@ -716,6 +756,18 @@ pub fn rename(old: &Path, new: &Path) -> io::Result<()> {
     Ok(())
 }
 
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    // With `MOVEFILE_WRITE_THROUGH`, `MoveFileExW` doesn't return before the
+    // rename is on disk, so there's no directory to sync afterwards.
+    crate::sys_common::fs::write_atomic_with(path, contents, |old, new| {
+        let old = to_u16s(old)?;
+        let new = to_u16s(new)?;
+        let flags = c::MOVEFILE_REPLACE_EXISTING | c::MOVEFILE_WRITE_THROUGH;
+        cvt(unsafe { c::MoveFileExW(old.as_ptr(), new.as_ptr(), flags) })?;
+        Ok(())
+    })
+}
+
 pub fn rmdir(p: &Path) -> io::Result<()> {
     let p = to_u16s(p)?;
     cvt(unsafe { c::RemoveDirectoryW(p.as_ptr()) })?;
@@ -2,4 +2,4 @@
 // Copyright 2015 Andrew Gallant, bluss and Nicolas Koch
 
 // Fallback memchr is fastest on Windows.
-pub use core::slice::memchr::{memchr, memrchr};
+pub use crate::sys_common::memchr::{memchr, memrchr};
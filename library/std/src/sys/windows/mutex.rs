@@ -62,8 +62,6 @@ pub const fn new() -> Mutex {
             lock: AtomicUsize::new(0),
         }
     }
-    #[inline]
-    pub unsafe fn init(&mut self) {}
     pub unsafe fn lock(&self) {
         match kind() {
             Kind::SRWLock => c::AcquireSRWLockExclusive(raw(self)),
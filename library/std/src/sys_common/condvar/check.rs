@@ -1,5 +1,7 @@
 use crate::sync::atomic::{AtomicUsize, Ordering};
 use crate::sys::mutex as mutex_imp;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use crate::sys_common::lazy_box::LazyBox;
 use crate::sys_common::mutex::MovableMutex;
 
 pub trait CondvarCheck {
@@ -8,7 +10,8 @@ pub trait CondvarCheck {
 
 /// For boxed mutexes, a `Condvar` will check it's only ever used with the same
 /// mutex, based on its (stable) address.
-impl CondvarCheck for Box<mutex_imp::Mutex> {
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+impl CondvarCheck for LazyBox<mutex_imp::Mutex> {
     type Check = SameMutexCheck;
 }
 
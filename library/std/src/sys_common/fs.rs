@@ -1,8 +1,11 @@
 #![allow(dead_code)] // not used on all platforms
 
+use crate::collections::hash_map::RandomState;
+use crate::ffi::OsString;
 use crate::fs;
-use crate::io::{self, Error, ErrorKind};
-use crate::path::Path;
+use crate::hash::{BuildHasher, Hasher};
+use crate::io::{self, Error, ErrorKind, Write};
+use crate::path::{Path, PathBuf};
 
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     if !from.is_file() {
@@ -37,3 +40,71 @@ fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
     }
     fs::remove_dir(path)
 }
+
+/// Returns the directory `path` is in, to create temporary files next to it.
+pub fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if parent != Path::new("") => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Returns a randomly named path next to `path` for a temporary file.
+///
+/// A file with that name may already exist, callers must not overwrite it.
+pub fn temp_sibling(path: &Path) -> io::Result<PathBuf> {
+    let name = path.file_name().ok_or_else(|| {
+        Error::new(ErrorKind::InvalidInput, "the path does not end in a file name")
+    })?;
+    let mut temp = OsString::from(".");
+    temp.push(name);
+    temp.push(format!(".{:016x}.tmp", RandomState::new().build_hasher().finish()));
+    Ok(parent_dir(path).join(temp))
+}
+
+/// Replaces `path` by a file with the given contents by writing to a temporary
+/// file in the same directory and renaming it, keeping the permissions of the
+/// file that is replaced. The new contents are synced to disk before the
+/// rename, but the rename itself is only durable once the directory is synced,
+/// which is left to the caller.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomic_with(path, contents, |from, to| fs::rename(from, to))
+}
+
+/// Like `write_atomic`, but renames the temporary file over `path` with
+/// `rename`, for platforms that need to rename it differently than
+/// `fs::rename` does.
+pub fn write_atomic_with(
+    path: &Path,
+    contents: &[u8],
+    rename: impl FnOnce(&Path, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let (mut file, temp) = loop {
+        let temp = temp_sibling(path)?;
+        match fs::OpenOptions::new().write(true).create_new(true).open(&temp) {
+            Ok(file) => break (file, temp),
+            Err(ref e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    };
+    let result = (|| {
+        file.write_all(contents)?;
+        keep_permissions(path, &file)?;
+        file.sync_all()?;
+        drop(file);
+        rename(&temp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Gives `file` the permissions of `path` if it exists.
+pub fn keep_permissions(path: &Path, file: &fs::File) -> io::Result<()> {
+    match fs::metadata(path) {
+        Ok(metadata) => file.set_permissions(metadata.permissions()),
+        Err(ref e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
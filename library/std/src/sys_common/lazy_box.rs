@@ -0,0 +1,85 @@
+//! A box that is allocated on first use, for OS lock types that must not move
+//! once used but should still be constructible in a `const` context.
+
+use crate::marker::PhantomData;
+use crate::ops::Deref;
+use crate::ptr::null_mut;
+use crate::sync::atomic::{
+    AtomicPtr,
+    Ordering::{AcqRel, Acquire},
+};
+
+pub struct LazyBox<T: LazyInit> {
+    ptr: AtomicPtr<T>,
+    _phantom: PhantomData<T>,
+}
+
+pub trait LazyInit {
+    /// Allocates and initializes a new value. This is called on first access
+    /// to a `LazyBox`, possibly by several threads at once, in which case all
+    /// but one of the values are destroyed again right away.
+    fn init() -> Box<Self>;
+
+    /// Releases the OS resources of a value returned by `init`, before it is
+    /// freed.
+    unsafe fn destroy(&self);
+}
+
+impl<T: LazyInit> LazyBox<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ptr: AtomicPtr::new(null_mut()), _phantom: PhantomData }
+    }
+
+    #[inline]
+    fn get_pointer(&self) -> *mut T {
+        let ptr = self.ptr.load(Acquire);
+        if ptr.is_null() {
+            self.initialize()
+        } else {
+            ptr
+        }
+    }
+
+    #[cold]
+    fn initialize(&self) -> *mut T {
+        let new_ptr = Box::into_raw(T::init());
+        match self.ptr.compare_exchange(null_mut(), new_ptr, AcqRel, Acquire) {
+            Ok(_) => new_ptr,
+            Err(ptr) => {
+                // Another thread initialized the box first, so use theirs.
+                unsafe {
+                    let new = Box::from_raw(new_ptr);
+                    LazyInit::destroy(&*new);
+                }
+                ptr
+            }
+        }
+    }
+
+    /// Releases the OS resources of the value with `LazyInit::destroy`, if it
+    /// was ever initialized. The allocation itself is freed on drop.
+    pub unsafe fn destroy(&self) {
+        let ptr = self.ptr.load(Acquire);
+        if !ptr.is_null() {
+            LazyInit::destroy(&*ptr);
+        }
+    }
+}
+
+impl<T: LazyInit> Deref for LazyBox<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*self.get_pointer() }
+    }
+}
+
+impl<T: LazyInit> Drop for LazyBox<T> {
+    fn drop(&mut self) {
+        let ptr = *self.ptr.get_mut();
+        if !ptr.is_null() {
+            drop(unsafe { Box::from_raw(ptr) });
+        }
+    }
+}
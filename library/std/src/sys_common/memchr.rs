@@ -0,0 +1,54 @@
+//! `memchr` for platforms whose C library doesn't provide a vectorized one.
+//!
+//! `core` can only use the instructions the target is compiled for, which
+//! on x86 usually means SSE2. Here we can ask the CPU whether it supports
+//! AVX2 as well.
+//!
+//! This is the only place that picks a `core::slice::simd` kernel at
+//! runtime. Unix platforms use libc's `memchr` instead of this module. The
+//! `core` functions built on the other kernels, like `str::from_utf8`, can't
+//! call into `std`, so they are limited to what the target enables.
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+mod imp {
+    use core::slice::{memchr as fallback, simd};
+
+    // What `is_x86_feature_detected!("avx2")` expands to, which can't be
+    // named from inside the crate that defines it.
+    #[cfg(not(test))]
+    #[inline]
+    fn has_avx2() -> bool {
+        crate::detect::check_for(crate::detect::Feature::avx2)
+    }
+
+    #[cfg(test)]
+    #[inline]
+    fn has_avx2() -> bool {
+        realstd::is_x86_feature_detected!("avx2")
+    }
+
+    pub fn memchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+        if has_avx2() {
+            // SAFETY: we just checked that the CPU supports AVX2.
+            unsafe { simd::memchr_avx2(needle, haystack) }
+        } else {
+            fallback::memchr(needle, haystack)
+        }
+    }
+
+    pub fn memrchr(needle: u8, haystack: &[u8]) -> Option<usize> {
+        if has_avx2() {
+            // SAFETY: we just checked that the CPU supports AVX2.
+            unsafe { simd::memrchr_avx2(needle, haystack) }
+        } else {
+            fallback::memrchr(needle, haystack)
+        }
+    }
+}
+
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2"))))]
+mod imp {
+    pub use core::slice::memchr::{memchr, memrchr};
+}
+
+pub use imp::{memchr, memrchr};
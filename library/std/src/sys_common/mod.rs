@@ -53,6 +53,9 @@ macro_rules! rtunwrap {
 pub mod condvar;
 pub mod fs;
 pub mod io;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub mod lazy_box;
+pub mod memchr;
 pub mod mutex;
 // `doc` is required because `sys/mod.rs` imports `unix/ext/mod.rs` on Windows
 // when generating documentation.
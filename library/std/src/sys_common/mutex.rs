@@ -1,4 +1,7 @@
 use crate::sys::mutex as imp;
+use crate::time::Instant;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use crate::{cmp, thread, time::Duration};
 
 /// An OS-based mutual exclusion lock, meant for use in static variables.
 ///
@@ -45,25 +48,23 @@ fn drop(&mut self) {
 
 /// An OS-based mutual exclusion lock.
 ///
-/// This mutex does *not* have a const constructor, cleans up its resources in
-/// its `Drop` implementation, may safely be moved (when not borrowed), and
-/// does not cause UB when used reentrantly.
+/// This mutex has a const constructor, cleans up its resources in its `Drop`
+/// implementation, may safely be moved (when not borrowed), and does not cause
+/// UB when used reentrantly.
 ///
 /// This mutex does not implement poisoning.
 ///
-/// This is either a wrapper around `Box<imp::Mutex>` or `imp::Mutex`,
-/// depending on the platform. It is boxed on platforms where `imp::Mutex` may
-/// not be moved.
+/// This is either a wrapper around `LazyBox<imp::Mutex>` or `imp::Mutex`,
+/// depending on the platform. It is boxed on first use on platforms where
+/// `imp::Mutex` may not be moved.
 pub struct MovableMutex(imp::MovableMutex);
 
 unsafe impl Sync for MovableMutex {}
 
 impl MovableMutex {
     /// Creates a new mutex.
-    pub fn new() -> Self {
-        let mut mutex = imp::MovableMutex::from(imp::Mutex::new());
-        unsafe { mutex.init() };
-        Self(mutex)
+    pub const fn new() -> Self {
+        Self(imp::MovableMutex::new())
     }
 
     pub(super) fn raw(&self) -> &imp::Mutex {
@@ -83,6 +84,23 @@ pub fn try_lock(&self) -> bool {
         unsafe { self.0.try_lock() }
     }
 
+    /// Attempts to lock the mutex, blocking the current thread until it is
+    /// available or `deadline` has passed, returning whether it was
+    /// successfully acquired or not.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[inline]
+    pub fn try_lock_until(&self, deadline: Instant) -> bool {
+        unsafe { self.0.try_lock_until(deadline) }
+    }
+
+    /// Attempts to lock the mutex, blocking the current thread until it is
+    /// available or `deadline` has passed, returning whether it was
+    /// successfully acquired or not.
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub fn try_lock_until(&self, deadline: Instant) -> bool {
+        poll_until(deadline, || self.try_lock())
+    }
+
     /// Unlocks the mutex.
     ///
     /// Behavior is undefined if the current thread does not actually hold the
@@ -98,3 +116,24 @@ fn drop(&mut self) {
         unsafe { self.0.destroy() };
     }
 }
+
+/// Calls `try_lock` until it succeeds or `deadline` has passed, sleeping
+/// for longer and longer in between, returning whether it succeeded.
+///
+/// This is how locks wait with a timeout on platforms that can't do that
+/// natively.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn poll_until(deadline: Instant, mut try_lock: impl FnMut() -> bool) -> bool {
+    let mut backoff = Duration::from_micros(10);
+    loop {
+        if try_lock() {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        thread::sleep(cmp::min(backoff, deadline - now));
+        backoff = cmp::min(backoff * 2, Duration::from_millis(1));
+    }
+}
@@ -1,5 +1,15 @@
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use crate::sys_common::lazy_box::{LazyBox, LazyInit};
+use crate::time::Instant;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
 use crate::sys::rwlock as imp;
 
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod gated;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use self::gated as imp;
+
 /// An OS-based reader-writer lock.
 ///
 /// This structure is entirely unsafe and serves as the lowest layer of a
@@ -38,6 +48,16 @@ pub unsafe fn try_read(&self) -> bool {
         self.0.try_read()
     }
 
+    /// Attempts to acquire shared access to this lock, blocking the current
+    /// thread until `deadline` at most, returning whether it succeeded or not.
+    ///
+    /// Behavior is undefined if the rwlock has been moved between this and any
+    /// previous method call.
+    #[inline]
+    pub unsafe fn try_read_until(&self, deadline: Instant) -> bool {
+        self.0.try_read_until(deadline)
+    }
+
     /// Acquires write access to the underlying lock, blocking the current thread
     /// to do so.
     ///
@@ -60,6 +80,52 @@ pub unsafe fn try_write(&self) -> bool {
         self.0.try_write()
     }
 
+    /// Attempts to acquire exclusive access to this lock, blocking the current
+    /// thread until `deadline` at most, returning whether it succeeded or not.
+    ///
+    /// Behavior is undefined if the rwlock has been moved between this and any
+    /// previous method call.
+    #[inline]
+    pub unsafe fn try_write_until(&self, deadline: Instant) -> bool {
+        self.0.try_write_until(deadline)
+    }
+
+    /// Acquires upgradable shared access to the underlying lock, blocking the
+    /// current thread to do so.
+    ///
+    /// Upgradable access is shared with readers, but excludes writers and
+    /// other upgradable readers.
+    ///
+    /// Behavior is undefined if the rwlock has been moved between this and any
+    /// previous method call.
+    #[inline]
+    pub unsafe fn upgradable_read(&self) {
+        self.0.upgradable_read()
+    }
+
+    /// Attempts to acquire upgradable shared access to this lock, returning
+    /// whether it succeeded or not.
+    ///
+    /// This function does not block the current thread.
+    ///
+    /// Behavior is undefined if the rwlock has been moved between this and any
+    /// previous method call.
+    #[inline]
+    pub unsafe fn try_upgradable_read(&self) -> bool {
+        self.0.try_upgradable_read()
+    }
+
+    /// Turns previously acquired upgradable access into exclusive access,
+    /// blocking the current thread until the other readers are gone. No
+    /// writer can take the lock in between.
+    ///
+    /// Behavior is undefined if the current thread does not have upgradable
+    /// access. The lock is released with `write_unlock` afterwards.
+    #[inline]
+    pub unsafe fn upgrade(&self) {
+        self.0.upgrade()
+    }
+
     /// Unlocks previously acquired shared access to this lock.
     ///
     /// Behavior is undefined if the current thread does not have shared access.
@@ -77,6 +143,15 @@ pub unsafe fn write_unlock(&self) {
         self.0.write_unlock()
     }
 
+    /// Unlocks previously acquired upgradable access to this lock.
+    ///
+    /// Behavior is undefined if the current thread does not have upgradable
+    /// access.
+    #[inline]
+    pub unsafe fn upgradable_unlock(&self) {
+        self.0.upgradable_unlock()
+    }
+
     /// Destroys OS-related resources with this RWLock.
     ///
     /// Behavior is undefined if there are any currently active users of this
@@ -86,3 +161,30 @@ pub unsafe fn destroy(&self) {
         self.0.destroy()
     }
 }
+
+/// A reader-writer lock that may safely be moved (when not borrowed), and has
+/// a const constructor.
+///
+/// The futex based lock on Linux and Android doesn't care about its address,
+/// so it is used directly.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub type MovableRWLock = RWLock;
+
+/// A reader-writer lock that may safely be moved (when not borrowed), and has
+/// a const constructor.
+///
+/// The lock is boxed on first use, because the platform lock must not move
+/// once used.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub type MovableRWLock = LazyBox<RWLock>;
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+impl LazyInit for RWLock {
+    fn init() -> Box<Self> {
+        box RWLock::new()
+    }
+
+    unsafe fn destroy(&self) {
+        RWLock::destroy(self)
+    }
+}
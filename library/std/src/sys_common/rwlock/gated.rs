@@ -0,0 +1,142 @@
+use crate::mem;
+use crate::sync::atomic::{AtomicBool, Ordering};
+use crate::sys::rwlock as imp;
+use crate::sys_common::mutex::poll_until;
+use crate::time::Instant;
+
+/// A reader-writer lock with upgradable reads, for platforms whose own lock
+/// doesn't support them.
+///
+/// Readers and writers use `inner` directly. Upgradable readers additionally
+/// take `gate` for writing, so at most one of them is active at a time.
+///
+/// Upgrading sets `upgrading`, releases the read lock on `inner` and then
+/// takes the write lock on it. A writer that gets `inner` in between sees
+/// `upgrading`, releases `inner` again and waits on `gate` until the
+/// upgraded lock is released, so the upgrade still happens before any other
+/// write.
+///
+/// The timed operations are implemented by polling.
+pub struct RWLock {
+    inner: imp::RWLock,
+    gate: imp::RWLock,
+    upgrading: AtomicBool,
+}
+
+// Releases the gate if taking `inner` panics, which happens when the lock is
+// used reentrantly on some platforms.
+struct GateGuard<'a>(&'a imp::RWLock);
+
+impl Drop for GateGuard<'_> {
+    fn drop(&mut self) {
+        unsafe { self.0.write_unlock() };
+    }
+}
+
+impl RWLock {
+    pub const fn new() -> RWLock {
+        RWLock {
+            inner: imp::RWLock::new(),
+            gate: imp::RWLock::new(),
+            upgrading: AtomicBool::new(false),
+        }
+    }
+
+    #[inline]
+    pub unsafe fn read(&self) {
+        self.inner.read()
+    }
+
+    #[inline]
+    pub unsafe fn try_read(&self) -> bool {
+        self.inner.try_read()
+    }
+
+    pub unsafe fn try_read_until(&self, deadline: Instant) -> bool {
+        poll_until(deadline, || self.inner.try_read())
+    }
+
+    pub unsafe fn write(&self) {
+        loop {
+            self.inner.write();
+            // `upgrading` is only set while `inner` is read locked, so
+            // taking the write lock makes any such store visible here.
+            if !self.upgrading.load(Ordering::Relaxed) {
+                return;
+            }
+            // We got in between an upgradable reader releasing its read lock
+            // and taking the write lock. Let it go first.
+            self.inner.write_unlock();
+            self.gate.read();
+            self.gate.read_unlock();
+        }
+    }
+
+    pub unsafe fn try_write(&self) -> bool {
+        if !self.inner.try_write() {
+            return false;
+        }
+        if self.upgrading.load(Ordering::Relaxed) {
+            self.inner.write_unlock();
+            return false;
+        }
+        true
+    }
+
+    pub unsafe fn try_write_until(&self, deadline: Instant) -> bool {
+        poll_until(deadline, || self.try_write())
+    }
+
+    pub unsafe fn upgradable_read(&self) {
+        self.gate.write();
+        let gate = GateGuard(&self.gate);
+        self.inner.read();
+        mem::forget(gate);
+    }
+
+    pub unsafe fn try_upgradable_read(&self) -> bool {
+        if !self.gate.try_write() {
+            return false;
+        }
+        if self.inner.try_read() {
+            true
+        } else {
+            self.gate.write_unlock();
+            false
+        }
+    }
+
+    pub unsafe fn upgrade(&self) {
+        self.upgrading.store(true, Ordering::Relaxed);
+        self.inner.read_unlock();
+        self.inner.write();
+    }
+
+    #[inline]
+    pub unsafe fn read_unlock(&self) {
+        self.inner.read_unlock()
+    }
+
+    pub unsafe fn write_unlock(&self) {
+        // A plain writer only keeps `inner` if `upgrading` wasn't set, and it
+        // can't be set while `inner` is write locked, so if it is set now
+        // this is the write lock of an upgraded reader.
+        if self.upgrading.load(Ordering::Relaxed) {
+            self.upgrading.store(false, Ordering::Relaxed);
+            self.inner.write_unlock();
+            self.gate.write_unlock();
+        } else {
+            self.inner.write_unlock();
+        }
+    }
+
+    pub unsafe fn upgradable_unlock(&self) {
+        self.inner.read_unlock();
+        self.gate.write_unlock();
+    }
+
+    pub unsafe fn destroy(&self) {
+        self.inner.destroy();
+        self.gate.destroy();
+    }
+}
@@ -260,3 +260,89 @@ fn test_thread_id_not_equal() {
 
 // NOTE: the corresponding test for stderr is in ui/thread-stderr, due
 // to the test harness apparently interfering with stderr configuration.
+
+#[test]
+fn test_scoped_threads_borrow() {
+    let mut a = vec![1, 2, 3];
+    let mut x = 0;
+    thread::scope(|s| {
+        s.spawn(|| assert_eq!(a.len(), 3));
+        s.spawn(|| x += a[0] + a[2]);
+    });
+    a.push(4);
+    assert_eq!(x, a.len());
+}
+
+#[test]
+fn test_scoped_threads_join_returns_value() {
+    let v = vec![1, 2, 3, 4];
+    let (left, right) = v.split_at(2);
+    let sum = thread::scope(|s| {
+        let l = s.spawn(|| left.iter().sum::<i32>());
+        let r = s.spawn(|| right.iter().sum::<i32>());
+        l.join().unwrap() + r.join().unwrap()
+    });
+    assert_eq!(sum, 10);
+}
+
+#[test]
+fn test_scoped_threads_all_joined() {
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+
+    let finished = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                finished.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+    });
+    assert_eq!(finished.load(Ordering::Relaxed), 8);
+}
+
+#[test]
+fn test_scoped_threads_nested_spawn() {
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+
+    let count = AtomicUsize::new(0);
+    thread::scope(|s| {
+        s.spawn(|| {
+            s.spawn(|| count.fetch_add(1, Ordering::Relaxed));
+            count.fetch_add(1, Ordering::Relaxed);
+        });
+    });
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_scoped_threads_joined_panic_is_handled() {
+    thread::scope(|s| {
+        let t = s.spawn(|| panic!("expected panic"));
+        assert!(t.join().is_err());
+    });
+}
+
+#[test]
+fn test_scoped_threads_unjoined_panic_propagates() {
+    let result = crate::panic::catch_unwind(|| {
+        thread::scope(|s| {
+            s.spawn(|| panic!("expected panic"));
+        })
+    });
+    let payload = result.unwrap_err();
+    assert_eq!(*payload.downcast_ref::<&str>().unwrap(), "a scoped thread panicked");
+}
+
+#[test]
+fn test_scoped_threads_builder() {
+    let name = thread::scope(|s| {
+        let t = Builder::new()
+            .name("scoped".to_string())
+            .spawn_scoped(s, || thread::current().name().map(|n| n.to_string()))
+            .unwrap();
+        assert_eq!(t.thread().name(), Some("scoped"));
+        t.join().unwrap()
+    });
+    assert_eq!(name.as_deref(), Some("scoped"));
+}
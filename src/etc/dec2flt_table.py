@@ -12,6 +12,9 @@ normalized and rounded perfectly, i.e., within 0.5 ULP of the true value.
 The representation ([u64], [i16]) instead of the more natural [(u64, i16)]
 is used because (u64, i16) has a ton of padding which would make the table
 even larger, and it's already uncomfortably large (6 KiB).
+
+Also outputs the 128-bit approximations of powers of five used by the
+Eisel-Lemire algorithm, as (high, low) pairs of 64 bit words.
 """
 from __future__ import print_function
 from math import ceil, log
@@ -96,6 +99,8 @@ def main():
     print_short_powers(32, 24)
     print()
     print_short_powers(64, 53)
+    print()
+    print_powers_of_five_128()
 
 
 def print_proper_powers():
@@ -138,5 +143,40 @@ def print_short_powers(num_bits, significand_size):
     print("];")
 
 
+def print_powers_of_five_128():
+    SMALLEST = -342
+    LARGEST = 308
+    powers = []
+    for q in range(SMALLEST, LARGEST + 1):
+        if q < 0:
+            # Round the reciprocal up, so that the approximation is never
+            # too small. Small reciprocals get fewer bits before the division,
+            # which still leaves the result with at least 128 significant bits.
+            power5 = 5 ** -q
+            z = power5.bit_length()
+            b = z + 127 if q >= -27 else 2 * z + 2 * 64
+            c = 2 ** b // power5 + 1
+            while c >= 2 ** 128:
+                c //= 2
+        else:
+            # Normalize so that the most significant bit is set, and
+            # truncate what doesn't fit.
+            c = 5 ** q
+            while c < 2 ** 127:
+                c *= 2
+            while c >= 2 ** 128:
+                c //= 2
+        powers.append((c >> 64, c & (2 ** 64 - 1)))
+    print("pub const SMALLEST_POWER_OF_FIVE: i32 = {};".format(SMALLEST))
+    print("pub const LARGEST_POWER_OF_FIVE: i32 = {};".format(LARGEST))
+    print()
+    print("#[rustfmt::skip]")
+    typ = "[(u64, u64); {}]".format(len(powers))
+    print("pub static POWERS_OF_FIVE_128: ", typ, " = [", sep='')
+    for hi, lo in powers:
+        print("    (0x{:016x}, 0x{:016x}),".format(hi, lo))
+    print("];")
+
+
 if __name__ == '__main__':
     main()
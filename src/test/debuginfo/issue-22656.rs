@@ -12,7 +12,7 @@
 
 // lldb-command:print v
 // lldbg-check:[...]$0 = size=3 { [0] = 1 [1] = 2 [2] = 3 }
-// lldbr-check:(alloc::vec::Vec<i32>) v = size=3 { [0] = 1 [1] = 2 [2] = 3 }
+// lldbr-check:(alloc::vec::Vec<i32, alloc::alloc::Global>) v = size=3 { [0] = 1 [1] = 2 [2] = 3 }
 // lldb-command:print zs
 // lldbg-check:[...]$1 = { x = y = 123 z = w = 456 }
 // lldbr-check:(issue_22656::StructWithZeroSizedField) zs = { x = y = 123 z = w = 456 }
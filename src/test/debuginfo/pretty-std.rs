@@ -74,7 +74,7 @@
 // NOTE: While slices have a .natvis entry that works in VS & VS Code, it fails in CDB 10.0.18362.1
 
 // cdb-command: dx vec,d
-// cdb-check:vec,d [...] : { size=4 } [Type: [...]::Vec<u64>]
+// cdb-check:vec,d [...] : { size=4 } [Type: [...]::Vec<u64,alloc::alloc::Global>]
 // cdb-check:    [size]           : 4 [Type: [...]]
 // cdb-check:    [capacity]       : [...] [Type: [...]]
 // cdb-check:    [0]              : 4 [Type: unsigned __int64]
@@ -87,8 +87,8 @@
 // NOTE: While string slices have a .natvis entry that works in VS & VS Code, it fails in CDB
 
 // cdb-command: dx string
-// cdb-check:string           : "IAMA string!" [Type: [...]::String]
-// cdb-check:    [<Raw View>]     [Type: [...]::String]
+// cdb-check:string           : "IAMA string!" [Type: [...]::String<alloc::alloc::Global>]
+// cdb-check:    [<Raw View>]     [Type: [...]::String<alloc::alloc::Global>]
 // cdb-check:    [size]           : 0xc [Type: [...]]
 // cdb-check:    [capacity]       : 0xc [Type: [...]]
 // cdb-check:    [0]              : 73 'I' [Type: char]
@@ -113,7 +113,7 @@
 // cdb-command: dx none
 // cdb-check:none             : { None } [Type: [...]::Option<i64>]
 // cdb-command: dx some_string
-// cdb-check:some_string      : { Some "IAMA optional string!" } [[...]::Option<[...]::String>]
+// cdb-check:some_string      : { Some "IAMA optional string!" } [[...]::Option<[...]::String<alloc::alloc::Global>>]
 
 #![allow(unused_variables)]
 use std::ffi::OsString;
@@ -0,0 +1,37 @@
+// compile-flags: -Z unused-pub-items
+
+pub fn called() {}
+
+pub fn never_called() {}
+
+pub struct Constructed(pub u8);
+
+pub struct NeverConstructed;
+
+pub enum Choice {
+    Yes,
+    No,
+}
+
+impl Choice {
+    pub fn yes() -> Choice {
+        Choice::Yes
+    }
+}
+
+pub trait Implemented {
+    fn method(&self);
+}
+
+#[allow(unused_pub_items)]
+pub fn allowed() {}
+
+#[no_mangle]
+pub extern "C" fn exported_symbol() {}
+
+// Not a candidate: only public items reachable from other crates are checked.
+fn private() {}
+
+pub fn uses_private() {
+    private();
+}
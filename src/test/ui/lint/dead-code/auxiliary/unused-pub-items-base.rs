@@ -0,0 +1,5 @@
+// compile-flags: -Z unused-pub-items
+
+pub fn used_downstream() {}
+
+pub fn unused() {}
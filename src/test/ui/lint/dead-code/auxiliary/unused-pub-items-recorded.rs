@@ -0,0 +1,8 @@
+// aux-build:unused-pub-items-base.rs
+// compile-flags: -Z unused-pub-items
+
+extern crate unused_pub_items_base as base;
+
+pub fn forward() {
+    base::used_downstream();
+}
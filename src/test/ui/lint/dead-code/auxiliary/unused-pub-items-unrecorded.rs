@@ -0,0 +1,7 @@
+// aux-build:unused-pub-items-base.rs
+
+extern crate unused_pub_items_base as base;
+
+pub fn forward() {
+    base::used_downstream();
+}
@@ -0,0 +1,13 @@
+// Checks that `-Z unused-pub-items` counts the uses of an item by every crate in the
+// build, not only by the crate that builds the executable.
+
+// build-pass
+// aux-build:unused-pub-items-base.rs
+// aux-build:unused-pub-items-recorded.rs
+// compile-flags: -Z unused-pub-items
+
+extern crate unused_pub_items_recorded as recorded;
+
+fn main() {
+    recorded::forward();
+}
@@ -0,0 +1,14 @@
+// Checks that `-Z unused-pub-items` does not report the items of a crate that another
+// crate in the build depends on without recording its uses, since any of them may be
+// used there.
+
+// build-pass
+// aux-build:unused-pub-items-base.rs
+// aux-build:unused-pub-items-unrecorded.rs
+// compile-flags: -Z unused-pub-items
+
+extern crate unused_pub_items_unrecorded as unrecorded;
+
+fn main() {
+    unrecorded::forward();
+}
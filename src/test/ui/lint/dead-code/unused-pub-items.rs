@@ -0,0 +1,25 @@
+// Checks that `-Z unused-pub-items` only reports the public items of upstream crates
+// that no crate in the build uses.
+
+// build-pass
+// aux-build:unused-pub-items-aux.rs
+// compile-flags: -Z unused-pub-items
+
+extern crate unused_pub_items_aux as aux;
+
+use aux::{Choice, Implemented};
+
+struct Local;
+
+impl Implemented for Local {
+    fn method(&self) {}
+}
+
+fn main() {
+    aux::called();
+    let _ = aux::Constructed(0);
+    if let Choice::No = Choice::yes() {
+        aux::uses_private();
+    }
+    Local.method();
+}
@@ -0,0 +1,34 @@
+#![feature(core_intrinsics, rustc_attrs)]
+
+use std::intrinsics::rustc_peek;
+
+#[rustc_mir(rustc_peek_constant_locals, stop_after_dataflow)]
+fn foo(c: bool, arg: i32) -> i32 {
+    let x = 1;
+    rustc_peek(x);
+
+    // Copying a known constant propagates it...
+    let y = x;
+    rustc_peek(y);
+
+    // ... but arguments are never known.
+    rustc_peek(arg); //~ ERROR rustc_peek: not a known constant
+
+    // Paths that assign different constants join to an unknown value...
+    let z = if c { 2 } else { 3 };
+    rustc_peek(z); //~ ERROR rustc_peek: not a known constant
+
+    // ... while paths that agree keep the constant.
+    let v = if c { 4 } else { 4 };
+    rustc_peek(v);
+
+    // Locals whose address is taken may be written through a pointer.
+    let mut w = 5;
+    let p = &mut w;
+    *p = 6;
+    rustc_peek(w); //~ ERROR rustc_peek: not a known constant
+
+    x + y + z + v + w + arg
+}
+
+fn main() {}
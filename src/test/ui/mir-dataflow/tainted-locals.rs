@@ -0,0 +1,41 @@
+#![feature(core_intrinsics, rustc_attrs)]
+
+use std::intrinsics::rustc_peek;
+
+fn source() -> i32 {
+    0
+}
+
+fn sanitize(x: i32) -> i32 {
+    x
+}
+
+fn sink(_: i32) {}
+
+#[rustc_mir(rustc_peek_tainted_locals, stop_after_dataflow)]
+fn foo(c: bool) {
+    let a = source();
+    rustc_peek(a);
+
+    // Taint flows through arithmetic...
+    let b = a + 1;
+    rustc_peek(b);
+
+    // ... but not into unrelated values...
+    let clean = 2;
+    rustc_peek(clean); //~ ERROR rustc_peek: bit not set
+
+    // ... or out of sanitizers.
+    let s = sanitize(b);
+    rustc_peek(s); //~ ERROR rustc_peek: bit not set
+
+    // Data that is tainted on some path may be tainted.
+    let m = if c { a } else { clean };
+    rustc_peek(m);
+
+    sink(clean);
+    sink(s);
+    sink(m); //~ ERROR rustc_peek: tainted data reaches sink
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Check that `-Z borrowck-explain` attaches every step of the constraint path
+// to lifetime errors, including paths through a closure body or an unsizing
+// cast to a trait object. Inferred regions are named by their index, which
+// changes whenever MIR building does, so normalize it.
+//
+// compile-flags: -Z borrowck-explain
+// normalize-stderr-test "'_#\d+r" -> "'_#Nr"
+
+#![feature(nll)]
+
+fn swap_lifetimes<'a, 'b>(x: &'a u32) -> &'b u32 {
+    x //~ ERROR lifetime may not live long enough
+}
+
+fn closure_to_fn_ptr<'a, 'b>() -> fn(&'a u32, &'b u32) -> &'a u32 {
+    let g: fn(_, _) -> _ = |_x, y| y;
+    g //~ ERROR lifetime may not live long enough
+}
+
+fn to_trait_object<'a>(x: &'a u32) -> &'static dyn std::fmt::Debug {
+    x //~ ERROR lifetime may not live long enough
+}
+
+fn main() {}
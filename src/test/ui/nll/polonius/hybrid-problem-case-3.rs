@@ -0,0 +1,37 @@
+// NLL problem case #3 is rejected by NLL but accepted by Polonius. Check that
+// the default hybrid algorithm (location-insensitive pre-pass, full analysis
+// only where it finds potential errors) accepts it like the full analysis does,
+// alongside loan-free bodies for which no facts are gathered at all.
+
+// check-pass
+// revisions: hybrid datafrog
+// compile-flags: -Z borrowck=mir -Z polonius
+//[datafrog] compile-flags: -Z polonius-algorithm=DatafrogOpt
+// ignore-compare-mode-nll
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+fn get_default<'m, K, V>(map: &'m mut HashMap<K, V>, key: K) -> &'m mut V
+where
+    K: Clone + Eq + Hash,
+    V: Default,
+{
+    match map.get_mut(&key) {
+        Some(value) => value,
+        None => {
+            map.insert(key.clone(), V::default());
+            map.get_mut(&key).unwrap()
+        }
+    }
+}
+
+fn no_loans(x: u32) -> u32 {
+    let y = x + 1;
+    y * 2
+}
+
+fn main() {
+    let mut map = HashMap::new();
+    *get_default(&mut map, "a") += no_loans(1);
+}
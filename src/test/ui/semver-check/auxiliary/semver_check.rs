@@ -0,0 +1,32 @@
+// no-prefer-dynamic
+// compile-flags: -C metadata=baseline
+// The baseline must be built with a different `-C metadata` than the crate it is compared to.
+
+#![crate_type = "lib"]
+
+pub fn unchanged(x: u32) -> u32 {
+    x
+}
+
+pub fn takes_u32(x: u32) -> u32 {
+    x
+}
+
+pub fn generic<T>(x: T) -> T {
+    x
+}
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub enum Shape {
+    Circle(Point),
+}
+
+pub trait Draw {
+    fn draw(&self);
+}
+
+pub const LIMIT: u32 = 8;
@@ -0,0 +1,36 @@
+// Checks that the breaking changes reported by `-Z semver-check` can be allowed.
+
+// check-pass
+// aux-crate:baseline=semver_check.rs
+// compile-flags: -Z semver-check=baseline
+
+#![crate_name = "semver_check"]
+#![crate_type = "lib"]
+#![allow(semver_breaking_changes)]
+
+pub fn unchanged(x: u32) -> u32 {
+    x
+}
+
+pub fn takes_u32(x: u64) -> u64 {
+    x
+}
+
+pub fn generic<T>(x: T) -> T {
+    x
+}
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub enum Shape {
+    Circle(Point),
+}
+
+pub trait Draw {
+    fn draw(&self);
+}
+
+pub const LIMIT: u32 = 8;
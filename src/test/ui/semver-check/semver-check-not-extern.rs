@@ -0,0 +1,5 @@
+// compile-flags: -Z semver-check=baseline
+
+#![crate_type = "lib"]
+
+pub fn f() {}
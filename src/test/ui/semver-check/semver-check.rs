@@ -0,0 +1,44 @@
+// Checks that `-Z semver-check` reports the breaking changes against an older version of the
+// crate. The baseline is built from `auxiliary/semver_check.rs`.
+
+// aux-crate:baseline=semver_check.rs
+// compile-flags: -Z semver-check=baseline
+// dont-check-compiler-stderr
+
+#![crate_type = "lib"]
+
+pub fn unchanged(x: u32) -> u32 {
+    x
+}
+
+pub fn takes_u32(x: u64) -> u64 {
+//~^ ERROR breaking change: the type of parameter #1 of `takes_u32` changed from `u32` to `u64`
+//~| ERROR breaking change: the return type of `takes_u32` changed from `u32` to `u64`
+    x
+}
+
+pub fn generic<T: Clone>(x: T) -> T {
+//~^ ERROR breaking change: `generic` gained the bound
+    x
+}
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32, //~ ERROR breaking change: field `z` was added to `Point`
+}
+
+pub enum Shape {
+    Circle(Point),
+    Square(Point), //~ ERROR breaking change: variant `Shape::Square` was added
+}
+
+pub trait Draw {
+    fn draw(&self);
+    fn area(&self) -> f64; //~ ERROR breaking change: non-defaulted associated function
+    fn name(&self) -> &'static str {
+        "shape"
+    }
+}
+
+pub const LIMIT: u64 = 8; //~ ERROR breaking change: the type of `LIMIT` changed